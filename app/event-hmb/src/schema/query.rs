@@ -11,13 +11,14 @@ use tracing::{info, instrument};
 use tracing_subscriber::fmt::format;
 use uuid::Uuid;
 
+use gql_impl_loader_sqlx::SqlxExecutor;
 use table_traits::types::DEFAULT_CONFIG;
 use table_traits::{Cursor, CursorWrap, Field, Page, PageByCursor, Table, TableFilter, TableSorter, UuidFilter};
 
 #[derive(SimpleObject)]
 // #[derive(SimpleObject, Table)]
 #[derive(Table, FromRow)]
-#[table(schema = "public", table = "otype")]
+#[table(schema = "public", table = "otype", check(migrations = "../../deploy/migration/files/postgres-migration"))]
 pub struct TableEx {
     #[column(id, filter, sorter)]
     pub otype_id: Uuid,
@@ -34,38 +35,10 @@ pub struct Query;
 #[Object]
 impl Query {
     #[instrument(level = "info", name = "test", skip_all)]
-    async fn test<'ctx>(&self, ctx: &Context<'ctx>, page: Option<PageByCursor<TableExCursor>>, filter: TableExFilter, sorter: TableExSorter) -> Connection<CursorWrap<TableExCursor>, TableEx> {
+    async fn test<'ctx>(&self, ctx: &Context<'ctx>, page: Option<PageByCursor<TableExCursor>>, filter: TableExFilter, sorter: TableExSorter) -> async_graphql::Result<Connection<CursorWrap<TableExCursor>, TableEx>> {
         let pool = ctx.data_unchecked::<PgPool>();
         let page = page.map(|x| Page::from(x));
-        let result = DEFAULT_CONFIG
-            .context_as(Some("hello".to_string()))
-            .connection(
-                page,
-                filter,
-                sorter,
-                move |c| {
-                    info!("c : {:?}", c);
-                    async { None }
-                },
-                move |query| {
-                    let (q, v) = query.build_sqlx(sea_query::PostgresQueryBuilder);
-                    info!("q : {}", q);
-                    info!("v : {:?}", v);
-                    async move {
-                        let result = sqlx::query_scalar_with::<_, Uuid, _>(&q, v).fetch_all(pool).await.unwrap();
-                        result
-                    }
-                },
-                move |ids| {
-                    info!("ids : {:?}", ids);
-                    async move {
-                        let a = sqlx::query_as!(TableEx, "select * from otype where otype_id = any($1)", &ids).fetch_all(pool).await.unwrap();
-                        a.into_iter().map(|x| (x.otype_id, x)).collect::<HashMap<_, _>>()
-                    }
-                },
-            )
-            .await;
-        result.unwrap()
+        Ok(SqlxExecutor::new(pool).with_alias("hello").connection(page, filter, sorter).await?)
     }
     // #[instrument(level = "info", name = "test", skip_all)]
     // async fn test_by_id<'ctx>(&self, ctx: &Context<'ctx>, id: Uuid) -> Test {
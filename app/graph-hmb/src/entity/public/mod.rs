@@ -1,7 +1,9 @@
 mod otype;
 mod project;
 mod project_otype;
+mod value;
 
 pub use otype::*;
 pub use project::*;
 pub use project_otype::*;
+pub use value::*;
@@ -1,16 +1,15 @@
-use std::collections::HashMap;
-use std::convert::Infallible;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
 use async_graphql::async_trait::async_trait;
 use async_graphql::connection::{Connection, Edge, EmptyFields};
-use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
 
 use async_graphql::{ComplexObject, Context, InputObject, SimpleObject};
-use futures::future::{join_all, FutureExt};
 use itertools::Itertools;
 use sea_query::{
-    enum_def, ColumnRef, Expr, JoinType, PostgresQueryBuilder, Query, SeaRc, SimpleExpr,
+    enum_def, Alias, ColumnRef, Expr, JoinType, PostgresQueryBuilder, Query, SeaRc, SimpleExpr,
+    TableRef,
 };
 use sea_query_binder::SqlxBinder;
 use serde::{Deserialize, Serialize};
@@ -27,30 +26,56 @@ use general_table::traits::{
     FilterField, FilterTable, InsertingError, InsertingTable, SortingTable, TableDefinition,
     UpdatingTable,
 };
-use graph_guard::rebac;
+use gql_impl_loader::{AuthorizedFetcher, CachedFetcher, TableLoader};
+use gql_impl_loader_sqlx::SqlxFetcher;
+use graph_guard::{rebac, OpenFgaRowAuthorizer, RebacMode};
+use openfga_dsl::{join_errors, Model, TypeDefinition};
+use table_traits::types::DEFAULT_CONFIG;
+use table_traits::{Field, Filter, RowAccess, Sorter, Table, TableFilter, TableSorter};
 
 use crate::entity::public::{Project, ProjectOtypeRefs};
 
 #[enum_def(prefix = "", suffix = "Refs")]
-#[derive(Serialize, Deserialize, Debug, Clone, FromRow, SimpleObject)]
+#[derive(Serialize, Deserialize, Debug, Clone, FromRow, SimpleObject, Table)]
 #[graphql(complex)]
+#[table(schema = "public", table = "otype", naming(filter = "${table}TableFilter"), rebac(otype = "otype", rel = "viewer"))]
 pub struct Otype {
+    #[column(id, filter, sorter)]
     pub otype_id: Uuid,
+    #[column(filter, sorter)]
     pub name: String,
     pub description: Option<String>,
-    #[graphql(directive = rebac::apply("allow", "field", "Otype.definition"))]
+    #[graphql(directive = rebac::apply("viewer", "otype", "$parent.otype_id", RebacMode::Null))]
     pub definition: Value,
 }
 
 #[ComplexObject]
-impl Otype {}
+impl Otype {
+    /// `definition` printed as OpenFGA DSL, null when it is not a type definition
+    #[graphql(directive = rebac::apply("viewer", "otype", "$parent.otype_id", RebacMode::Null))]
+    async fn definition_dsl(&self) -> Option<String> {
+        TypeDefinition::from_json(&self.definition)
+            .ok()
+            .map(|x| x.to_string())
+    }
+}
+
+/// rows shared between requests by `CachedFetcher` are checked against the user of every request
+pub type OtypeLoader =
+    TableLoader<Otype, AuthorizedFetcher<Otype, CachedFetcher<Otype, SqlxFetcher>>>;
 
-pub struct OtypeLoader(PgPool);
+pub struct ProjectOtypeLoader {
+    pub pool: PgPool,
+    pub authorizer: OpenFgaRowAuthorizer,
+}
 
 #[derive(Debug, Default, Clone, InputObject)]
 pub struct OtypeFilter {
     pub otype_id: Option<UuidFilter>,
     pub name: Option<NameFilter>,
+    /// `Context::rebac_condition` of the request, applied with the other filters so cursor results see it too
+    #[graphql(skip)]
+    pub rebac: Option<SimpleExpr>,
 }
 
 #[derive(Debug, Default, Clone, InputObject)]
@@ -63,7 +88,7 @@ pub struct OtypeSorting {
 pub struct OtypeModifing {
     pub name: Option<String>,
     pub description: Option<String>,
-    #[graphql(directive = rebac::apply("allow", "field", "Otype.definition"))]
+    #[graphql(directive = rebac::apply("allow", "field", "Otype.definition", RebacMode::Deny))]
     pub definition: Option<Value>,
 }
 
@@ -71,6 +96,8 @@ pub struct OtypeModifing {
 pub struct OtypeCreating {
     pub name: String,
     pub description: Option<String>,
+    /// OpenFGA type definition of `name`, DSL as a string or json of the authorization model
+    #[graphql(directive = rebac::apply("allow", "field", "Otype.definition", RebacMode::Deny))]
     pub definition: Option<Value>,
 }
 
@@ -83,6 +110,7 @@ impl FilterTable for OtypeFilter {
                 .as_ref()
                 .and_then(|x| x.to_expr(OtypeRefs::OtypeId)),
             self.name.as_ref().and_then(|x| x.to_expr(OtypeRefs::Name)),
+            self.rebac.clone(),
         ]
     }
 }
@@ -116,6 +144,10 @@ impl InsertingTable for OtypeCreating {
         ctx: &Context<'ctx>,
     ) -> Result<Self::Returning, InsertingError<Self::Failure>> {
         let pool = ctx.data_unchecked::<PgPool>();
+        let definition = match &self.definition {
+            Some(definition) => Some(self.validate_definition(pool, definition).await?),
+            None => None,
+        };
         let row = sqlx::query_as!(
             Otype,
             r#"
@@ -125,7 +157,7 @@ impl InsertingTable for OtypeCreating {
             "#,
             self.name,
             self.description,
-            self.definition
+            definition
         )
         .fetch_one(pool)
         .await
@@ -133,6 +165,75 @@ impl InsertingTable for OtypeCreating {
         Ok(row)
     }
 }
+
+impl OtypeCreating {
+    /// parses `definition` and validates it against definitions of the other otypes,
+    /// returns its json to store so `definition` is always the json of the authorization model
+    async fn validate_definition(
+        &self,
+        pool: &PgPool,
+        definition: &Value,
+    ) -> Result<Value, InsertingError<String>> {
+        let otype = match definition {
+            Value::String(source) => {
+                let model =
+                    openfga_dsl::parse(source).map_err(|e| InsertingError::Throw(e.to_string()))?;
+                if !model.conditions.is_empty() {
+                    return Err(InsertingError::Throw(
+                        "conditions can't be defined by an otype".to_string(),
+                    ));
+                }
+                match <[TypeDefinition; 1]>::try_from(model.types) {
+                    Ok([otype]) => otype,
+                    Err(_) => {
+                        return Err(InsertingError::Throw(format!(
+                            "definition must define exactly one type {}",
+                            self.name
+                        )))
+                    }
+                }
+            }
+            definition => TypeDefinition::from_json(definition)
+                .map_err(|e| InsertingError::Throw(e.to_string()))?,
+        };
+        if otype.name != self.name {
+            return Err(InsertingError::Throw(format!(
+                "definition defines type {} instead of {}",
+                otype.name, self.name
+            )));
+        }
+        let others = sqlx::query_scalar!(
+            r#"select definition from public.otype where name <> $1"#,
+            self.name
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|err| InsertingError::Throw(err.to_string()))?;
+        // definitions stored before they were validated are left out of the model
+        let mut types = others
+            .iter()
+            .filter_map(|x| TypeDefinition::from_json(x).ok())
+            .collect::<Vec<_>>();
+        if !types.iter().any(|x| x.name == "user") {
+            types.push(TypeDefinition {
+                name: "user".to_string(),
+                relations: Vec::new(),
+                position: None,
+            });
+        }
+        let json = otype.to_json();
+        types.push(otype);
+        let model = Model {
+            schema_version: "1.1".to_string(),
+            types,
+            conditions: Vec::new(),
+        };
+        model
+            .validate_type(&self.name)
+            .map_err(|e| InsertingError::Throw(join_errors(&e)))?;
+        Ok(json)
+    }
+}
 //
 // impl UpdatingTable for OtypeModifing {
 //     type Table = Otype;
@@ -190,45 +291,90 @@ impl TableDefinition for Otype {
         // 필요한 데이터 로더 및 설정 정보 가져오기
         let config = ctx.data_unchecked::<GeneralTableConfig>();
         let pool = ctx.data_unchecked::<PgPool>();
-        let loader = ctx.data_unchecked::<DataLoader<Self::Loader>>();
+        let loader = ctx.data_unchecked::<DataLoader<Self::Loader, HashMapCache>>();
         // 커서 값 검증
         let cursor = cursor.validate(&config.cursor_config, &sorting)?;
+        // 조회 가능한 otype 목록 (rebac)
+        let mut context = DEFAULT_CONFIG
+            .context_as::<Self, _>(None)
+            .with_authorizer(ctx.data_unchecked::<OpenFgaRowAuthorizer>());
+        context.authorize().await?;
+        // - 권한 필터는 다른 필터와 함께 적용해 has_next, has_prev 계산에도 반영
+        let filter = OtypeFilter {
+            rebac: context.rebac_condition(),
+            ..filter
+        };
+        // 조회 가능한 otype 이 너무 많으면 id 필터 대신 행 단위로 검사,
+        // 걸러진 행만큼 다음 행을 더 읽어 limit + 1 개를 채움 (has_next 계산용)
+        let check_rows = matches!(context.access, RowAccess::CheckRows);
+        let batch = cursor
+            .limit
+            .map(|limit| limit as u64 + u64::from(check_rows));
         // select 문 동적 생성 시작
-        let (query, parames) = Query::select()
-            // 필요한 컬럼 선택
-            .tap_mut(|x| {
-                x.column(OtypeRefs::OtypeId).from(OtypeRefs::Table);
-            })
-            // where 절 동적 생성 시작
-            // - 필터 적용
-            .tap_mut(|x| {
-                filter.apply(x);
-            })
-            // - 페이지네이션 (커서 기반) 에 필요한 조건문 적용
-            .tap_mut(|x| {
-                cursor.apply(x, &sorting);
-            })
-            // order by 정렬 수행
-            .tap_mut(|x| {
-                sorting.apply(x);
-            })
-            // limit 적용
-            .tap_mut(|x| {
-                cursor.limit.tap_some(|limit| {
-                    x.limit(*limit as u64);
-                });
-            })
-            .build_sqlx(PostgresQueryBuilder);
-        // 대상 id 전체 조회
-        info!("otype find query: {}, {:?}", &query, &parames);
-        let result_ids = sqlx::query_scalar_with::<_, Uuid, _>(&query, parames)
-            .fetch_all(pool)
-            .await?;
-        let result = join_all(result_ids.into_iter().map(|otype_id| {
-            FutureExt::map(loader.load_one(otype_id), |x| x.unwrap().unwrap())
-                .map(|x| Edge::new(sorting.encode_key(&x), x))
-        }))
-        .await;
+        let select = |offset: u64| {
+            Query::select()
+                // 필요한 컬럼 선택
+                .tap_mut(|x| {
+                    x.column(OtypeRefs::OtypeId).from(OtypeRefs::Table);
+                })
+                // where 절 동적 생성 시작
+                // - 필터 적용
+                .tap_mut(|x| {
+                    filter.apply(x);
+                })
+                // - 페이지네이션 (커서 기반) 에 필요한 조건문 적용
+                .tap_mut(|x| {
+                    cursor.apply(x, &sorting);
+                })
+                // order by 정렬 수행
+                .tap_mut(|x| {
+                    sorting.apply(x);
+                })
+                // limit, offset 적용
+                .tap_mut(|x| {
+                    batch.tap_some(|batch| {
+                        x.limit(*batch);
+                    });
+                    if offset > 0 {
+                        x.offset(offset);
+                    }
+                })
+                .build_sqlx(PostgresQueryBuilder)
+        };
+        let mut loaded = Vec::new();
+        let mut offset = 0;
+        loop {
+            let (query, parames) = select(offset);
+            // 대상 id 조회
+            info!("otype find query: {}, {:?}", &query, &parames);
+            let result_ids = sqlx::query_scalar_with::<_, Uuid, _>(&query, parames)
+                .fetch_all(pool)
+                .await?;
+            let fetched = result_ids.len() as u64;
+            // OtypeLoader 가 조회할 수 없는 행을 제외
+            let mut rows = loader.load_many(result_ids.clone()).await?;
+            loaded.extend(
+                result_ids
+                    .into_iter()
+                    .filter_map(|otype_id| rows.remove(&otype_id)),
+            );
+            offset += fetched;
+            match batch {
+                Some(batch) if check_rows && fetched == batch && (loaded.len() as u64) < batch => {
+                    continue
+                }
+                _ => break,
+            }
+        }
+        let checked_has_next =
+            batch.is_some_and(|batch| check_rows && loaded.len() as u64 >= batch);
+        if let Some(limit) = cursor.limit {
+            loaded.truncate(limit as usize);
+        }
+        let result = loaded
+            .into_iter()
+            .map(|x| Edge::new(sorting.encode_key(&x), x))
+            .collect::<Vec<_>>();
         // has_next, has_prev 계산
         let (cursor_query, cursor_values) = cursor
             .prepare_cursor_result(&result, &filter, &sorting)
@@ -236,8 +382,14 @@ impl TableDefinition for Otype {
         let row = sqlx::query_with(&cursor_query, cursor_values)
             .fetch_one(pool)
             .await?;
+        // 행 단위 검사에서 커서 쿼리는 조회할 수 없는 행까지 세므로 has_next 는 채운 행으로 계산,
+        // has_prev 는 이전 페이지에 조회할 수 없는 행만 있어도 true 가 될 수 있음
         let has_prev = row.get::<bool, _>(0);
-        let has_next = row.get::<bool, _>(1);
+        let has_next = if check_rows {
+            checked_has_next
+        } else {
+            row.get::<bool, _>(1)
+        };
         // 결과물 반환
         let mut connection = Connection::new(has_prev, has_next);
         connection.edges.extend(result);
@@ -245,33 +397,9 @@ impl TableDefinition for Otype {
     }
 }
 
-#[async_trait]
-impl Loader<Uuid> for OtypeLoader {
-    type Value = Otype;
-    type Error = Infallible;
-    #[instrument(skip_all)]
-    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
-        let result = query_as!(
-            Otype,
-            r#"
-                select  *
-                from    public.otype
-                where   otype_id = any($1)
-            "#,
-            keys
-        )
-        .fetch_all(&self.0)
-        .await
-        .unwrap();
-
-        Ok(result.into_iter().map(|val| (val.otype_id, val)).collect())
-    }
-}
-
-#[async_trait]
-impl Loader<IdRef<Project, Otype>> for OtypeLoader {
+impl Loader<IdRef<Project, Otype>> for ProjectOtypeLoader {
     type Value = Vec<Otype>;
-    type Error = Infallible;
+    type Error = async_graphql::Error;
     #[instrument(skip_all)]
     async fn load(
         &self,
@@ -293,6 +421,11 @@ impl Loader<IdRef<Project, Otype>> for OtypeLoader {
             #[sqlx(flatten)]
             otype: Otype,
         }
+        // 조회 가능한 otype 목록 (rebac), otype_id 는 project_otype 에도 있으므로 otype 테이블로 한정
+        let mut context = DEFAULT_CONFIG
+            .context_as::<Otype, _>(Some("otype".to_string()))
+            .with_authorizer(&self.authorizer);
+        context.authorize().await?;
         for (group, (filter, data)) in grouped_by.into_iter() {
             // select 문 동적 생성 시작
             let (query, parames) = Query::select()
@@ -319,12 +452,25 @@ impl Loader<IdRef<Project, Otype>> for OtypeLoader {
                             .is_in(data.clone()),
                     );
                 })
+                // - 권한 필터 적용
+                .tap_mut(|x| {
+                    x.and_where_option(context.rebac_condition());
+                })
                 .build_sqlx(PostgresQueryBuilder);
             let temp = sqlx::query_as_with::<_, ProjectOtype, _>(&query, parames)
-                .fetch_all(&self.0)
-                .await
-                .unwrap();
-            for data in temp.into_iter() {
+                .fetch_all(&self.pool)
+                .await?;
+            // 조회 가능한 otype 이 너무 많으면 id 필터 대신 행 단위로 검사
+            let visible = context
+                .check_rows(temp.iter().map(|x| x.otype.clone()).collect())
+                .await?
+                .into_iter()
+                .map(|x| x.otype_id)
+                .collect::<HashSet<_>>();
+            for data in temp
+                .into_iter()
+                .filter(|x| visible.contains(&x.otype.otype_id))
+            {
                 result
                     .get_mut(&IdRef::<Project, Otype>::new(
                         group.clone(),
@@ -1,34 +1,35 @@
-use std::collections::HashMap;
-use std::convert::Infallible;
-
 use async_graphql::connection::{Connection, Edge, EmptyFields};
-use async_graphql::dataloader::{DataLoader, Loader};
-use async_graphql::futures_util::future::join_all;
-use async_graphql::futures_util::FutureExt;
+use async_graphql::dataloader::{DataLoader, HashMapCache};
 use async_graphql::{ComplexObject, Context, InputObject, SimpleObject};
 use async_trait::async_trait;
 use itertools::Itertools;
-use sea_query::{enum_def, Expr, PostgresQueryBuilder, Query, SimpleExpr};
+use sea_query::{enum_def, Alias, Expr, PostgresQueryBuilder, Query, SimpleExpr, TableRef};
 use sea_query_binder::SqlxBinder;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::types::Uuid;
-use sqlx::{query_as, FromRow, PgPool, Row};
+use sqlx::{FromRow, PgPool, Row};
 use tap::{Tap, TapOptional};
 use tracing::instrument;
 
-use crate::entity::public::{Otype, OtypeFilter, OtypeLoader};
+use crate::entity::public::{Otype, OtypeFilter, ProjectOtypeLoader};
 use general_table::config::GeneralTableConfig;
 
 use general_table::object::{Cursor, IdRef, NameFilter, UuidFilter};
 use general_table::scalar::{AscendingOption, SortDirection, SortOption};
 use general_table::traits::{FilterField, FilterTable, SortingTable, TableDefinition};
+use gql_impl_loader::TableLoader;
+use gql_impl_loader_sqlx::SqlxFetcher;
+use table_traits::{Field, Filter, Sorter, Table, TableFilter, TableSorter};
 
 #[enum_def(prefix = "", suffix = "Refs")]
-#[derive(Serialize, Deserialize, Debug, Clone, FromRow, SimpleObject)]
+#[derive(Serialize, Deserialize, Debug, Clone, FromRow, SimpleObject, Table)]
 #[graphql(complex)]
+#[table(schema = "public", table = "project", naming(filter = "${table}TableFilter"))]
 pub struct Project {
+    #[column(id, filter, sorter)]
     pub project_id: Uuid,
+    #[column(filter, sorter)]
     pub name: String,
     pub description: Option<String>,
 }
@@ -41,7 +42,7 @@ impl Project {
         ctx: &Context<'ctx>,
         filter: Option<OtypeFilter>,
     ) -> async_graphql::Result<Vec<Otype>> {
-        let loader = ctx.data_unchecked::<DataLoader<OtypeLoader>>();
+        let loader = ctx.data_unchecked::<DataLoader<ProjectOtypeLoader>>();
         let id = IdRef::<Project, Otype>::group_by_filter(ctx.path_node, self.project_id, filter);
         let result: Vec<Otype> = loader.load_one(id).await?.unwrap();
         Ok(result)
@@ -52,7 +53,7 @@ impl Project {
     }
 }
 
-pub struct ProjectLoader(PgPool);
+pub type ProjectLoader = TableLoader<Project, SqlxFetcher>;
 
 #[derive(Debug, Default, Clone, InputObject)]
 pub struct ProjectFilter {
@@ -150,7 +151,7 @@ impl TableDefinition for Project {
         // 필요한 데이터 로더 및 설정 정보 가져오기
         let config = ctx.data_unchecked::<GeneralTableConfig>();
         let pool = ctx.data_unchecked::<PgPool>();
-        let loader = ctx.data_unchecked::<DataLoader<Self::Loader>>();
+        let loader = ctx.data_unchecked::<DataLoader<Self::Loader, HashMapCache>>();
         // 커서 값 검증
         let cursor = cursor.validate(&config.cursor_config, &sorting)?;
         // select 문 동적 생성 시작
@@ -183,11 +184,12 @@ impl TableDefinition for Project {
         let result_ids = sqlx::query_scalar_with::<_, Uuid, _>(&query, parames)
             .fetch_all(pool)
             .await?;
-        let result = join_all(result_ids.into_iter().map(|id| {
-            FutureExt::map(loader.load_one(id), |x| x.unwrap().unwrap())
-                .map(|x| Edge::new(sorting.encode_key(&x), x))
-        }))
-        .await;
+        let mut loaded = loader.load_many(result_ids.clone()).await?;
+        let result = result_ids
+            .into_iter()
+            .filter_map(|id| loaded.remove(&id))
+            .map(|x| Edge::new(sorting.encode_key(&x), x))
+            .collect::<Vec<_>>();
         // has_next, has_prev 계산
         let (cursor_query, cursor_values) = cursor
             .prepare_cursor_result(&result, &filter, &sorting)
@@ -203,29 +205,3 @@ impl TableDefinition for Project {
         Ok(connection)
     }
 }
-
-#[async_trait]
-impl Loader<Uuid> for ProjectLoader {
-    type Value = Project;
-    type Error = Infallible;
-    #[instrument(skip_all)]
-    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
-        let result = query_as!(
-            Project,
-            r#"
-                select  *
-                from    public.project
-                where   project_id = any($1)
-            "#,
-            keys
-        )
-        .fetch_all(&self.0)
-        .await
-        .unwrap();
-
-        Ok(result
-            .into_iter()
-            .map(|val| (val.project_id, val))
-            .collect())
-    }
-}
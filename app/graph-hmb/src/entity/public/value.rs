@@ -0,0 +1,84 @@
+use async_graphql::{ErrorExtensions, InputObject, SimpleObject, Value};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+use sqlx::{FromRow, PgPool};
+
+use crate::service::{ValueValidator, ValueViolation};
+
+/// relationship tuple `user_name relation object_name` of a source, synced to OpenFGA by sync-value
+#[derive(Serialize, Deserialize, Debug, Clone, FromRow, SimpleObject)]
+pub struct SourceValue {
+    pub value_id: Uuid,
+    pub source_id: Uuid,
+    pub user_name: String,
+    pub relation: String,
+    pub object_name: String,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct ValueCreating {
+    /// project the source relates the otypes in, checked against `project_source` and not stored
+    pub project_id: Uuid,
+    pub source_id: Uuid,
+    /// `<otype>:<id>`, `<otype>:*` or `<otype>:<id>#<relation>`
+    pub user_name: String,
+    pub relation: String,
+    /// `<otype>:<id>`
+    pub object_name: String,
+}
+
+/// unique constraint of `user_name, relation, object_name`, see `v0.0.5_value_unique_tuple.sql`
+const VALUE_TUPLE_KEY: &str = "value_tuple_key";
+
+impl ValueCreating {
+    /// validates and inserts the value in one transaction, rows the validation read can't change before the insert commits
+    ///
+    /// violations are given as `extensions.violations` of the error, each `{code, field, message}` as in `validateValue`
+    pub async fn insert(&self, pool: &PgPool) -> async_graphql::Result<SourceValue> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("set transaction isolation level serializable")
+            .execute(&mut *tx)
+            .await?;
+        let violations = ValueValidator::new(&mut tx).validate(self).await?;
+        if !violations.is_empty() {
+            return Err(violation_error(violations));
+        }
+        let row = sqlx::query_as!(
+            SourceValue,
+            r#"
+                insert into public.value (source_id, user_name, relation, object_name)
+                values ($1, $2, $3, $4)
+                returning *
+            "#,
+            self.source_id,
+            self.user_name,
+            self.relation,
+            self.object_name
+        )
+        .fetch_one(&mut *tx)
+        .await;
+        let row = match row {
+            Err(sqlx::Error::Database(err)) if err.constraint() == Some(VALUE_TUPLE_KEY) => {
+                return Err(violation_error(vec![ValueViolation::duplicate(self)]))
+            }
+            row => row?,
+        };
+        tx.commit().await?;
+        Ok(row)
+    }
+}
+
+fn violation_error(violations: Vec<ValueViolation>) -> async_graphql::Error {
+    let message = violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    async_graphql::Error::new(message).extend_with(|_, extensions| {
+        extensions.set("code", "INVALID_VALUE");
+        extensions.set(
+            "violations",
+            Value::List(violations.iter().map(ValueViolation::to_value).collect()),
+        );
+    })
+}
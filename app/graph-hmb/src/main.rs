@@ -1,48 +1,73 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use async_graphql::dataloader::DataLoader;
 
 use async_graphql::http::{GraphiQLSource, ALL_WEBSOCKET_PROTOCOLS};
-use async_graphql::EmptySubscription;
+use async_graphql::{Data, EmptySubscription};
 use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::extract::{MatchedPath, Request, State, WebSocketUpgrade};
-use axum::http::HeaderMap;
+use axum::http::header::{AUTHORIZATION, USER_AGENT};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::routing::get;
-use axum::{response, Router};
+use axum::{response, Extension, Router};
 use axum_client_ip::{InsecureClientIp, SecureClientIpSource};
-use axum_extra::headers::authorization::Basic;
-use axum_extra::headers::Authorization;
-use axum_extra::TypedHeader;
 use opentelemetry::global;
 
 use tower_http::trace::TraceLayer;
 use tracing::{info, info_span, instrument, span, Instrument, Level};
+use sqlx::PgPool;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use general_table::config::{CursorConfig, GeneralTableConfig};
 
-use graph_guard::User;
+use gql_impl_loader::{AuthorizedFetcher, CacheConfig, CacheInvalidator, CachedFetcher, EntityCache};
+use gql_impl_loader_sqlx::{NotifySource, SqlxFetcher, NOTIFY_CHANNEL};
+use graph_guard::{ApiKeyAuthenticator, JwksSource, JwtAuthenticator, OpenFgaRowAuthorizer, User, API_KEY_HEADER};
+use openfga_client::{DecisionCache, DecisionCacheConfig, OpenFGA};
 
+use crate::entity::public::{Otype, OtypeLoader, ProjectLoader, ProjectOtypeLoader};
 use crate::schema::{InnerLoader, Mutation, Query, Schema};
+use crate::service::PgApiKeyStore;
 
 pub mod entity;
 pub mod schema;
+pub mod service;
 
+/// user of the api key in `X-Api-Key` or of the bearer token in `Authorization`, anonymous without both
+///
+/// the client the token was issued to is the agent, `User-Agent` is used otherwise
 #[instrument(skip_all)]
-fn get_token_from_headers(
+async fn get_user_from_headers(
     ip: InsecureClientIp,
-    basic_auth: Option<TypedHeader<Authorization<Basic>>>,
-    _headers: &HeaderMap,
-) -> User {
-    let id = basic_auth.map(|auth| auth.username().to_string());
-    User {
-        id,
-        ip: Some(ip.0),
-        hint: None,
-        agent: None,
+    authenticator: &JwtAuthenticator,
+    api_keys: &ApiKeyAuthenticator,
+    headers: &HeaderMap,
+) -> Result<User, graph_guard::Error> {
+    let api_key = headers.get(API_KEY_HEADER).map(|x| x.to_str().unwrap_or_default());
+    let authorization = headers
+        .get(AUTHORIZATION)
+        .map(|x| x.to_str().ok().and_then(graph_guard::bearer_token));
+    let mut user = match (api_key, authorization) {
+        (Some(key), _) => api_keys.user(key).await?,
+        (None, None) => User::default(),
+        (None, Some(None)) => {
+            return Err(graph_guard::Error::AuthMalformedToken(
+                "expected Authorization: Bearer <token>".to_string(),
+            ))
+        }
+        (None, Some(Some(token))) => authenticator.user(token).await?,
+    };
+    user.ip = Some(ip.0);
+    if user.agent.is_none() {
+        user.agent = headers
+            .get(USER_AGENT)
+            .and_then(|x| x.to_str().ok())
+            .map(ToString::to_string);
     }
+    Ok(user)
 }
 
 #[instrument(skip_all)]
@@ -50,39 +75,110 @@ async fn graphiql() -> impl IntoResponse {
     response::Html(GraphiQLSource::build().endpoint("/").finish())
 }
 
+/// entity loaders are created per request, so cached rows never outlive the request
+/// `otype` rows are additionally shared between requests through `EntityCache`, invalidated by `NotifySource`
+/// and checked against `authorizer` of the request after they are loaded
+///
+/// websocket connections keep their loaders for the whole connection, their caches are disabled so they only batch
+fn insert_loaders(data: &mut Data, pg: &PgPool, otypes: &EntityCache<Otype>, authorizer: &OpenFgaRowAuthorizer, cache: bool) {
+    let spawner = |x| tokio::spawn(x.instrument(span!(Level::INFO, "dataloader")));
+    let otype = OtypeLoader::new(AuthorizedFetcher::new(CachedFetcher::new(otypes.clone(), SqlxFetcher::new(pg.clone())), Arc::new(authorizer.clone()))).per_request(spawner);
+    let project = ProjectLoader::new(SqlxFetcher::new(pg.clone())).per_request(spawner);
+    let project_otype = DataLoader::new(ProjectOtypeLoader { pool: pg.clone(), authorizer: authorizer.clone() }, spawner);
+    otype.enable_all_cache(cache);
+    project.enable_all_cache(cache);
+    project_otype.enable_all_cache(cache);
+    data.insert(otype);
+    data.insert(project);
+    data.insert(project_otype);
+}
+
 #[instrument(skip_all)]
 async fn graphql_handler(
     State(schema): State<Schema>,
-    basic_auth: Option<TypedHeader<Authorization<Basic>>>,
+    Extension(pg): Extension<PgPool>,
+    Extension(otypes): Extension<EntityCache<Otype>>,
+    Extension(openfga): Extension<OpenFGA>,
+    Extension(authenticator): Extension<JwtAuthenticator>,
+    Extension(api_keys): Extension<ApiKeyAuthenticator>,
     ip: InsecureClientIp,
     headers: HeaderMap,
     req: GraphQLRequest,
-) -> GraphQLResponse {
-    let req = req
-        .into_inner()
-        .data(get_token_from_headers(ip, basic_auth, &headers));
-    schema.execute(req).await.into()
+) -> Response {
+    let user = match get_user_from_headers(ip, &authenticator, &api_keys, &headers).await {
+        Ok(user) => user,
+        Err(err) => return (StatusCode::UNAUTHORIZED, err.to_string()).into_response(),
+    };
+    let authorizer = OpenFgaRowAuthorizer::new(openfga, user.clone()).with_group_tuples(GROUP_OTYPE, GROUP_RELATION);
+    let mut req = req.into_inner().data(authorizer.clone()).data(user);
+    insert_loaders(&mut req.data, &pg, &otypes, &authorizer, true);
+    GraphQLResponse::from(schema.execute(req).await).into_response()
 }
 
+/// browsers can't set headers on websockets, the api key or bearer token is taken from the `connection_init` payload instead
 async fn graphql_ws_handler(
     State(schema): State<Schema>,
+    Extension(pg): Extension<PgPool>,
+    Extension(otypes): Extension<EntityCache<Otype>>,
+    Extension(openfga): Extension<OpenFGA>,
+    Extension(authenticator): Extension<JwtAuthenticator>,
+    Extension(api_keys): Extension<ApiKeyAuthenticator>,
+    ip: InsecureClientIp,
+    headers: HeaderMap,
     protocol: GraphQLProtocol,
     websocket: WebSocketUpgrade,
 ) -> Response {
+    let agent = headers
+        .get(USER_AGENT)
+        .and_then(|x| x.to_str().ok())
+        .map(ToString::to_string);
     websocket
         .protocols(ALL_WEBSOCKET_PROTOCOLS)
         .on_upgrade(move |stream| {
             GraphQLWebSocket::new(stream, schema.clone(), protocol)
-                // .on_connection_init(on_connection_init)
+                .on_connection_init(move |payload| async move {
+                    let api_key = graph_guard::connection_init_api_key(&payload);
+                    let mut user = match (api_key, graph_guard::connection_init_token(&payload)) {
+                        (Some(key), _) => api_keys.user(key).await?,
+                        (None, Some(token)) => authenticator.user(token).await?,
+                        (None, None) => User::default(),
+                    };
+                    user.ip = Some(ip.0);
+                    user.agent = user.agent.or(agent);
+                    let mut data = Data::default();
+                    let authorizer = OpenFgaRowAuthorizer::new(openfga, user.clone()).with_group_tuples(GROUP_OTYPE, GROUP_RELATION);
+                    insert_loaders(&mut data, &pg, &otypes, &authorizer, false);
+                    data.insert(authorizer);
+                    data.insert(user);
+                    Ok(data)
+                })
                 .serve()
         })
 }
 
+const OPENFGA_URL: &str = "http://openfga.auth.svc:8080";
+const OPENFGA_STORE: &str = "hmb";
+const DEX_ISSUER: &str = "https://dex.egoavara.net/";
+const DEX_JWKS_URL: &str = "https://dex.egoavara.net/keys";
+const DEX_AUDIENCE: &str = "egoavara-net";
+/// `groups` claim of the token is checked as `<principal> member group:<group>`, see `openfga/model.json`
+const GROUP_OTYPE: &str = "group";
+const GROUP_RELATION: &str = "member";
+
 #[tokio::main]
 async fn main() {
     bootstrap::tracing::init().await;
     let pg = bootstrap::postgres::init_svc().await;
-    let openfga = openfga_client::init().await;
+    let model = include_str!("../openfga/model.json").parse().expect("invalid openfga model");
+    let openfga = openfga_client::reconcile(OPENFGA_URL, OPENFGA_STORE, &model)
+        .await
+        .expect("failed to reconcile openfga store and model")
+        .with_decision_cache(DecisionCache::new(&DecisionCacheConfig::default()));
+    let authenticator = JwtAuthenticator::new(JwksSource::Url(DEX_JWKS_URL.to_string()), DEX_ISSUER, DEX_AUDIENCE);
+    let api_keys = ApiKeyAuthenticator::new(PgApiKeyStore::new(pg.clone()));
+    let otypes = EntityCache::<Otype>::new(&CacheConfig::default());
+    let invalidator = CacheInvalidator::default().with(otypes.clone());
+    tokio::spawn(NotifySource::new(pg.clone(), NOTIFY_CHANNEL).run(invalidator).instrument(span!(Level::INFO, "cache_invalidation")));
     let config = GeneralTableConfig {
         cursor_config: CursorConfig {
             default_limit: Some(100),
@@ -103,9 +199,9 @@ async fn main() {
             tokio::spawn(x.instrument(span!(Level::INFO, "dataloader")))
         }))
         .data(config)
-        .data(pg)
+        .data(pg.clone())
         // .extension(async_graphql::extensions::Tracing)
-        .extension(graph_guard::GraphGuard::new(openfga))
+        .extension(graph_guard::GraphGuard::new(openfga.clone()).with_group_tuples(GROUP_OTYPE, GROUP_RELATION))
         .finish();
 
     let app = Router::new()
@@ -140,7 +236,12 @@ async fn main() {
                 span
             }),
         )
-        .layer(SecureClientIpSource::ConnectInfo.into_extension());
+        .layer(SecureClientIpSource::ConnectInfo.into_extension())
+        .layer(Extension(pg))
+        .layer(Extension(otypes))
+        .layer(Extension(openfga))
+        .layer(Extension(authenticator))
+        .layer(Extension(api_keys));
 
     println!("GraphiQL IDE: http://localhost:8000");
 
@@ -1,9 +1,11 @@
 use async_graphql::{Context, Object};
+use sqlx::PgPool;
 use tracing::instrument;
 
 use general_table::utils::TableInserter;
+use graph_guard::{rebac, RebacMode};
 
-use crate::entity::public::OtypeCreating;
+use crate::entity::public::{OtypeCreating, SourceValue, ValueCreating};
 
 pub struct Mutation;
 
@@ -17,4 +19,15 @@ impl Mutation {
     ) -> TableInserter<OtypeCreating> {
         TableInserter::new(args)
     }
+
+    /// inserts a value of a source once `validateValue` finds no violation, `writer` of the source only
+    #[instrument(level = "info", name = "create_value", skip_all)]
+    #[graphql(directive = rebac::apply("writer", "source", "$args.args.sourceId", RebacMode::Deny))]
+    async fn create_value<'a>(
+        &self,
+        ctx: &Context<'a>,
+        args: ValueCreating,
+    ) -> async_graphql::Result<SourceValue> {
+        args.insert(ctx.data_unchecked::<PgPool>()).await
+    }
 }
@@ -1,4 +1,5 @@
 use crate::entity;
+use crate::service::{ValueValidation, ValueValidator};
 
 use async_graphql::connection::Connection;
 use async_graphql::dataloader::{DataLoader, Loader};
@@ -9,6 +10,7 @@ use general_table::traits::TableDefinition;
 
 use std::collections::HashMap;
 use std::convert::Infallible;
+use sqlx::PgPool;
 use tracing::instrument;
 
 
@@ -60,6 +62,17 @@ impl Query {
         .unwrap()
     }
 
+    /// every violation `createValue` would reject `input` for
+    #[instrument(level = "info", name = "validate_value", skip_all)]
+    async fn validate_value<'a>(
+        &self,
+        ctx: &Context<'a>,
+        input: entity::public::ValueCreating,
+    ) -> async_graphql::Result<ValueValidation> {
+        let mut conn = ctx.data_unchecked::<PgPool>().acquire().await?;
+        Ok(ValueValidator::new(&mut conn).validate(&input).await?.into())
+    }
+
     async fn find_test<'ctx>(&self, _ctx: &Context<'ctx>, names: Vec<String>) -> Vec<Test> {
         names.into_iter().map(|name| Test { name }).collect()
     }
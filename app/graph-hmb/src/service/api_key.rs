@@ -0,0 +1,47 @@
+use async_graphql::async_trait::async_trait;
+use graph_guard::{ApiKey, ApiKeyStore, Error, Principal};
+use sqlx::PgPool;
+
+/// `api_key` rows, revoked and expired keys are filtered out by the query
+#[derive(Debug, Clone)]
+pub struct PgApiKeyStore {
+    pool: PgPool,
+}
+
+impl PgApiKeyStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for PgApiKeyStore {
+    async fn find_api_key(&self, hash: &str) -> Result<Option<ApiKey>, Error> {
+        let row = sqlx::query!(
+            r#"
+                select api_key_id, name, principal
+                from public.api_key
+                where key_hash = $1
+                  and revoked_at is null
+                  and (expires_at is null or expires_at > now())
+            "#,
+            hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::AuthUnavailableApiKeys(err.to_string()))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let api_key_id = row.api_key_id.to_string();
+        let principal = match row.principal {
+            Some(principal) => principal.parse()?,
+            None => Principal::ApiKey(api_key_id.clone()),
+        };
+        Ok(Some(ApiKey {
+            api_key_id,
+            principal,
+            name: Some(row.name),
+        }))
+    }
+}
@@ -0,0 +1,5 @@
+mod api_key;
+mod value;
+
+pub use api_key::*;
+pub use value::*;
@@ -0,0 +1,506 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use async_graphql::indexmap::IndexMap;
+use async_graphql::{Enum, InputType, Name, SimpleObject, Value};
+use openfga_dsl::{RestrictionKind, TypeDefinition};
+use sqlx::types::Uuid;
+use sqlx::PgConnection;
+
+use crate::entity::public::ValueCreating;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum ValueViolationCode {
+    /// `user_name` is not `<otype>:<id>`, `<otype>:*` or `<otype>:<id>#<relation>`
+    InvalidUser,
+    /// `object_name` is not `<otype>:<id>`
+    InvalidObject,
+    UnknownSource,
+    UnknownUserOtype,
+    UnknownObjectOtype,
+    /// `source_assignable` has no row of the source, user otype and object otype
+    NotAssignable,
+    /// `project_source` has no row of the project, user otype, object otype and source
+    NotInProject,
+    /// `definition` of the object otype is not an OpenFGA type definition
+    InvalidDefinition,
+    UnknownRelation,
+    /// the relation does not accept the user otype as a direct assignment
+    UserNotAllowed,
+    /// another value of any source is the same tuple, OpenFGA stores a tuple once
+    Duplicate,
+}
+
+/// reason a value can't be inserted, `field` is the field of `ValueCreating` it concerns
+#[derive(Debug, Clone, PartialEq, Eq, SimpleObject)]
+pub struct ValueViolation {
+    pub code: ValueViolationCode,
+    pub field: String,
+    pub message: String,
+}
+
+impl ValueViolation {
+    fn new(code: ValueViolationCode, field: &str, message: String) -> Self {
+        Self {
+            code,
+            field: field.to_string(),
+            message,
+        }
+    }
+
+    pub fn duplicate(value: &ValueCreating) -> Self {
+        Self::new(
+            ValueViolationCode::Duplicate,
+            "objectName",
+            format!(
+                "{} {} {} is already a value",
+                value.user_name, value.relation, value.object_name
+            ),
+        )
+    }
+
+    /// `{code, field, message}` as in `validateValue`, for error extensions
+    pub fn to_value(&self) -> Value {
+        Value::Object(IndexMap::from([
+            (Name::new("code"), self.code.to_value()),
+            (Name::new("field"), Value::from(self.field.as_str())),
+            (Name::new("message"), Value::from(self.message.as_str())),
+        ]))
+    }
+}
+
+impl Display for ValueViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ValueValidation {
+    pub valid: bool,
+    pub violations: Vec<ValueViolation>,
+}
+
+impl From<Vec<ValueViolation>> for ValueValidation {
+    fn from(violations: Vec<ValueViolation>) -> Self {
+        Self {
+            valid: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
+/// user of a tuple, `<otype>:<id>`, `<otype>:*` or `<otype>:<id>#<relation>`
+struct UserName<'a> {
+    otype: &'a str,
+    kind: RestrictionKind,
+}
+
+fn parse_user(user_name: &str) -> Option<UserName<'_>> {
+    let (otype, id) = user_name.split_once(':')?;
+    let (id, kind) = match id.split_once('#') {
+        Some((id, relation)) if !relation.is_empty() => {
+            (id, RestrictionKind::Userset(relation.to_string()))
+        }
+        Some(_) => return None,
+        None if id == "*" => (id, RestrictionKind::Wildcard),
+        None => (id, RestrictionKind::Type),
+    };
+    (!otype.is_empty() && !id.is_empty()).then_some(UserName { otype, kind })
+}
+
+/// otype of an object `<otype>:<id>`
+fn parse_object(object_name: &str) -> Option<&str> {
+    let (otype, id) = object_name.split_once(':')?;
+    (!otype.is_empty() && !id.is_empty() && id != "*" && !id.contains('#')).then_some(otype)
+}
+
+/// checks a value against `source_assignable`, `project_source` and the definition of its object otype
+///
+/// every violation is collected, checks depending on an unknown otype are skipped
+///
+/// runs on a connection so `createValue` validates and inserts in one transaction
+pub struct ValueValidator<'a> {
+    conn: &'a mut PgConnection,
+}
+
+impl<'a> ValueValidator<'a> {
+    pub fn new(conn: &'a mut PgConnection) -> Self {
+        Self { conn }
+    }
+
+    pub async fn validate(
+        &mut self,
+        value: &ValueCreating,
+    ) -> Result<Vec<ValueViolation>, sqlx::Error> {
+        use ValueViolationCode::*;
+        let mut violations = Vec::new();
+        let user = parse_user(&value.user_name);
+        if user.is_none() {
+            violations.push(ValueViolation::new(
+                InvalidUser,
+                "userName",
+                format!(
+                    "{:?} is not <otype>:<id>, <otype>:* or <otype>:<id>#<relation>",
+                    value.user_name
+                ),
+            ));
+        }
+        let object_otype = parse_object(&value.object_name);
+        if object_otype.is_none() {
+            violations.push(ValueViolation::new(
+                InvalidObject,
+                "objectName",
+                format!("{:?} is not <otype>:<id>", value.object_name),
+            ));
+        }
+        let source_exists = sqlx::query_scalar!(
+            r#"select exists(select 1 from public.source where source_id = $1) as "exists!""#,
+            value.source_id
+        )
+        .fetch_one(&mut *self.conn)
+        .await?;
+        if !source_exists {
+            violations.push(ValueViolation::new(
+                UnknownSource,
+                "sourceId",
+                format!("source {} does not exist", value.source_id),
+            ));
+        }
+        let duplicate = sqlx::query_scalar!(
+            r#"
+                select exists(
+                    select 1 from public.value
+                    where user_name = $1 and relation = $2 and object_name = $3
+                ) as "exists!"
+            "#,
+            value.user_name,
+            value.relation,
+            value.object_name
+        )
+        .fetch_one(&mut *self.conn)
+        .await?;
+        if duplicate {
+            violations.push(ValueViolation::duplicate(value));
+        }
+
+        let names = user
+            .iter()
+            .map(|x| x.otype.to_string())
+            .chain(object_otype.map(str::to_string))
+            .collect::<Vec<_>>();
+        let otypes = sqlx::query!(
+            r#"select otype_id, name, definition from public.otype where name = any($1)"#,
+            &names
+        )
+        .fetch_all(&mut *self.conn)
+        .await?
+        .into_iter()
+        .map(|x| (x.name, (x.otype_id, x.definition)))
+        .collect::<HashMap<_, _>>();
+        let user_otype = user.as_ref().and_then(|user| {
+            let found = otypes.get(user.otype);
+            if found.is_none() {
+                violations.push(ValueViolation::new(
+                    UnknownUserOtype,
+                    "userName",
+                    format!("otype {} does not exist", user.otype),
+                ));
+            }
+            found.map(|(id, _)| *id)
+        });
+        let object = object_otype.and_then(|otype| {
+            let found = otypes.get(otype);
+            if found.is_none() {
+                violations.push(ValueViolation::new(
+                    UnknownObjectOtype,
+                    "objectName",
+                    format!("otype {} does not exist", otype),
+                ));
+            }
+            found
+        });
+
+        if let (Some(user_otype_id), Some((object_otype_id, _))) = (user_otype, object) {
+            violations.extend(
+                self.assignable(value, user_otype_id, *object_otype_id, source_exists)
+                    .await?,
+            );
+        }
+        if let (Some(object_otype), Some((_, definition))) = (object_otype, object) {
+            violations.extend(relation_violations(
+                object_otype,
+                definition,
+                &value.relation,
+                user.as_ref().filter(|_| user_otype.is_some()),
+            ));
+        }
+        Ok(violations)
+    }
+
+    /// `source_assignable` and `project_source` of the otypes
+    async fn assignable(
+        &mut self,
+        value: &ValueCreating,
+        user_otype_id: Uuid,
+        object_otype_id: Uuid,
+        source_exists: bool,
+    ) -> Result<Vec<ValueViolation>, sqlx::Error> {
+        let mut violations = Vec::new();
+        if !source_exists {
+            return Ok(violations);
+        }
+        let row = sqlx::query!(
+            r#"
+                select
+                    exists(
+                        select 1 from public.source_assignable
+                        where source_id = $1 and user_otype_id = $2 and object_otype_id = $3
+                    ) as "assignable!",
+                    exists(
+                        select 1 from public.project_source
+                        where project_id = $4 and source_id = $1 and user_otype_id = $2 and object_otype_id = $3
+                    ) as "in_project!"
+            "#,
+            value.source_id,
+            user_otype_id,
+            object_otype_id,
+            value.project_id
+        )
+        .fetch_one(&mut *self.conn)
+        .await?;
+        if !row.assignable {
+            violations.push(ValueViolation::new(
+                ValueViolationCode::NotAssignable,
+                "sourceId",
+                format!(
+                    "source {} can't relate {} to {}",
+                    value.source_id, value.user_name, value.object_name
+                ),
+            ));
+        }
+        if !row.in_project {
+            violations.push(ValueViolation::new(
+                ValueViolationCode::NotInProject,
+                "projectId",
+                format!(
+                    "project {} does not relate {} to {} by source {}",
+                    value.project_id, value.user_name, value.object_name, value.source_id
+                ),
+            ));
+        }
+        Ok(violations)
+    }
+}
+
+/// the relation exists in `definition` and accepts `user` directly
+fn relation_violations(
+    object_otype: &str,
+    definition: &serde_json::Value,
+    relation: &str,
+    user: Option<&UserName<'_>>,
+) -> Vec<ValueViolation> {
+    let definition = match TypeDefinition::from_json(definition) {
+        Ok(definition) => definition,
+        Err(err) => {
+            return vec![ValueViolation::new(
+                ValueViolationCode::InvalidDefinition,
+                "objectName",
+                format!("definition of otype {} is invalid, {}", object_otype, err),
+            )]
+        }
+    };
+    let Some(found) = definition.relation(relation) else {
+        return vec![ValueViolation::new(
+            ValueViolationCode::UnknownRelation,
+            "relation",
+            format!("otype {} has no relation {}", object_otype, relation),
+        )];
+    };
+    let Some(user) = user else {
+        return Vec::new();
+    };
+    let allowed = found.rewrite.direct();
+    if allowed
+        .iter()
+        .any(|x| x.otype == user.otype && x.kind == user.kind)
+    {
+        return Vec::new();
+    }
+    let allowed = allowed.iter().map(ToString::to_string).collect::<Vec<_>>();
+    vec![ValueViolation::new(
+        ValueViolationCode::UserNotAllowed,
+        "userName",
+        if allowed.is_empty() {
+            format!(
+                "relation {} of otype {} can't be assigned directly",
+                relation, object_otype
+            )
+        } else {
+            format!(
+                "relation {} of otype {} accepts [{}] only",
+                relation,
+                object_otype,
+                allowed.join(", ")
+            )
+        },
+    )]
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const MODEL: &str = r#"model
+  schema 1.1
+
+type user
+
+type team
+  relations
+    define member: [user]
+
+type document
+  relations
+    define owner: [user]
+    define viewer: [user, user:*, team#member] or owner
+    define can_edit: owner
+"#;
+
+    fn document() -> serde_json::Value {
+        openfga_dsl::parse(MODEL)
+            .unwrap()
+            .types
+            .into_iter()
+            .find(|x| x.name == "document")
+            .unwrap()
+            .to_json()
+    }
+
+    fn user(user_name: &str) -> Option<(&str, RestrictionKind)> {
+        parse_user(user_name).map(|x| (x.otype, x.kind))
+    }
+
+    /// codes of violations of `relation` of `document` by `user_name`
+    fn violations(relation: &str, user_name: Option<&str>) -> Vec<ValueViolationCode> {
+        let user = user_name.map(|x| parse_user(x).unwrap());
+        relation_violations("document", &document(), relation, user.as_ref())
+            .into_iter()
+            .map(|x| x.code)
+            .collect()
+    }
+
+    #[test]
+    fn parse_users() {
+        assert_eq!(user("user:anne"), Some(("user", RestrictionKind::Type)));
+        assert_eq!(user("user:*"), Some(("user", RestrictionKind::Wildcard)));
+        assert_eq!(
+            user("team:product#member"),
+            Some(("team", RestrictionKind::Userset("member".to_string())))
+        );
+        // ids may contain `:`, only the first one separates the otype
+        assert_eq!(
+            user("user:auth0|anne:1"),
+            Some(("user", RestrictionKind::Type))
+        );
+        for invalid in [
+            "anne",
+            ":anne",
+            "user:",
+            "team:product#",
+            "team:#member",
+            "",
+        ] {
+            assert_eq!(user(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn parse_objects() {
+        assert_eq!(parse_object("document:roadmap"), Some("document"));
+        assert_eq!(parse_object("document:2024:q1"), Some("document"));
+        for invalid in [
+            "roadmap",
+            ":roadmap",
+            "document:",
+            "document:*",
+            "team:product#member",
+            "",
+        ] {
+            assert_eq!(parse_object(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn relation_accepts_user() {
+        use ValueViolationCode::*;
+        assert_eq!(violations("viewer", Some("user:anne")), vec![]);
+        assert_eq!(violations("viewer", Some("user:*")), vec![]);
+        assert_eq!(violations("viewer", Some("team:product#member")), vec![]);
+        assert_eq!(violations("owner", Some("user:anne")), vec![]);
+        assert_eq!(
+            violations("viewer", Some("team:product")),
+            vec![UserNotAllowed]
+        );
+        assert_eq!(violations("owner", Some("user:*")), vec![UserNotAllowed]);
+        assert_eq!(
+            violations("viewr", Some("user:anne")),
+            vec![UnknownRelation]
+        );
+        // the user otype is unknown, only the relation is checked
+        assert_eq!(violations("viewer", None), vec![]);
+        assert_eq!(violations("viewr", None), vec![UnknownRelation]);
+    }
+
+    #[test]
+    fn relation_violation_messages() {
+        let team = parse_user("team:product").unwrap();
+        let messages = |relation: &str| {
+            relation_violations("document", &document(), relation, Some(&team))
+                .into_iter()
+                .map(|x| (x.field, x.message))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages("viewer"),
+            vec![(
+                "userName".to_string(),
+                "relation viewer of otype document accepts [user, user:*, team#member] only"
+                    .to_string()
+            )]
+        );
+        assert_eq!(
+            messages("can_edit"),
+            vec![(
+                "userName".to_string(),
+                "relation can_edit of otype document can't be assigned directly".to_string()
+            )]
+        );
+        assert_eq!(
+            messages("viewr"),
+            vec![(
+                "relation".to_string(),
+                "otype document has no relation viewr".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_definition() {
+        let violations =
+            relation_violations("document", &json!({ "relations": 1 }), "viewer", None);
+        assert_eq!(
+            violations
+                .into_iter()
+                .map(|x| (x.code, x.field))
+                .collect::<Vec<_>>(),
+            vec![(
+                ValueViolationCode::InvalidDefinition,
+                "objectName".to_string()
+            )]
+        );
+    }
+}
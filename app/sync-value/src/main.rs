@@ -0,0 +1,34 @@
+use openfga_client::OpenFGA;
+use tracing::info;
+use value_sync::{KafkaDeadLetter, KafkaSource, ValueSync};
+
+const OPENFGA_URL: &str = "http://openfga.auth.svc:8080";
+const OPENFGA_STORE: &str = "hmb";
+
+const KAFKA_BROKERS: &str = "kafka-svc.event-system.svc:9092";
+/// instances share the group, partitions of the topic are split between them
+const KAFKA_GROUP_ID: &str = "sync-value";
+/// debezium postgres connector topic `{topic_prefix}.{schema}.{table}`
+const VALUE_TOPIC: &str = "postgres.public.value";
+const DEAD_LETTER_TOPIC: &str = "sync-value.dead-letter";
+
+#[tokio::main]
+async fn main() {
+    bootstrap::tracing::init().await;
+    // graph-hmb reconciles the model, tuples are written against its newest model at startup
+    let openfga = OpenFGA::new(OPENFGA_URL, String::new(), String::new());
+    let store = openfga.reconcile_store(OPENFGA_STORE).await.expect("failed to reconcile openfga store");
+    let openfga = openfga.with_store(store.id);
+    let model = openfga
+        .latest_authorization_model()
+        .await
+        .expect("failed to read openfga model")
+        .and_then(|x| x.id)
+        .expect("openfga store has no model, graph-hmb writes it on startup");
+    let openfga = openfga.with_authorization_model_id(model);
+
+    let mut source = KafkaSource::new(KAFKA_BROKERS, KAFKA_GROUP_ID, VALUE_TOPIC).expect("failed to create kafka consumer");
+    let dead_letter = KafkaDeadLetter::new(KAFKA_BROKERS, DEAD_LETTER_TOPIC).expect("failed to create kafka producer");
+    info!("Starting up");
+    ValueSync::new(openfga, dead_letter).run(&mut source).await;
+}
@@ -0,0 +1,42 @@
+-- publishes primary key of changed row on channel `table_change`, consumed by gql-impl-loader caches
+-- trigger arguments are primary key columns, payload stays small regardless of row size (NOTIFY limit is 8000 bytes)
+create or replace function notify_table_change() returns trigger as
+$$
+declare
+    new_key     jsonb := '{}'::jsonb;
+    old_key     jsonb := '{}'::jsonb;
+    column_name text;
+begin
+    foreach column_name in array tg_argv
+        loop
+            if tg_op <> 'DELETE' then
+                new_key := new_key || jsonb_build_object(column_name, to_jsonb(new) -> column_name);
+            end if;
+            if tg_op <> 'INSERT' then
+                old_key := old_key || jsonb_build_object(column_name, to_jsonb(old) -> column_name);
+            end if;
+        end loop;
+    if tg_op <> 'DELETE' then
+        perform pg_notify('table_change', jsonb_build_object('schema', tg_table_schema, 'table', tg_table_name, 'key', new_key)::text);
+    end if;
+    -- delete, or update of primary key
+    if tg_op <> 'INSERT' and old_key is distinct from new_key then
+        perform pg_notify('table_change', jsonb_build_object('schema', tg_table_schema, 'table', tg_table_name, 'key', old_key)::text);
+    end if;
+    return null;
+end;
+$$ language plpgsql;
+
+drop trigger if exists otype_notify_table_change on otype;
+create trigger otype_notify_table_change
+    after insert or update or delete
+    on otype
+    for each row
+execute function notify_table_change('otype_id');
+
+drop trigger if exists project_notify_table_change on project;
+create trigger project_notify_table_change
+    after insert or update or delete
+    on project
+    for each row
+execute function notify_table_change('project_id');
@@ -0,0 +1,2 @@
+-- debezium sends the whole old row of updates and deletes, sync-value deletes the old tuple of `value` by it
+alter table value replica identity full;
@@ -0,0 +1,14 @@
+-- api keys of batch jobs and workers, only the sha-256 hex of a key is stored
+-- `principal` is the openfga user the key acts as, `service:<id>` or `<otype>:<id>#<relation>`, `api_key:<api_key_id>` when null
+create table if not exists api_key
+(
+    api_key_id uuid          not null default uuid_generate_v4(),
+    name       varchar(1024) not null,
+    key_hash   char(64)      not null,
+    principal  varchar       null,
+    created_at timestamptz   not null default now(),
+    expires_at timestamptz   null,
+    revoked_at timestamptz   null,
+    primary key (api_key_id),
+    unique (key_hash)
+);
@@ -0,0 +1,4 @@
+-- a tuple is stored once in openfga, deleting one of several rows of the same tuple would delete the tuple of the others
+-- fails while duplicates exist, they have to be merged by hand as deleting them deletes their tuple through sync-value
+alter table value
+    add constraint value_tuple_key unique (user_name, relation, object_name);
@@ -1,14 +1,240 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
+use std::collections::HashMap;
+
+use async_graphql::connection::Connection;
+use async_graphql::OutputType;
+use async_trait::async_trait;
+use sea_query::{Asterisk, Expr, PostgresQueryBuilder, Query, SelectStatement, ValueTuple};
+use sea_query_binder::SqlxBinder;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool, Postgres};
+use tracing::debug;
+
+use gql_impl_loader::TableFetcher;
+use table_traits::types::{Config, ConnectionError, Context, DEFAULT_CONFIG};
+use table_traits::{CursorWrap, Field, Page, RowAuthorizer, Table};
+
+pub use notify::*;
+
+mod notify;
+
+#[derive(thiserror::Error, Debug)]
+pub enum SqlxLoaderError {
+    #[error(transparent)]
+    ConnectionError(#[from] ConnectionError),
+    #[error(transparent)]
+    SqlxError(#[from] sqlx::Error),
+    #[error("table `{table}` has {count} id columns, connection supports single id column only")]
+    CompositeId { table: String, count: usize },
+}
+
+/// runs `Context::connection` against postgres
+///
+/// first query selects ids matching filter, sorter and page, second loads rows by `id = ANY($1)`
+///
+/// ```ignore
+/// async fn otypes(&self, ctx: &Context<'_>, page: Option<PageByCursor<OtypeCursor>>, filter: OtypeFilter, sorter: OtypeSorter) -> Result<Connection<CursorWrap<OtypeCursor>, Otype>> {
+///     Ok(SqlxExecutor::new(ctx.data_unchecked::<PgPool>()).connection(page.map(Page::from), filter, sorter).await?)
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct SqlxExecutor<'a> {
+    pool: &'a PgPool,
+    config: &'a Config,
+    alias: Option<&'a str>,
+    authorizer: Option<&'a dyn RowAuthorizer>,
+}
+
+impl<'a> SqlxExecutor<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        SqlxExecutor {
+            pool,
+            config: &DEFAULT_CONFIG,
+            alias: None,
+            authorizer: None,
+        }
+    }
+
+    pub fn with_config(self, config: &'a Config) -> Self {
+        SqlxExecutor { config, ..self }
+    }
+
+    /// table alias of id query
+    pub fn with_alias(self, alias: &'a str) -> Self {
+        SqlxExecutor { alias: Some(alias), ..self }
+    }
+
+    /// principal of tables with `#[table(rebac(..))]`, connection of those tables fails without it
+    pub fn with_authorizer(self, authorizer: &'a dyn RowAuthorizer) -> Self {
+        SqlxExecutor { authorizer: Some(authorizer), ..self }
+    }
+
+    pub async fn connection<T>(&self, page: Option<Page<T::Cursor>>, filter: T::Filter, sorter: T::Sorter) -> Result<Connection<CursorWrap<T::Cursor>, T>, SqlxLoaderError>
+    where
+        T: Table + OutputType + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+        T::Id: for<'r> sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres> + Send + Unpin,
+    {
+        let id_count = T::id_fields().len();
+        if id_count != 1 {
+            return Err(SqlxLoaderError::CompositeId { table: T::descriptor().name, count: id_count });
+        }
+        self.context::<T>().connection(page, filter, sorter, |_| async { Ok(None) }, |query| self.fetch_ids::<T>(query), |ids| self.fetch_by_ids::<T>(ids)).await
+    }
+
+    fn context<T: Table>(&self) -> Context<'a, T> {
+        let context = self.config.context_as::<T, _>(self.alias.map(|x| x.to_string()));
+        match self.authorizer {
+            | Some(authorizer) => context.with_authorizer(authorizer),
+            | None => context,
+        }
+    }
+
+    pub async fn fetch_ids<T>(&self, query: SelectStatement) -> Result<Vec<T::Id>, SqlxLoaderError>
+    where
+        T: Table,
+        T::Id: for<'r> sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres> + Send + Unpin,
+    {
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        debug!(sql, "fetch ids");
+        Ok(sqlx::query_scalar_with::<_, T::Id, _>(&sql, values).fetch_all(self.pool).await?)
+    }
+
+    pub async fn fetch_by_ids<T>(&self, ids: Vec<T::Id>) -> Result<HashMap<T::Id, T>, SqlxLoaderError>
+    where
+        T: Table + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let rows = fetch_by_ids::<T>(self.pool, &ids).await?;
+        Ok(rows.into_iter().map(|x| (x.id(), x)).collect())
+    }
+}
+
+/// `TableFetcher` loading rows by `id = ANY(..)`, or `(id0, id1) IN (..)` for composite ids
+#[derive(Debug, Clone)]
+pub struct SqlxFetcher {
+    pool: PgPool,
+}
+
+impl SqlxFetcher {
+    pub fn new(pool: PgPool) -> Self {
+        SqlxFetcher { pool }
+    }
+}
+
+#[async_trait]
+impl<T> TableFetcher<T> for SqlxFetcher
+where
+    T: Table + for<'r> FromRow<'r, PgRow> + Send + Unpin + 'static,
+{
+    type Error = sqlx::Error;
+
+    async fn fetch(&self, ids: &[T::Id]) -> Result<Vec<T>, Self::Error> {
+        fetch_by_ids::<T>(&self.pool, ids).await
+    }
+}
+
+async fn fetch_by_ids<T>(pool: &PgPool, ids: &[T::Id]) -> Result<Vec<T>, sqlx::Error>
+where
+    T: Table + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (sql, values) = by_ids_query::<T>(ids).build_sqlx(PostgresQueryBuilder);
+    debug!(sql, "fetch by ids");
+    sqlx::query_as_with::<_, T, _>(&sql, values).fetch_all(pool).await
+}
+
+fn by_ids_query<T: Table>(ids: &[T::Id]) -> SelectStatement {
+    let id_columns = T::id_fields().into_iter().map(|x| x.column_ident()).collect::<Vec<_>>();
+    let cond = match id_columns.as_slice() {
+        [id_column] => Expr::col(id_column.clone()).is_in(ids.iter().flat_map(T::id_values)),
+        _ => Expr::tuple(id_columns.into_iter().map(|x| Expr::col(x).into())).in_tuples(ids.iter().map(|x| ValueTuple::Many(T::id_values(x)))),
+    };
+    Query::select().column(Asterisk).from(T::table()).and_where(cond).to_owned()
 }
 
 #[cfg(test)]
-mod tests {
+#[allow(clippy::needless_update)] // struct update emitted by `#[derive(Table)]`
+mod test {
+    use std::sync::Mutex;
+
+    use async_graphql::SimpleObject;
+    use pretty_assertions::assert_eq;
+    use sea_query::{Alias, TableRef};
+    use table_traits::{Field, PageByCursor, StringFilter};
+
     use super::*;
 
+    #[derive(Debug, Clone, PartialEq, FromRow, SimpleObject, Table)]
+    #[table(schema = "public", table = "item")]
+    pub struct Item {
+        #[column(id, filter, sorter)]
+        pub item_id: String,
+        #[column()]
+        pub name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, FromRow, SimpleObject, Table)]
+    #[table(schema = "public", table = "member")]
+    pub struct Member {
+        #[column(id, filter, sorter)]
+        pub group_id: String,
+        #[column(id, filter, sorter)]
+        pub user_id: String,
+    }
+
+    fn pool() -> PgPool {
+        PgPool::connect_lazy("postgres://localhost/unused").unwrap()
+    }
+
+    fn sql(query: SelectStatement) -> String {
+        query.to_string(PostgresQueryBuilder)
+    }
+
     #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    fn by_ids_query_single_id() {
+        let query = by_ids_query::<Item>(&["a".to_string(), "b".to_string()]);
+        assert_eq!(sql(query), r#"SELECT * FROM "public"."item" WHERE "item_id" IN ('a', 'b')"#);
+    }
+
+    #[test]
+    fn by_ids_query_composite_id() {
+        let query = by_ids_query::<Member>(&[("g1".to_string(), "u1".to_string()), ("g1".to_string(), "u2".to_string())]);
+        assert_eq!(sql(query), r#"SELECT * FROM "public"."member" WHERE ("group_id", "user_id") IN (('g1', 'u1'), ('g1', 'u2'))"#);
+    }
+
+    #[tokio::test]
+    async fn id_query() {
+        let pool = pool();
+        let executor = SqlxExecutor::new(&pool).with_alias("i");
+        let queries = Mutex::new(Vec::new());
+        let filter = ItemFilter { item_id: Some(StringFilter { eq: Some("a".to_string()), ..Default::default() }), ..Default::default() };
+        let page = Page::Cursor(PageByCursor { first: Some(10), ..Default::default() });
+        let connection = executor
+            .context::<Item>()
+            .connection(
+                Some(page),
+                filter,
+                ItemSorter(Vec::new()),
+                |_| async { Ok(None) },
+                |query| {
+                    queries.lock().unwrap().push(sql(query));
+                    async { Ok(vec!["a".to_string()]) }
+                },
+                |ids| async move { Ok::<_, SqlxLoaderError>(ids.into_iter().map(|item_id| (item_id.clone(), Item { item_id, name: "a".to_string() })).collect()) },
+            )
+            .await
+            .unwrap();
+        assert_eq!(connection.edges.len(), 1);
+        assert_eq!(*queries.lock().unwrap(), vec![r#"SELECT "i"."item_id" FROM "public"."item" AS "i" WHERE "i"."item_id" = 'a' LIMIT 10"#.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn connection_rejects_composite_id() {
+        let pool = pool();
+        let result = SqlxExecutor::new(&pool).connection::<Member>(None, Default::default(), MemberSorter(Vec::new())).await;
+        match result {
+            | Err(SqlxLoaderError::CompositeId { table, count }) => assert_eq!((table.as_str(), count), ("Member", 2)),
+            | _ => panic!("expected composite id error"),
+        }
     }
 }
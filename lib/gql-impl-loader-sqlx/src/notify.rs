@@ -0,0 +1,68 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+
+use gql_impl_loader::{CacheInvalidator, ChangeEvent};
+
+/// channel of `notify_table_change()` trigger function, see `v0.0.2_notify_table_change.sql`
+pub const NOTIFY_CHANNEL: &str = "table_change";
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// listens `LISTEN {channel}` and applies `ChangeEvent` payloads to caches
+pub struct NotifySource {
+    pool: PgPool,
+    channel: String,
+}
+
+impl NotifySource {
+    pub fn new(pool: PgPool, channel: &str) -> Self {
+        NotifySource { pool, channel: channel.to_string() }
+    }
+
+    /// listens until the task is dropped, failures are logged and the listener reconnects with exponential backoff
+    ///
+    /// notifications sent while disconnected are gone, so every cached row is dropped on failure and again once listening
+    pub async fn run(self, invalidator: CacheInvalidator) {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut reconnect = false;
+        loop {
+            let Err(err) = self.listen(&invalidator, &mut backoff, reconnect).await;
+            error!(%err, channel = self.channel, ?backoff, "listener failed, dropping cached rows");
+            invalidator.invalidate_all();
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            reconnect = true;
+        }
+    }
+
+    async fn listen(&self, invalidator: &CacheInvalidator, backoff: &mut Duration, reconnect: bool) -> Result<Infallible, sqlx::Error> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(&self.channel).await?;
+        if reconnect {
+            info!(channel = self.channel, "listener reconnected, dropping cached rows");
+            invalidator.invalidate_all();
+        }
+        *backoff = INITIAL_BACKOFF;
+        loop {
+            // `None` means connection was lost and re-established, notifications in between are gone
+            let Some(notification) = listener.try_recv().await? else {
+                warn!(channel = self.channel, "listener reconnected, dropping cached rows");
+                invalidator.invalidate_all();
+                continue;
+            };
+            match serde_json::from_str::<ChangeEvent>(notification.payload()) {
+                Ok(event) => invalidator.apply(&event),
+                // the changed row is unknown, drop every cached row instead of serving it stale
+                Err(err) => {
+                    warn!(%err, payload = notification.payload(), "invalid change event, dropping cached rows");
+                    invalidator.invalidate_all();
+                }
+            }
+        }
+    }
+}
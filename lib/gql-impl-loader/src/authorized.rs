@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use table_traits::types::{RowAuthError, DEFAULT_CONFIG};
+use table_traits::{RowAuthorizer, Table};
+
+use crate::TableFetcher;
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuthorizedFetchError<E: std::error::Error + 'static> {
+    #[error(transparent)]
+    Fetch(E),
+    #[error(transparent)]
+    RowAuthError(#[from] RowAuthError),
+}
+
+/// `TableFetcher` dropping rows the principal of `authorizer` can't see, tables without rebac are passed through
+///
+/// rows loaded by id never go through `Context::rebac_condition`, so they are checked by `RowAuthorizer::check_objects`,
+/// wrap the `CachedFetcher` so rows shared between requests are still checked for every principal
+///
+/// ```ignore
+/// let authorizer = Arc::new(OpenFgaRowAuthorizer::new(openfga, user));
+/// let loader = TableLoader::new(AuthorizedFetcher::new(CachedFetcher::new(otypes, SqlxFetcher::new(pool)), authorizer)).per_request(tokio::spawn);
+/// ```
+pub struct AuthorizedFetcher<T: Table, F: TableFetcher<T>> {
+    inner: F,
+    authorizer: Arc<dyn RowAuthorizer>,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Table, F: TableFetcher<T>> AuthorizedFetcher<T, F> {
+    pub fn new(inner: F, authorizer: Arc<dyn RowAuthorizer>) -> Self {
+        AuthorizedFetcher { inner, authorizer, _phantom: PhantomData }
+    }
+}
+
+#[async_trait]
+impl<T, F> TableFetcher<T> for AuthorizedFetcher<T, F>
+where
+    T: Table + Send + 'static,
+    F: TableFetcher<T>,
+{
+    type Error = AuthorizedFetchError<F::Error>;
+
+    async fn fetch(&self, ids: &[T::Id]) -> Result<Vec<T>, Self::Error> {
+        let rows = self.inner.fetch(ids).await.map_err(AuthorizedFetchError::Fetch)?;
+        let context = DEFAULT_CONFIG.context_as::<T, _>(None).with_authorizer(self.authorizer.as_ref());
+        Ok(context.check_loaded(rows).await?)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::needless_update)] // struct update emitted by `#[derive(Table)]`
+mod test {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    use pretty_assertions::assert_eq;
+    use sea_query::{Alias, TableRef};
+    use table_traits::{AllowedObjects, Field};
+
+    use super::*;
+    use crate::test::Item;
+    use crate::FnFetcher;
+
+    #[derive(Debug, Clone, PartialEq, Table)]
+    #[table(schema = "public", table = "doc", rebac(otype = "doc", rel = "viewer"))]
+    pub struct Doc {
+        #[column(id, filter, sorter)]
+        pub doc_id: String,
+    }
+
+    /// principal seeing `visible`, recording the objects it is asked to check
+    struct Visible {
+        visible: HashSet<String>,
+        checked: Mutex<Vec<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl RowAuthorizer for Visible {
+        async fn list_objects(&self, _otype: &str, _rel: &str) -> Result<AllowedObjects, RowAuthError> {
+            Ok(AllowedObjects::Ids(self.visible.iter().cloned().collect()))
+        }
+
+        async fn check_objects(&self, otype: &str, rel: &str, oids: Vec<String>) -> Result<HashSet<String>, RowAuthError> {
+            assert_eq!((otype, rel), ("doc", "viewer"));
+            self.checked.lock().unwrap().push(oids.clone());
+            Ok(oids.into_iter().filter(|x| self.visible.contains(x)).collect())
+        }
+    }
+
+    fn visible(oids: &[&str]) -> Arc<Visible> {
+        Arc::new(Visible { visible: oids.iter().map(ToString::to_string).collect(), checked: Mutex::new(Vec::new()) })
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn fetch_drops_invisible_rows() {
+        let authorizer = visible(&["a", "c"]);
+        let inner = FnFetcher::new(|ids: Vec<String>| async move { Ok::<_, std::io::Error>(ids.into_iter().map(|doc_id| Doc { doc_id }).collect()) });
+        let fetcher = AuthorizedFetcher::new(inner, authorizer.clone());
+        let rows = fetcher.fetch(&ids(&["a", "b", "c"])).await.unwrap();
+        assert_eq!(rows, vec![Doc { doc_id: "a".to_string() }, Doc { doc_id: "c".to_string() }]);
+        assert_eq!(*authorizer.checked.lock().unwrap(), vec![ids(&["a", "b", "c"])]);
+    }
+
+    #[tokio::test]
+    async fn fetch_without_rebac() {
+        let authorizer = visible(&[]);
+        let inner = FnFetcher::new(|ids: Vec<String>| async move { Ok::<_, std::io::Error>(ids.into_iter().map(|item_id| Item { name: item_id.clone(), item_id }).collect()) });
+        let fetcher = AuthorizedFetcher::new(inner, authorizer.clone());
+        assert_eq!(fetcher.fetch(&ids(&["a"])).await.unwrap().len(), 1);
+        assert!(authorizer.checked.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetch_error() {
+        let inner = FnFetcher::new(|_: Vec<String>| async move { Err::<Vec<Doc>, _>(std::io::Error::other("connection reset")) });
+        let fetcher = AuthorizedFetcher::new(inner, visible(&["a"]));
+        let err = fetcher.fetch(&ids(&["a"])).await.unwrap_err();
+        assert!(matches!(err, AuthorizedFetchError::Fetch(_)));
+        assert_eq!(err.to_string(), "connection reset");
+    }
+}
@@ -0,0 +1,243 @@
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use moka::sync::Cache;
+use serde::de::DeserializeOwned;
+
+use table_traits::Table;
+
+use crate::{ChangeKey, Invalidate, TableFetcher};
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// maximum number of cached rows per table, least recently used rows are evicted first
+    pub max_capacity: u64,
+    /// rows older than ttl are fetched again even without change event
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig { max_capacity: 10_000, ttl: Duration::from_secs(300) }
+    }
+}
+
+/// rows of `T` shared between requests, cheap to clone
+///
+/// every invalidation bumps `generation`, rows fetched before the latest invalidation are not stored,
+/// so a fetch racing with a change event can't put stale row back into cache
+///
+/// invalidations hold the write lock of `generation`, inserts the read lock from the generation check to the last row,
+/// so an invalidation can't land between the check and the insert
+pub struct EntityCache<T: Table> {
+    rows: Cache<T::Id, T>,
+    generation: Arc<RwLock<u64>>,
+}
+
+impl<T: Table> Clone for EntityCache<T> {
+    fn clone(&self) -> Self {
+        EntityCache { rows: self.rows.clone(), generation: self.generation.clone() }
+    }
+}
+
+impl<T> EntityCache<T>
+where
+    T: Table + Clone + Send + Sync + 'static,
+{
+    pub fn new(config: &CacheConfig) -> Self {
+        EntityCache {
+            rows: Cache::builder().max_capacity(config.max_capacity).time_to_live(config.ttl).build(),
+            generation: Arc::new(RwLock::new(0)),
+        }
+    }
+
+    pub fn get(&self, id: &T::Id) -> Option<T> {
+        self.rows.get(id)
+    }
+
+    pub fn invalidate_id(&self, id: &T::Id) {
+        let mut generation = self.generation.write().unwrap_or_else(PoisonError::into_inner);
+        *generation += 1;
+        self.rows.invalidate(id);
+    }
+
+    pub fn clear(&self) {
+        let mut generation = self.generation.write().unwrap_or_else(PoisonError::into_inner);
+        *generation += 1;
+        self.rows.invalidate_all();
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        *self.generation.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn insert_if_current(&self, generation: u64, rows: &[T]) {
+        let current = self.generation.read().unwrap_or_else(PoisonError::into_inner);
+        if *current != generation {
+            return;
+        }
+        for row in rows {
+            self.rows.insert(row.id(), row.clone());
+        }
+    }
+}
+
+impl<T> Invalidate for EntityCache<T>
+where
+    T: Table + Clone + Send + Sync + 'static,
+    T::Id: DeserializeOwned,
+{
+    fn invalidate(&self, key: &ChangeKey) {
+        let id_columns = T::descriptor().id_columns;
+        match key.decode::<T::Id>(&id_columns) {
+            Some(id) => self.invalidate_id(&id),
+            // unknown key shape, drop everything instead of serving stale rows
+            None => self.clear(),
+        }
+    }
+
+    fn invalidate_all(&self) {
+        self.clear();
+    }
+}
+
+/// `TableFetcher` serving rows from `EntityCache`, only missing ids reach the inner fetcher
+pub struct CachedFetcher<T: Table, F: TableFetcher<T>> {
+    cache: EntityCache<T>,
+    inner: F,
+}
+
+impl<T, F> CachedFetcher<T, F>
+where
+    T: Table + Clone + Send + Sync + 'static,
+    F: TableFetcher<T>,
+{
+    pub fn new(cache: EntityCache<T>, inner: F) -> Self {
+        CachedFetcher { cache, inner }
+    }
+}
+
+#[async_trait]
+impl<T, F> TableFetcher<T> for CachedFetcher<T, F>
+where
+    T: Table + Clone + Send + Sync + 'static,
+    F: TableFetcher<T>,
+{
+    type Error = F::Error;
+
+    async fn fetch(&self, ids: &[T::Id]) -> Result<Vec<T>, Self::Error> {
+        let mut rows = Vec::with_capacity(ids.len());
+        let mut missing = Vec::new();
+        for id in ids {
+            match self.cache.get(id) {
+                Some(row) => rows.push(row),
+                None => missing.push(id.clone()),
+            }
+        }
+        if missing.is_empty() {
+            return Ok(rows);
+        }
+        let generation = self.cache.generation();
+        let fetched = self.inner.fetch(&missing).await?;
+        self.cache.insert_if_current(generation, &fetched);
+        rows.extend(fetched);
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::test::{Item, Member};
+    use crate::FnFetcher;
+
+    fn item(item_id: &str, name: &str) -> Item {
+        Item { item_id: item_id.to_string(), name: name.to_string() }
+    }
+
+    fn member(group_id: &str, user_id: &str) -> Member {
+        Member { group_id: group_id.to_string(), user_id: user_id.to_string(), role: "member".to_string() }
+    }
+
+    fn key(value: serde_json::Value) -> ChangeKey {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn items(rows: &[Item]) -> EntityCache<Item> {
+        let cache = EntityCache::new(&CacheConfig::default());
+        cache.insert_if_current(cache.generation(), rows);
+        cache
+    }
+
+    #[test]
+    fn invalidate_by_key() {
+        let cache = items(&[item("a", "a"), item("b", "b")]);
+        cache.invalidate(&key(json!({ "item_id": "a" })));
+        assert_eq!(cache.get(&"a".to_string()), None);
+        assert_eq!(cache.get(&"b".to_string()), Some(item("b", "b")));
+    }
+
+    #[test]
+    fn invalidate_by_composite_key() {
+        let cache = EntityCache::<Member>::new(&CacheConfig::default());
+        cache.insert_if_current(cache.generation(), &[member("g1", "u1"), member("g1", "u2")]);
+        cache.invalidate(&key(json!({ "group_id": "g1", "user_id": "u1" })));
+        assert_eq!(cache.get(&("g1".to_string(), "u1".to_string())), None);
+        assert_eq!(cache.get(&("g1".to_string(), "u2".to_string())), Some(member("g1", "u2")));
+    }
+
+    #[test]
+    fn invalidate_unknown_key_clears() {
+        let cache = items(&[item("a", "a"), item("b", "b")]);
+        cache.invalidate(&key(json!({ "id": 1 })));
+        assert_eq!(cache.get(&"a".to_string()), None);
+        assert_eq!(cache.get(&"b".to_string()), None);
+    }
+
+    #[test]
+    fn invalidate_all() {
+        let cache = items(&[item("a", "a"), item("b", "b")]);
+        Invalidate::invalidate_all(&cache);
+        assert_eq!(cache.get(&"a".to_string()), None);
+        assert_eq!(cache.get(&"b".to_string()), None);
+    }
+
+    #[test]
+    fn insert_after_invalidation_is_dropped() {
+        let cache = items(&[]);
+        let generation = cache.generation();
+        cache.invalidate_id(&"b".to_string());
+        cache.insert_if_current(generation, &[item("a", "stale")]);
+        assert_eq!(cache.get(&"a".to_string()), None);
+    }
+
+    #[tokio::test]
+    async fn fetch_racing_invalidation() {
+        let cache = items(&[]);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fetcher = CachedFetcher::new(cache.clone(), {
+            let (cache, calls) = (cache.clone(), calls.clone());
+            FnFetcher::new(move |ids: Vec<String>| {
+                let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                // change event of the row arrives while the first fetch is in flight
+                if call == 1 {
+                    cache.invalidate_id(&"a".to_string());
+                }
+                async move { Ok::<_, std::io::Error>(ids.iter().map(|x| item(x, &format!("v{}", call))).collect()) }
+            })
+        });
+
+        assert_eq!(fetcher.fetch(&["a".to_string()]).await.unwrap(), vec![item("a", "v1")]);
+        assert_eq!(cache.get(&"a".to_string()), None);
+
+        assert_eq!(fetcher.fetch(&["a".to_string()]).await.unwrap(), vec![item("a", "v2")]);
+        assert_eq!(fetcher.fetch(&["a".to_string()]).await.unwrap(), vec![item("a", "v2")]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(feature = "debezium")]
+use rdkafka::config::ClientConfig;
+#[cfg(feature = "debezium")]
+use rdkafka::consumer::{Consumer, StreamConsumer};
+#[cfg(feature = "debezium")]
+use rdkafka::error::KafkaResult;
+#[cfg(feature = "debezium")]
+use rdkafka::Message;
+use serde_json::Value;
+#[cfg(feature = "debezium")]
+use tracing::warn;
+
+#[cfg(feature = "debezium")]
+use crate::CacheInvalidator;
+use crate::{ChangeEvent, ChangeKey};
+
+/// consumes debezium postgres connector topics `{topic_prefix}.{schema}.{table}` of registered tables
+///
+/// every instance must see every change, so `group_id` has to be unique per process (ex. pod name)
+#[cfg(feature = "debezium")]
+pub struct DebeziumSource {
+    consumer: StreamConsumer,
+    topic_prefix: String,
+}
+
+#[cfg(feature = "debezium")]
+impl DebeziumSource {
+    pub fn new(brokers: &str, group_id: &str, topic_prefix: &str) -> KafkaResult<Self> {
+        let consumer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("group.id", group_id)
+            .set("auto.offset.reset", "latest")
+            .set("enable.auto.commit", "true")
+            .create()?;
+        Ok(DebeziumSource { consumer, topic_prefix: topic_prefix.to_string() })
+    }
+
+    pub async fn run(self, invalidator: CacheInvalidator) -> KafkaResult<()> {
+        let topics = invalidator.tables().map(|(schema, table)| format!("{}.{}.{}", self.topic_prefix, schema, table)).collect::<Vec<_>>();
+        self.consumer.subscribe(&topics.iter().map(String::as_str).collect::<Vec<_>>())?;
+        loop {
+            let message = match self.consumer.recv().await {
+                Ok(message) => message,
+                Err(err) => {
+                    // offsets are `latest`, records during the error are lost
+                    warn!(%err, "debezium consumer error, dropping cached rows");
+                    invalidator.invalidate_all();
+                    continue;
+                }
+            };
+            match ChangeEvent::from_debezium(message.topic(), message.key()) {
+                Some(event) => invalidator.apply(&event),
+                None => warn!(topic = message.topic(), "unexpected debezium record"),
+            }
+        }
+    }
+}
+
+impl ChangeEvent {
+    /// change of debezium record on topic `{topic_prefix}.{schema}.{table}`
+    ///
+    /// key of the record is the primary key, with or without json converter schema envelope,
+    /// create, update, delete and tombstone records of a row share the key, so the value is never read
+    pub fn from_debezium(topic: &str, key: Option<&[u8]>) -> Option<ChangeEvent> {
+        let mut segments = topic.rsplitn(3, '.');
+        let table = segments.next()?.to_string();
+        let schema = segments.next()?.to_string();
+        let key = match key.map(serde_json::from_slice::<Value>) {
+            // json converter with `schemas.enable=true` wraps key as `{"schema": .., "payload": {..}}`
+            Some(Ok(Value::Object(mut object))) if object.contains_key("schema") => match object.remove("payload") {
+                Some(Value::Object(payload)) => Some(payload),
+                _ => None,
+            },
+            Some(Ok(Value::Object(object))) => Some(object),
+            _ => None,
+        };
+        Some(ChangeEvent { schema, table, key: key.map(ChangeKey) })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const TOPIC: &str = "hmb.public.otype";
+
+    fn event(key: Option<serde_json::Value>) -> Option<ChangeEvent> {
+        Some(ChangeEvent {
+            schema: "public".to_string(),
+            table: "otype".to_string(),
+            key: key.map(|x| serde_json::from_value(x).unwrap()),
+        })
+    }
+
+    #[test]
+    fn create_update_delete_and_tombstone() {
+        // create, update and delete records and the tombstone after a delete carry the same key, only their values differ
+        let key = br#"{"otype_id": "0b9c5a2e-35a4-4a4e-9b1f-0f6d1c1a2b3c"}"#;
+        assert_eq!(ChangeEvent::from_debezium(TOPIC, Some(key)), event(Some(json!({ "otype_id": "0b9c5a2e-35a4-4a4e-9b1f-0f6d1c1a2b3c" }))));
+    }
+
+    #[test]
+    fn key_with_schema_envelope() {
+        let key = json!({
+            "schema": {
+                "type": "struct",
+                "fields": [{ "type": "string", "optional": false, "name": "io.debezium.data.Uuid", "version": 1, "field": "otype_id" }],
+                "optional": false,
+                "name": "hmb.public.otype.Key"
+            },
+            "payload": { "otype_id": "0b9c5a2e-35a4-4a4e-9b1f-0f6d1c1a2b3c" }
+        });
+        let key = serde_json::to_vec(&key).unwrap();
+        assert_eq!(ChangeEvent::from_debezium(TOPIC, Some(&key)), event(Some(json!({ "otype_id": "0b9c5a2e-35a4-4a4e-9b1f-0f6d1c1a2b3c" }))));
+    }
+
+    #[test]
+    fn unknown_key_drops_the_table() {
+        // table without primary key, or a key the converter didn't write as json object
+        assert_eq!(ChangeEvent::from_debezium(TOPIC, None), event(None));
+        assert_eq!(ChangeEvent::from_debezium(TOPIC, Some(b"not json")), event(None));
+        assert_eq!(ChangeEvent::from_debezium(TOPIC, Some(br#""0b9c5a2e""#)), event(None));
+        assert_eq!(ChangeEvent::from_debezium(TOPIC, Some(br#"{"schema": {}, "payload": null}"#)), event(None));
+    }
+
+    #[test]
+    fn topic() {
+        assert_eq!(ChangeEvent::from_debezium("cdc.hmb.public.otype", None), event(None));
+        assert_eq!(ChangeEvent::from_debezium("otype", None), None);
+    }
+}
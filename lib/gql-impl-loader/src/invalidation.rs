@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::debug;
+
+use table_traits::Table;
+
+use crate::EntityCache;
+
+/// primary key columns of changed row, `{"otype_id": "..."}`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChangeKey(pub Map<String, Value>);
+
+impl ChangeKey {
+    /// single id column decodes the column value, composite ids decode a tuple ordered as `id_columns`
+    pub fn decode<I: DeserializeOwned>(&self, id_columns: &[String]) -> Option<I> {
+        let value = match id_columns {
+            [column] => self.0.get(column)?.clone(),
+            columns => Value::Array(columns.iter().map(|x| self.0.get(x).cloned()).collect::<Option<Vec<_>>>()?),
+        };
+        serde_json::from_value(value).ok()
+    }
+}
+
+/// change of single row, published by `notify_table_change()` trigger or built from debezium record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub schema: String,
+    pub table: String,
+    /// `None` when the key is unknown, every cached row of the table is dropped
+    #[serde(default)]
+    pub key: Option<ChangeKey>,
+}
+
+pub trait Invalidate: Send + Sync {
+    fn invalidate(&self, key: &ChangeKey);
+
+    fn invalidate_all(&self);
+}
+
+/// routes change events to caches registered by `schema.table`
+///
+/// ```ignore
+/// let otypes = EntityCache::<Otype>::new(&CacheConfig::default());
+/// let invalidator = CacheInvalidator::default().with(otypes.clone());
+/// tokio::spawn(NotifySource::new(pool, NOTIFY_CHANNEL).run(invalidator));
+/// ```
+#[derive(Clone, Default)]
+pub struct CacheInvalidator {
+    targets: HashMap<(String, String), Vec<Arc<dyn Invalidate>>>,
+}
+
+impl CacheInvalidator {
+    pub fn with<T>(mut self, cache: EntityCache<T>) -> Self
+    where
+        T: Table + Clone + Send + Sync + 'static,
+        T::Id: DeserializeOwned,
+    {
+        let descriptor = T::descriptor();
+        self.targets.entry((descriptor.schema, descriptor.table)).or_default().push(Arc::new(cache));
+        self
+    }
+
+    /// registered `(schema, table)` pairs
+    pub fn tables(&self) -> impl Iterator<Item = &(String, String)> {
+        self.targets.keys()
+    }
+
+    pub fn apply(&self, event: &ChangeEvent) {
+        let Some(targets) = self.targets.get(&(event.schema.clone(), event.table.clone())) else {
+            return;
+        };
+        debug!(schema = event.schema, table = event.table, key = ?event.key, "invalidate");
+        for target in targets {
+            match &event.key {
+                Some(key) => target.invalidate(key),
+                None => target.invalidate_all(),
+            }
+        }
+    }
+
+    /// drop every cached row, used when events may have been missed (ex. reconnect)
+    pub fn invalidate_all(&self) {
+        for target in self.targets.values().flatten() {
+            target.invalidate_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::test::{Item, Member};
+    use crate::CacheConfig;
+
+    fn item(item_id: &str) -> Item {
+        Item { item_id: item_id.to_string(), name: item_id.to_string() }
+    }
+
+    fn key(value: Value) -> ChangeKey {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn columns(columns: &[&str]) -> Vec<String> {
+        columns.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn decode_key() {
+        let single = key(json!({ "item_id": "a" }));
+        assert_eq!(single.decode::<String>(&columns(&["item_id"])), Some("a".to_string()));
+        assert_eq!(single.decode::<String>(&columns(&["other_id"])), None);
+
+        let composite = key(json!({ "user_id": "u1", "group_id": "g1" }));
+        assert_eq!(composite.decode::<(String, String)>(&columns(&["group_id", "user_id"])), Some(("g1".to_string(), "u1".to_string())));
+        assert_eq!(composite.decode::<(String, String)>(&columns(&["group_id", "role"])), None);
+        assert_eq!(composite.decode::<u64>(&columns(&["group_id"])), None);
+    }
+
+    #[test]
+    fn trigger_payload() {
+        let event = serde_json::from_str::<ChangeEvent>(r#"{"schema": "public", "table": "item", "key": {"item_id": "a"}}"#).unwrap();
+        assert_eq!(event, ChangeEvent { schema: "public".to_string(), table: "item".to_string(), key: Some(key(json!({ "item_id": "a" }))) });
+
+        let event = serde_json::from_str::<ChangeEvent>(r#"{"schema": "public", "table": "item"}"#).unwrap();
+        assert_eq!(event.key, None);
+    }
+
+    #[test]
+    fn apply_routes_by_table() {
+        let items = EntityCache::<Item>::new(&CacheConfig::default());
+        let members = EntityCache::<Member>::new(&CacheConfig::default());
+        let invalidator = CacheInvalidator::default().with(items.clone()).with(members.clone());
+        items.insert_if_current(items.generation(), &[item("a"), item("b")]);
+        let event = |table: &str, key: Option<ChangeKey>| ChangeEvent { schema: "public".to_string(), table: table.to_string(), key };
+
+        let mut tables = invalidator.tables().cloned().collect::<Vec<_>>();
+        tables.sort();
+        assert_eq!(tables, vec![("public".to_string(), "item".to_string()), ("public".to_string(), "member".to_string())]);
+
+        // same key column on another table
+        invalidator.apply(&event("member", Some(key(json!({ "item_id": "a" })))));
+        invalidator.apply(&event("other", None));
+        assert_eq!(items.get(&"a".to_string()), Some(item("a")));
+
+        invalidator.apply(&event("item", Some(key(json!({ "item_id": "a" })))));
+        assert_eq!(items.get(&"a".to_string()), None);
+        assert_eq!(items.get(&"b".to_string()), Some(item("b")));
+
+        invalidator.apply(&event("item", None));
+        assert_eq!(items.get(&"b".to_string()), None);
+    }
+
+    #[test]
+    fn invalidate_all() {
+        let items = EntityCache::<Item>::new(&CacheConfig::default());
+        let invalidator = CacheInvalidator::default().with(items.clone());
+        items.insert_if_current(items.generation(), &[item("a")]);
+        invalidator.invalidate_all();
+        assert_eq!(items.get(&"a".to_string()), None);
+    }
+}
@@ -1,14 +1,210 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
+use async_graphql::futures_util::future::BoxFuture;
+use async_trait::async_trait;
+
+use table_traits::Table;
+
+pub use authorized::*;
+pub use cache::*;
+#[cfg(feature = "debezium")]
+pub use debezium::*;
+pub use invalidation::*;
+
+mod authorized;
+mod cache;
+mod debezium;
+mod invalidation;
+
+/// loads rows of `T` by id, one call per batch of `DataLoader`
+///
+/// returned rows are keyed by `Table::id`, missing ids are simply absent
+#[async_trait]
+pub trait TableFetcher<T: Table>: Send + Sync + 'static {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn fetch(&self, ids: &[T::Id]) -> Result<Vec<T>, Self::Error>;
+}
+
+/// `Loader<T::Id>` for any derived table, composite ids are loaded as tuple of id columns
+///
+/// errors of fetcher are shared with every waiting key as `Arc<F::Error>`
+pub struct TableLoader<T: Table, F: TableFetcher<T>> {
+    fetcher: F,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Table, F: TableFetcher<T>> TableLoader<T, F> {
+    pub fn new(fetcher: F) -> Self {
+        TableLoader { fetcher, _phantom: PhantomData }
+    }
+
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// loader with `HashMapCache`, create it for every request so cached rows never outlive the request
+    ///
+    /// ```ignore
+    /// let req = req.data(TableLoader::new(SqlxFetcher::new(pool.clone())).per_request(tokio::spawn));
+    /// ```
+    pub fn per_request<S, R>(self, spawner: S) -> DataLoader<Self, HashMapCache>
+    where
+        T: Clone + Send + Sync,
+        S: Fn(BoxFuture<'static, ()>) -> R + Send + Sync + 'static,
+    {
+        DataLoader::with_cache(self, spawner, HashMapCache::default())
+    }
+}
+
+impl<T, F> Loader<T::Id> for TableLoader<T, F>
+where
+    T: Table + Clone + Send + Sync + 'static,
+    F: TableFetcher<T>,
+{
+    type Value = T;
+    type Error = Arc<F::Error>;
+
+    async fn load(&self, keys: &[T::Id]) -> Result<HashMap<T::Id, Self::Value>, Self::Error> {
+        let rows = self.fetcher.fetch(keys).await.map_err(Arc::new)?;
+        Ok(rows.into_iter().map(|x| (x.id(), x)).collect())
+    }
+}
+
+/// fetcher from closure, useful for tests and non sql sources
+pub struct FnFetcher<T, F> {
+    f: F,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T, F> FnFetcher<T, F> {
+    pub fn new(f: F) -> Self {
+        FnFetcher { f, _phantom: PhantomData }
+    }
+}
+
+#[async_trait]
+impl<T, E, R, F> TableFetcher<T> for FnFetcher<T, F>
+where
+    T: Table + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+    R: Future<Output = Result<Vec<T>, E>> + Send,
+    F: Fn(Vec<T::Id>) -> R + Send + Sync + 'static,
+{
+    type Error = E;
+
+    async fn fetch(&self, ids: &[T::Id]) -> Result<Vec<T>, Self::Error> {
+        (self.f)(ids.to_vec()).await
+    }
 }
 
 #[cfg(test)]
-mod tests {
+#[allow(clippy::needless_update)] // struct update emitted by `#[derive(Table)]`
+mod test {
+    use std::collections::BTreeSet;
+    use std::sync::Mutex;
+
+    use pretty_assertions::assert_eq;
+    use sea_query::{Alias, TableRef};
+    use table_traits::Field;
+
     use super::*;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    #[derive(Debug, Clone, PartialEq, Table)]
+    #[table(schema = "public", table = "item")]
+    pub struct Item {
+        #[column(id, filter, sorter)]
+        pub item_id: String,
+        #[column()]
+        pub name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Table)]
+    #[table(schema = "public", table = "member")]
+    pub struct Member {
+        #[column(id, filter, sorter)]
+        pub group_id: String,
+        #[column(id, filter, sorter)]
+        pub user_id: String,
+        #[column()]
+        pub role: String,
+    }
+
+    fn item(item_id: &str) -> Item {
+        Item { item_id: item_id.to_string(), name: format!("item {}", item_id) }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    /// fetcher of every id but `missing`, recording the batches it is called with
+    fn items(calls: Arc<Mutex<Vec<Vec<String>>>>) -> impl TableFetcher<Item, Error = std::io::Error> {
+        FnFetcher::new(move |ids: Vec<String>| {
+            let calls = calls.clone();
+            async move {
+                let mut batch = ids.clone();
+                batch.sort();
+                calls.lock().unwrap().push(batch);
+                Ok(ids.iter().filter(|x| *x != "missing").map(|x| item(x)).collect())
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn load_batch() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let loader = TableLoader::new(items(calls.clone())).per_request(tokio::spawn);
+        let (a, b, missing) = tokio::join!(loader.load_one("a".to_string()), loader.load_one("b".to_string()), loader.load_one("missing".to_string()));
+        assert_eq!(a.unwrap(), Some(item("a")));
+        assert_eq!(b.unwrap(), Some(item("b")));
+        assert_eq!(missing.unwrap(), None);
+        assert_eq!(*calls.lock().unwrap(), vec![ids(&["a", "b", "missing"])]);
+    }
+
+    #[tokio::test]
+    async fn load_cached_per_request() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let loader = TableLoader::new(items(calls.clone())).per_request(tokio::spawn);
+        let first = loader.load_many(ids(&["a", "b"])).await.unwrap();
+        let second = loader.load_many(ids(&["b", "c"])).await.unwrap();
+        assert_eq!(first, HashMap::from([("a".to_string(), item("a")), ("b".to_string(), item("b"))]));
+        assert_eq!(second, HashMap::from([("b".to_string(), item("b")), ("c".to_string(), item("c"))]));
+        assert_eq!(*calls.lock().unwrap(), vec![ids(&["a", "b"]), ids(&["c"])]);
+
+        let other = TableLoader::new(items(calls.clone())).per_request(tokio::spawn);
+        other.load_one("a".to_string()).await.unwrap();
+        assert_eq!(calls.lock().unwrap().last(), Some(&ids(&["a"])));
+    }
+
+    #[tokio::test]
+    async fn load_composite_id() {
+        let fetcher = FnFetcher::new(|ids: Vec<(String, String)>| async move {
+            Ok::<_, std::io::Error>(
+                ids.into_iter()
+                    .filter(|(group_id, _)| group_id == "g1")
+                    .map(|(group_id, user_id)| Member { group_id, user_id, role: "member".to_string() })
+                    .collect(),
+            )
+        });
+        let key = |group_id: &str, user_id: &str| (group_id.to_string(), user_id.to_string());
+        let loader = TableLoader::new(fetcher).per_request(tokio::spawn);
+        let rows = loader.load_many([key("g1", "u1"), key("g1", "u2"), key("g2", "u1")]).await.unwrap();
+        assert_eq!(rows.keys().cloned().collect::<BTreeSet<_>>(), BTreeSet::from([key("g1", "u1"), key("g1", "u2")]));
+        assert_eq!(rows[&key("g1", "u2")], Member { group_id: "g1".to_string(), user_id: "u2".to_string(), role: "member".to_string() });
+    }
+
+    #[tokio::test]
+    async fn load_error_shared() {
+        let fetcher = FnFetcher::new(|_: Vec<String>| async move { Err::<Vec<Item>, _>(std::io::Error::other("connection reset")) });
+        let loader = TableLoader::new(fetcher).per_request(tokio::spawn);
+        let (a, b) = tokio::join!(loader.load_one("a".to_string()), loader.load_one("b".to_string()));
+        let (a, b) = (a.unwrap_err(), b.unwrap_err());
+        assert_eq!(a.to_string(), "connection reset");
+        assert!(Arc::ptr_eq(&a, &b));
     }
 }
@@ -1,23 +1,3 @@
-pub use crate_location::*;
-pub use macro_filter::filter;
-pub use macro_sorter::sorter;
-pub use macro_table::table;
+//! compatibility layer, `table-traits-core` is the maintained macro implementation
 
-mod crate_location;
-mod macro_filter;
-// mod table_column;
-mod derive_table;
-mod enum_filter_kind;
-mod enum_sorter_kind;
-mod impl_table;
-mod impl_table_naming;
-mod macro_sorter;
-mod macro_table;
-mod syntax;
-mod token_cursor;
-mod token_field;
-mod token_filter;
-mod token_sorter;
-mod token_table_filter;
-mod token_table_sorter;
-mod utils;
+pub use table_traits_core::*;
@@ -1,16 +0,0 @@
-mod filter;
-mod filter_field;
-mod filter_kind;
-mod filter_option;
-mod sorter;
-mod sorter_field;
-mod sorter_kind;
-mod sorter_option;
-
-pub(crate) use filter::*;
-pub(crate) use filter_field::*;
-pub(crate) use filter_kind::*;
-pub(crate) use filter_option::*;
-pub(crate) use sorter::*;
-pub(crate) use sorter_field::*;
-pub(crate) use sorter_option::*;
@@ -1,30 +1,12 @@
-pub use cursor::*;
-pub use field::*;
-pub use filter::*;
-pub use filter_default::*;
-pub use filter_impl::*;
-pub use page::*;
-pub use range::*;
-pub use sorter::*;
-pub use sorter_default::*;
-pub use sorter_impl::*;
-pub use table::*;
-pub use table_filter::*;
-pub use table_sorter::*;
+//! compatibility layer, `table-traits-impl` is the maintained runtime
+//!
+//! every item keeps its `gql_impl::` path, new code should depend on `table-traits` directly
 
-mod cursor;
-mod field;
-mod filter;
-mod filter_default;
-mod filter_impl;
-mod page;
-pub mod prelude;
-pub mod private;
-mod range;
-mod sorter;
-mod sorter_default;
-mod sorter_impl;
-mod table;
-mod table_filter;
-mod table_sorter;
-pub mod types;
\ No newline at end of file
+pub use table_traits_impl::*;
+
+pub mod types {
+    pub use table_traits_impl::types::*;
+
+    pub type ConfigSql = OptionsSql;
+    pub type ConfigProcessing = OptionsProcessing;
+}
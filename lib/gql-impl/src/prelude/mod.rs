@@ -1,5 +0,0 @@
-pub use async_graphql;
-pub use data_encoding;
-pub use postcard;
-pub use sea_query;
-pub use serde;
@@ -1,4 +0,0 @@
-pub use filter_type::*;
-pub use internal_converter::*;
-mod filter_type;
-mod internal_converter;
@@ -1,9 +0,0 @@
-mod configs;
-mod errors;
-mod orders;
-mod state;
-
-pub use configs::*;
-pub use errors::*;
-pub use orders::*;
-pub use state::*;
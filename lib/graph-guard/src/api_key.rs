@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::Value;
+
+use crate::{Error, Principal, User};
+
+/// header api keys are sent in, batch jobs and workers authenticate with it instead of a bearer token
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// key of the api key store, hex of the sha-256 of a key, keys themselves are never stored
+pub fn hash_api_key(key: &str) -> String {
+    digest(&SHA256, key.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|x| format!("{:02x}", x))
+        .collect()
+}
+
+/// random key of 256 bits, stored as `hash_api_key` of it and shown to its owner once
+pub fn generate_api_key() -> Result<String, Error> {
+    let mut key = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| Error::AuthUnavailableApiKeys("no random source".to_string()))?;
+    Ok(URL_SAFE_NO_PAD.encode(key))
+}
+
+/// api key found by its hash, valid at the time it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    pub api_key_id: String,
+    /// principal the key acts as, `api_key:<api_key_id>` unless it is issued to a service account or a userset
+    pub principal: Principal,
+    pub name: Option<String>,
+}
+
+/// api keys by `hash_api_key`, revoked and expired keys are not found
+///
+/// graph-hmb reads them from postgres, `HashMap` is for tests
+#[async_trait]
+pub trait ApiKeyStore: std::fmt::Debug + Send + Sync {
+    async fn find_api_key(&self, hash: &str) -> Result<Option<ApiKey>, Error>;
+}
+
+#[async_trait]
+impl ApiKeyStore for HashMap<String, ApiKey> {
+    async fn find_api_key(&self, hash: &str) -> Result<Option<ApiKey>, Error> {
+        Ok(self.get(hash).cloned())
+    }
+}
+
+/// authenticates the `API_KEY_HEADER` of a request
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuthenticator {
+    store: Arc<dyn ApiKeyStore>,
+}
+
+impl ApiKeyAuthenticator {
+    pub fn new<S: ApiKeyStore + 'static>(store: S) -> Self {
+        Self {
+            store: Arc::new(store),
+        }
+    }
+
+    /// user of the principal of `key`, the key name as hint
+    pub async fn user(&self, key: &str) -> Result<User, Error> {
+        let api_key = self.authenticate(key).await?;
+        Ok(User {
+            hint: api_key.name,
+            principal: api_key.principal,
+            ..User::default()
+        })
+    }
+
+    pub async fn authenticate(&self, key: &str) -> Result<ApiKey, Error> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(Error::AuthInvalidApiKey);
+        }
+        self.store
+            .find_api_key(&hash_api_key(key))
+            .await?
+            .ok_or(Error::AuthInvalidApiKey)
+    }
+}
+
+/// api key of a graphql websocket `connection_init` payload
+///
+/// clients send it as `{"X-Api-Key": "<key>"}`, `{"headers": {"X-Api-Key": ..}}` or `{"apiKey": "<key>"}`
+pub fn connection_init_api_key(payload: &Value) -> Option<&str> {
+    let object = payload.as_object()?;
+    fn header(object: &serde_json::Map<String, Value>) -> Option<&str> {
+        object
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(API_KEY_HEADER))
+            .and_then(|(_, value)| value.as_str())
+    }
+    header(object)
+        .or_else(|| header(object.get("headers")?.as_object()?))
+        .or_else(|| object.get("apiKey")?.as_str())
+}
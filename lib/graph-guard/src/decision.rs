@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use openfga_client::Tuple;
+
+/// OpenFGA decisions made for the current request, a tuple is checked once per request
+///
+/// `GraphGuard` puts it into the request data, resolvers can reuse decisions of `@rebac`
+///
+/// ```ignore
+/// let allowed = ctx.data_unchecked::<RebacDecisions>().get(&tuple);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RebacDecisions(Arc<Mutex<HashMap<Tuple, bool>>>);
+
+impl RebacDecisions {
+    /// `allowed` of `tuple`, `None` when it is not checked yet
+    pub fn get(&self, tuple: &Tuple) -> Option<bool> {
+        self.0.lock().unwrap().get(tuple).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn insert(&self, tuple: Tuple, allowed: bool) {
+        self.0.lock().unwrap().insert(tuple, allowed);
+    }
+}
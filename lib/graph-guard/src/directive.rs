@@ -6,11 +6,112 @@ use async_graphql::registry::{MetaDirective, MetaDirectiveInvocation, MetaInputV
 use async_graphql::{InputType, TypeDirective};
 use openfga_client::Tuple;
 
+#[derive(Debug, Clone)]
 pub struct RebacTypeDirective {
     pub rel: String,
     pub otype: String,
-    pub oid: String,
+    pub oid: RebacOid,
     pub result: bool,
+    pub mode: RebacMode,
+}
+
+/// what happens to a field when its `@rebac` check fails
+///
+/// - `deny`, the whole operation is rejected (default)
+/// - `null`, the field resolves to `null` with an error at its path, non-null fields propagate `null` to the parent
+/// - `omit`, the field is removed from the response without error
+///
+/// `@rebac` on an argument, input field or enum value is checked when the value is supplied and always denies,
+/// `@rebac` on an object guards every field returning it and supports static oids only
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RebacMode {
+    #[default]
+    Deny,
+    Null,
+    Omit,
+}
+
+impl RebacMode {
+    /// the strictest mode wins when several directives of a field fail
+    pub(crate) fn strictness(&self) -> u8 {
+        match self {
+            RebacMode::Deny => 2,
+            RebacMode::Null => 1,
+            RebacMode::Omit => 0,
+        }
+    }
+}
+
+impl std::str::FromStr for RebacMode {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deny" => Ok(RebacMode::Deny),
+            "null" => Ok(RebacMode::Null),
+            "omit" => Ok(RebacMode::Omit),
+            _ => Err(crate::Error::DirectiveInvalidMode(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for RebacMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RebacMode::Deny => write!(f, "deny"),
+            RebacMode::Null => write!(f, "null"),
+            RebacMode::Omit => write!(f, "omit"),
+        }
+    }
+}
+
+/// object id of `@rebac`, either static or resolved per field execution
+///
+/// - `"1234"`, static object id, checked at validation
+/// - `"$args.input.id"`, argument of the field, dotted path walks into input objects
+/// - `"$parent.otype_id"`, field of the parent object, selected alongside the guarded field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebacOid {
+    Static(String),
+    Args(Vec<String>),
+    Parent(String),
+}
+
+impl RebacOid {
+    pub fn is_static(&self) -> bool {
+        matches!(self, RebacOid::Static(_))
+    }
+}
+
+impl std::str::FromStr for RebacOid {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(template) = s.strip_prefix('$') else {
+            return Ok(RebacOid::Static(s.to_string()));
+        };
+        let mut path = template.split('.');
+        let source = path.next();
+        let path = path.map(ToString::to_string).collect::<Vec<_>>();
+        if path.is_empty() || path.iter().any(|x| x.is_empty()) {
+            return Err(crate::Error::DirectiveInvalidOidTemplate(s.to_string()));
+        }
+        match (source, path.as_slice()) {
+            (Some("args"), _) => Ok(RebacOid::Args(path)),
+            (Some("parent"), [field]) => Ok(RebacOid::Parent(field.clone())),
+            _ => Err(crate::Error::DirectiveInvalidOidTemplate(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for RebacOid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RebacOid::Static(oid) => write!(f, "{}", oid),
+            RebacOid::Args(path) => write!(f, "$args.{}", path.join(".")),
+            RebacOid::Parent(field) => write!(f, "$parent.{}", field),
+        }
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -20,6 +121,13 @@ impl RebacTypeDirective {
     pub fn fga_notation(&self) -> String {
         format!("{}:{}", self.otype, self.oid)
     }
+    /// same directive with oid resolved by the executing field
+    pub fn resolved(&self, oid: String) -> RebacTypeDirective {
+        RebacTypeDirective {
+            oid: RebacOid::Static(oid),
+            ..self.clone()
+        }
+    }
     pub fn tuple(&self, user: &User) -> Tuple {
         Tuple::new(user.fga_notation(), self.rel.clone(), self.fga_notation())
     }
@@ -33,6 +141,7 @@ impl<'a> TryFrom<&'a MetaDirectiveInvocation> for RebacTypeDirective {
         let mut otype: Option<String> = None;
         let mut oid: Option<String> = None;
         let mut result: Option<bool> = None;
+        let mut mode: Option<RebacMode> = None;
 
         for (name, value) in &value.args {
             match (name.as_str(), value) {
@@ -84,6 +193,17 @@ impl<'a> TryFrom<&'a MetaDirectiveInvocation> for RebacTypeDirective {
                         "result".to_string(),
                     ));
                 }
+                ("mode", async_graphql_value::ConstValue::String(str)) => {
+                    if mode.is_some() {
+                        return Err(crate::Error::DirectiveDuplicateArgument("mode".to_string()));
+                    }
+                    mode = Some(str.parse()?);
+                }
+                ("mode", _) => {
+                    return Err(crate::Error::DirectiveArgumentMustBeAString(
+                        "mode".to_string(),
+                    ));
+                }
                 _ => {
                     return Err(crate::Error::DirectiveUnknownArgument(
                         name.to_string(),
@@ -96,8 +216,9 @@ impl<'a> TryFrom<&'a MetaDirectiveInvocation> for RebacTypeDirective {
             (Some(rel), Some(otype), Some(oid), oresult) => Ok(RebacTypeDirective {
                 rel,
                 otype,
-                oid,
+                oid: oid.parse()?,
                 result: oresult.unwrap_or(true),
+                mode: mode.unwrap_or_default(),
             }),
             (rel, otype, oid, _) => {
                 let not_exist_fields = vec![rel, otype, oid]
@@ -121,6 +242,9 @@ impl TypeDirective for rebac {
             locations: <[_]>::into_vec(Box::new([
                 async_graphql::registry::__DirectiveLocation::FIELD_DEFINITION,
                 async_graphql::registry::__DirectiveLocation::OBJECT,
+                async_graphql::registry::__DirectiveLocation::ARGUMENT_DEFINITION,
+                async_graphql::registry::__DirectiveLocation::INPUT_FIELD_DEFINITION,
+                async_graphql::registry::__DirectiveLocation::ENUM_VALUE,
             ])),
             args: {
                 let mut args = IndexMap::new();
@@ -135,6 +259,8 @@ impl TypeDirective for rebac {
                         inaccessible: false,
                         tags: Default::default(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Default::default(),
                     },
                 );
                 args.insert(
@@ -148,6 +274,8 @@ impl TypeDirective for rebac {
                         inaccessible: false,
                         tags: Default::default(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Default::default(),
                     },
                 );
                 args.insert(
@@ -161,6 +289,23 @@ impl TypeDirective for rebac {
                         inaccessible: false,
                         tags: Default::default(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Default::default(),
+                    },
+                );
+                args.insert(
+                    ToOwned::to_owned("mode"),
+                    MetaInputValue {
+                        name: ToString::to_string("mode"),
+                        description: None,
+                        ty: <Option<String> as InputType>::create_type_info(registry),
+                        default_value: Some(ToString::to_string("\"deny\"")),
+                        visible: None,
+                        inaccessible: false,
+                        tags: Default::default(),
+                        is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Default::default(),
                     },
                 );
                 args.insert(
@@ -174,6 +319,8 @@ impl TypeDirective for rebac {
                         inaccessible: false,
                         tags: Default::default(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Default::default(),
                     },
                 );
                 args
@@ -190,11 +337,18 @@ impl async_graphql::registry::location_traits::Directive_At_FIELD_DEFINITION for
 
 impl async_graphql::registry::location_traits::Directive_At_OBJECT for rebac {}
 
+impl async_graphql::registry::location_traits::Directive_At_ARGUMENT_DEFINITION for rebac {}
+
+impl async_graphql::registry::location_traits::Directive_At_INPUT_FIELD_DEFINITION for rebac {}
+
+impl async_graphql::registry::location_traits::Directive_At_ENUM_VALUE for rebac {}
+
 impl rebac {
     pub fn apply<REL: Into<String>, OTY: Into<String>, OID: Into<String>>(
         rel: REL,
         otype: OTY,
         oid: OID,
+        mode: RebacMode,
     ) -> MetaDirectiveInvocation {
         let rel = rel.into();
         let otype = otype.into();
@@ -211,6 +365,10 @@ impl rebac {
             panic!("oid cannot contain ':', for '? {} {}:{}'", rel, otype, oid);
         }
 
+        if let Err(err) = oid.parse::<RebacOid>() {
+            panic!("{}, for '? {} {}:{}'", err, rel, otype, oid);
+        }
+
         let directive = Cow::into_owned(Cow::Borrowed("rebac"));
         let mut args = IndexMap::new();
         if let Some(val) = InputType::as_raw_value(&rel) {
@@ -235,6 +393,10 @@ impl rebac {
             ToString::to_string("result"),
             async_graphql::ScalarType::to_value(&true),
         );
+        args.insert(
+            ToString::to_string("mode"),
+            async_graphql::ScalarType::to_value(&mode.to_string()),
+        );
         MetaDirectiveInvocation {
             name: directive,
             args,
@@ -1,45 +1,124 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use async_graphql::parser::types::{
-    ExecutableDocument, Field, FragmentDefinition, InlineFragment, OperationDefinition,
-    OperationType, Selection,
+    Directive, ExecutableDocument, Field, FragmentDefinition, FragmentSpread, InlineFragment,
+    OperationDefinition, OperationType, Selection,
 };
-use async_graphql::registry::{MetaDirective, MetaField, MetaType, Registry};
-use async_graphql::{Pos, Positioned, ServerError};
+use async_graphql::registry::{
+    MetaDirectiveInvocation, MetaField, MetaInputValue, MetaType, Registry,
+};
+use async_graphql::{Name, Pos, Positioned, ServerError, Value, Variables};
 use tracing::{info, warn};
 
-use crate::RebacTypeDirective;
+use crate::dynamic_oid::resolve_args_oid;
+use crate::{RebacMode, RebacOid, RebacTypeDirective};
 
+/// collects `@rebac` of the fields the executed operation will actually resolve
+///
+/// fragment spreads are expanded in place, selections excluded by `@skip`/`@include` are not searched
 pub(crate) struct DirectiveSearcher<'a> {
     directives: Vec<FoundRebacTypeDirective>,
-    #[allow(dead_code)]
-    fragment: HashMap<String, Vec<MetaDirective>>,
+    fragments: HashMap<&'a Name, &'a FragmentDefinition>,
+    /// fragments already searched, their directives do not depend on where they are spread
+    expanded: HashSet<&'a Name>,
     errors: Vec<crate::Error>,
 
     registry: &'a Registry,
+    /// request variables, `@skip`/`@include` do not see defaults
+    variables: &'a Variables,
+    /// request variables with defaults, for arguments
+    arguments: Variables,
 }
 
 pub(crate) struct FoundRebacTypeDirective {
     pub(crate) type_directive: RebacTypeDirective,
     pub(crate) pos: Pos,
+    pub(crate) otype: String,
+    pub(crate) field: String,
+}
+
+/// field of the executed operation, `@rebac` found around it guards this field
+struct FieldSite<'b> {
+    field: &'b Field,
+    meta: &'b MetaField,
+    pos: Pos,
+    otype: &'b str,
+    name: &'b str,
+}
+
+/// where `@rebac` is declared
+enum Location<'b> {
+    Field,
+    Object(&'b str),
+    /// argument, input field or enum value
+    Input(&'b str),
+}
+
+impl std::fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Field => write!(f, "field"),
+            Location::Object(name) => write!(f, "object {}", name),
+            Location::Input(name) => write!(f, "input value {}", name),
+        }
+    }
+}
+
+/// request variables with defaults of the operation, as seen by resolvers
+pub(crate) fn with_defaults(variables: &Variables, operation: &OperationDefinition) -> Variables {
+    let mut variables = variables.clone();
+    for var in &operation.variable_definitions {
+        if let Some(default) = &var.node.default_value {
+            variables
+                .entry(var.node.name.node.clone())
+                .or_insert_with(|| default.node.clone());
+        }
+    }
+    variables
+}
+
+/// operation executed by the request, same rule as the executor
+pub(crate) fn find_operation<'a>(
+    doc: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Result<&'a Positioned<OperationDefinition>, crate::Error> {
+    let mut operations = doc.operations.iter();
+    match operation_name {
+        Some(operation_name) => operations
+            .find(|(name, _)| name.is_some_and(|name| name.as_str() == operation_name))
+            .map(|(_, def)| def)
+            .ok_or_else(|| crate::Error::RuntimeUnknownOperation(operation_name.to_string())),
+        None => match (operations.next(), operations.next()) {
+            (Some((_, def)), None) => Ok(def),
+            _ => Err(crate::Error::RuntimeOperationNameRequired),
+        },
+    }
 }
 
 impl<'a> DirectiveSearcher<'a> {
-    pub(crate) fn new(reg: &'a Registry) -> Self {
+    pub(crate) fn new(reg: &'a Registry, variables: &'a Variables) -> Self {
         Self {
             directives: Vec::new(),
-            fragment: HashMap::new(),
+            fragments: HashMap::new(),
+            expanded: HashSet::new(),
             errors: Vec::new(),
             registry: reg,
+            variables,
+            arguments: Variables::default(),
         }
     }
-    pub(crate) fn search(mut self, doc: &'a ExecutableDocument) -> Self {
-        doc.fragments.iter().for_each(|(_, def)| {
-            self.search_fragment_definition(&def.node);
-        });
-        doc.operations.iter().for_each(|(_, def)| {
-            self.search_operation_definition(&def.node);
-        });
+    pub(crate) fn search(
+        mut self,
+        doc: &'a ExecutableDocument,
+        operation: &'a OperationDefinition,
+    ) -> Self {
+        self.fragments = doc
+            .fragments
+            .iter()
+            .map(|(name, def)| (name, &def.node))
+            .collect();
+        self.arguments = with_defaults(self.variables, operation);
+        self.search_operation_definition(operation);
 
         self
     }
@@ -72,15 +151,48 @@ impl<'a> DirectiveSearcher<'a> {
             .for_each(|s| self.search_selection(&s.node, otype.clone()))
     }
     fn search_selection(&mut self, def: &'a Selection, parent_otype: String) {
+        if self.is_skipped(def.directives()) {
+            return;
+        }
         match def {
             Selection::Field(field) => self.search_field(&parent_otype, field),
-            Selection::FragmentSpread(_sfrag) => {
-                // do nothing, predefined fragment already checked in rsearch_rabac_fragdef
-            }
+            Selection::FragmentSpread(sfrag) => self.search_fragment_spread(sfrag),
             Selection::InlineFragment(ifrag) => self.search_inline_fragment(parent_otype, ifrag),
         }
     }
 
+    fn search_fragment_spread(&mut self, sfrag: &'a Positioned<FragmentSpread>) {
+        let name = &sfrag.node.fragment_name.node;
+        // unknown fragments are reported by validation, cyclic spreads stop at the second visit
+        let Some(def) = self.fragments.get(name).copied() else {
+            return;
+        };
+        if self.expanded.insert(name) {
+            self.search_fragment_definition(def);
+        }
+    }
+
+    /// `@skip`/`@include` evaluated the way the executor removes skipped selections,
+    /// against the request variables only, a condition which is not a boolean counts as `false`
+    fn is_skipped(&self, directives: &[Positioned<Directive>]) -> bool {
+        directives.iter().any(|d| {
+            let include = match d.node.name.node.as_str() {
+                "skip" => false,
+                "include" => true,
+                _ => return false,
+            };
+            let Some(condition) = d.node.get_argument("if") else {
+                return false;
+            };
+            let condition = condition
+                .node
+                .clone()
+                .into_const_with(|name| self.variables.get(&name).cloned().ok_or(()))
+                .unwrap_or_default();
+            include != matches!(condition, Value::Boolean(true))
+        })
+    }
+
     fn search_inline_fragment(
         &mut self,
         parent_otype: String,
@@ -133,31 +245,51 @@ impl<'a> DirectiveSearcher<'a> {
             child_field.ty,
             child_type.name()
         );
-        let result = child_field
+        let site = FieldSite {
+            field: &field.node,
+            meta: &child_field,
+            pos: field.pos,
+            otype: parent_otype,
+            name: &field_name,
+        };
+        if child_field
             .directive_invocations
             .iter()
-            .filter(|d| d.name == "rebac")
-            .map(|d| {
-                info!(
-                    "found at {} ... {{ {}: {} ({}) ... }}",
-                    &parent_otype,
-                    &field_name,
-                    child_field.ty,
-                    child_type.name()
-                );
-                RebacTypeDirective::try_from(d).map(|x| FoundRebacTypeDirective {
-                    type_directive: x,
-                    pos: field.pos,
-                })
-            })
-            .collect::<Result<Vec<_>, _>>();
-        match result {
-            Ok(directives) => {
-                self.directives.extend(directives);
-            }
-            Err(err) => {
-                self.errors.push(err);
-            }
+            .any(|d| d.name == "rebac")
+        {
+            info!(
+                "found at {} ... {{ {}: {} ({}) ... }}",
+                &parent_otype,
+                &field_name,
+                child_field.ty,
+                child_type.name()
+            );
+        }
+        self.collect(&child_field.directive_invocations, Location::Field, &site);
+        // object level directives guard every field returning the object
+        if let MetaType::Object {
+            directive_invocations,
+            ..
+        } = &child_type
+        {
+            self.collect(
+                directive_invocations,
+                Location::Object(child_type.name()),
+                &site,
+            );
+        }
+        for (name, value) in &field.node.arguments {
+            let Some(meta) = child_field.args.get(name.node.as_str()) else {
+                continue;
+            };
+            let Ok(value) = value
+                .node
+                .clone()
+                .into_const_with(|var| self.arguments.get(&var).cloned().ok_or(()))
+            else {
+                continue;
+            };
+            self.search_input_value(meta, &value, &site);
         }
         field
             .node
@@ -168,6 +300,102 @@ impl<'a> DirectiveSearcher<'a> {
             .for_each(|s| self.search_selection(&s.node, child_type.name().to_string()))
     }
 
+    /// `@rebac` of a supplied argument or input field, and of the enum values inside it
+    fn search_input_value(&mut self, meta: &MetaInputValue, value: &Value, site: &FieldSite<'_>) {
+        if matches!(value, Value::Null) {
+            return;
+        }
+        self.collect(
+            &meta.directive_invocations,
+            Location::Input(&meta.name),
+            site,
+        );
+        self.search_input_type(&meta.ty, value, site);
+    }
+
+    fn search_input_type(&mut self, ty: &str, value: &Value, site: &FieldSite<'_>) {
+        let registry = self.registry;
+        match (registry.concrete_type_by_name(ty), value) {
+            (_, Value::List(items)) => items
+                .iter()
+                .for_each(|x| self.search_input_type(ty, x, site)),
+            (Some(MetaType::InputObject { input_fields, .. }), Value::Object(fields)) => {
+                for (name, value) in fields {
+                    if let Some(meta) = input_fields.get(name.as_str()) {
+                        self.search_input_value(meta, value, site);
+                    }
+                }
+            }
+            // enum values of variables are given as strings
+            (Some(MetaType::Enum { enum_values, .. }), Value::Enum(_) | Value::String(_)) => {
+                let name = match value {
+                    Value::Enum(name) => name.as_str(),
+                    Value::String(name) => name.as_str(),
+                    _ => unreachable!(),
+                };
+                if let Some(meta) = enum_values.get(name) {
+                    self.collect(
+                        &meta.directive_invocations,
+                        Location::Input(&meta.name),
+                        site,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// directives checked before execution
+    ///
+    /// templated oids of fields are resolved per field execution, see `dynamic_oid`,
+    /// except `$args` in deny mode, which rejects the operation regardless of the parent value
+    fn collect(
+        &mut self,
+        directives: &[MetaDirectiveInvocation],
+        location: Location<'_>,
+        site: &FieldSite<'_>,
+    ) {
+        for directive in directives.iter().filter(|d| d.name == "rebac") {
+            let directive = match RebacTypeDirective::try_from(directive) {
+                Ok(directive) => directive,
+                Err(err) => {
+                    self.errors.push(err);
+                    continue;
+                }
+            };
+            let directive = match (&location, &directive.oid) {
+                (_, RebacOid::Static(_)) => Ok(directive),
+                (Location::Field, RebacOid::Args(_)) if directive.mode == RebacMode::Deny => {
+                    resolve_args_oid(&directive, site.field, site.meta, &self.arguments)
+                }
+                (Location::Field, _) => continue,
+                (Location::Input(_), RebacOid::Args(_)) => {
+                    resolve_args_oid(&directive, site.field, site.meta, &self.arguments)
+                }
+                (location, oid) => Err(crate::Error::DirectiveUnsupportedOidTemplate {
+                    oid: oid.to_string(),
+                    location: location.to_string(),
+                }),
+            };
+            match directive {
+                Ok(directive) => self.directives.push(FoundRebacTypeDirective {
+                    type_directive: match location {
+                        // supplying a guarded input can not be redacted
+                        Location::Input(_) => RebacTypeDirective {
+                            mode: RebacMode::Deny,
+                            ..directive
+                        },
+                        _ => directive,
+                    },
+                    pos: site.pos,
+                    otype: site.otype.to_string(),
+                    field: site.name.to_string(),
+                }),
+                Err(err) => self.errors.push(err),
+            }
+        }
+    }
+
     fn get_field_type_by_name(
         &self,
         otype: &String,
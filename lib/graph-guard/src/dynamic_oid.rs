@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+
+use async_graphql::parser::types::{
+    DocumentOperations, ExecutableDocument, Field, OperationType, Selection, SelectionSet,
+};
+use async_graphql::registry::{MetaField, MetaInputValue, MetaTypeName, Registry};
+use async_graphql::{
+    Name, PathSegment, Positioned, QueryPathNode, QueryPathSegment, ServerError, Value, Variables,
+};
+
+use crate::{RebacMode, RebacOid, RebacTypeDirective};
+
+const HIDDEN_PREFIX: &str = "_rebac_";
+const HIDDEN_OID_SUFFIX: &str = "_oid";
+
+/// guarded field selection whose oid is read from `$parent.<field>`
+///
+/// the selection is renamed to `_rebac_<idx>` and `_rebac_<idx>_oid: <field>` is selected next to it,
+/// both keys are replaced back by `response_key` once the parent object is resolved
+pub(crate) struct ParentOidBinding {
+    pub(crate) response_key: Name,
+    pub(crate) directive: RebacTypeDirective,
+    pub(crate) nullable: bool,
+}
+
+/// guarded field found in value of a resolved field
+pub(crate) struct ParentOidCheck {
+    /// list indexes from the resolved value to the parent object
+    pub(crate) indexes: Vec<usize>,
+    pub(crate) key: Name,
+    pub(crate) nullable: bool,
+    pub(crate) mode: RebacMode,
+    pub(crate) directive: Result<RebacTypeDirective, crate::Error>,
+}
+
+pub(crate) struct ParentOidRewriter<'a> {
+    bindings: Vec<ParentOidBinding>,
+    errors: Vec<crate::Error>,
+
+    registry: &'a Registry,
+}
+
+impl<'a> ParentOidRewriter<'a> {
+    pub(crate) fn new(reg: &'a Registry) -> Self {
+        Self {
+            bindings: Vec::new(),
+            errors: Vec::new(),
+            registry: reg,
+        }
+    }
+
+    pub(crate) fn rewrite(mut self, doc: &mut ExecutableDocument) -> Self {
+        for (_, def) in doc.fragments.iter_mut() {
+            let otype = def.node.type_condition.node.on.node.to_string();
+            self.rewrite_selection_set(&otype, &mut def.node.selection_set.node);
+        }
+        let operations = match &mut doc.operations {
+            DocumentOperations::Single(def) => vec![def],
+            DocumentOperations::Multiple(defs) => defs.values_mut().collect(),
+        };
+        for def in operations {
+            let root_type = match def.node.ty {
+                OperationType::Query => Some(self.registry.query_type.clone()),
+                OperationType::Mutation => self.registry.mutation_type.clone(),
+                OperationType::Subscription => self.registry.subscription_type.clone(),
+            };
+            match root_type {
+                Some(root_type) => {
+                    self.rewrite_selection_set(&root_type, &mut def.node.selection_set.node)
+                }
+                None => self
+                    .errors
+                    .push(crate::Error::RuntimeUnavailableOperationType(
+                        def.node.ty.to_string(),
+                    )),
+            }
+        }
+        self
+    }
+
+    fn rewrite_selection_set(&mut self, otype: &str, set: &mut SelectionSet) {
+        let mut injected = Vec::new();
+        for item in set.items.iter_mut() {
+            match &mut item.node {
+                Selection::Field(field) => {
+                    // hidden keys of the response are restored to their response keys, clients can't use them
+                    let response_key = field.node.response_key().node.as_str();
+                    if response_key.starts_with(HIDDEN_PREFIX) {
+                        self.errors
+                            .push(crate::Error::RuntimeReservedAlias(response_key.to_string()));
+                        continue;
+                    }
+                    let field_name = field.node.name.node.to_string();
+                    let Some(meta_field) = self
+                        .registry
+                        .types
+                        .get(otype)
+                        .and_then(|t| t.field_by_name(&field_name))
+                    else {
+                        // unknown fields are reported by validation
+                        continue;
+                    };
+                    let child_type = MetaTypeName::concrete_typename(&meta_field.ty).to_string();
+                    self.rewrite_selection_set(&child_type, &mut field.node.selection_set.node);
+
+                    let directive = meta_field
+                        .directive_invocations
+                        .iter()
+                        .filter(|d| d.name == "rebac")
+                        .map(RebacTypeDirective::try_from)
+                        .find(|d| {
+                            d.as_ref()
+                                .map_or(true, |d| matches!(d.oid, RebacOid::Parent(_)))
+                        });
+                    let directive = match directive {
+                        Some(Ok(directive)) => directive,
+                        Some(Err(err)) => {
+                            self.errors.push(err);
+                            continue;
+                        }
+                        None => continue,
+                    };
+                    let RebacOid::Parent(parent_field) = &directive.oid else {
+                        continue;
+                    };
+                    let Some(parent_field) = self.parent_field_name(otype, parent_field) else {
+                        self.errors.push(crate::Error::RuntimeUnknownTypeField {
+                            otype: otype.to_string(),
+                            field: parent_field.clone(),
+                        });
+                        continue;
+                    };
+
+                    let idx = self.bindings.len();
+                    let pos = field.pos;
+                    self.bindings.push(ParentOidBinding {
+                        response_key: field.node.response_key().node.clone(),
+                        directive,
+                        nullable: !meta_field.ty.ends_with('!'),
+                    });
+                    field.node.alias = Some(Positioned::new(hidden_key(idx), pos));
+                    injected.push(Positioned::new(
+                        Selection::Field(Positioned::new(
+                            Field {
+                                alias: Some(Positioned::new(hidden_oid_key(idx), pos)),
+                                name: Positioned::new(Name::new(parent_field), pos),
+                                arguments: Vec::new(),
+                                // follows @skip/@include of the guarded field
+                                directives: field.node.directives.clone(),
+                                selection_set: Default::default(),
+                            },
+                            pos,
+                        )),
+                        pos,
+                    ));
+                }
+                Selection::FragmentSpread(_) => {
+                    // fragment definitions are rewritten on their own
+                }
+                Selection::InlineFragment(ifrag) => {
+                    let otype = ifrag
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map(|x| x.node.on.node.to_string())
+                        .unwrap_or_else(|| otype.to_string());
+                    self.rewrite_selection_set(&otype, &mut ifrag.node.selection_set.node);
+                }
+            }
+        }
+        set.items.extend(injected);
+    }
+
+    /// field of parent type, `otype_id` is accepted for `otypeId`
+    fn parent_field_name(&self, otype: &str, field: &str) -> Option<String> {
+        let meta_type = self.registry.types.get(otype)?;
+        if meta_type.field_by_name(field).is_some() {
+            return Some(field.to_string());
+        }
+        let mut camel = String::with_capacity(field.len());
+        let mut upper = false;
+        for c in field.chars() {
+            match c {
+                '_' if !camel.is_empty() => upper = true,
+                c if upper => {
+                    camel.extend(c.to_uppercase());
+                    upper = false;
+                }
+                c => camel.push(c),
+            }
+        }
+        meta_type.field_by_name(&camel).map(|_| camel)
+    }
+
+    pub(crate) fn end(self) -> Result<Vec<ParentOidBinding>, ServerError> {
+        if self.errors.is_empty() {
+            Ok(self.bindings)
+        } else {
+            let message = self
+                .errors
+                .into_iter()
+                .map(|e| e.to_string())
+                .reduce(|a, b| a + "\n" + &b)
+                .unwrap_or_else(|| "Unknown error".to_string());
+            Err(ServerError::new(
+                format!("graph_guard::rebac, \n{}", message),
+                None,
+            ))
+        }
+    }
+}
+
+fn hidden_key(idx: usize) -> Name {
+    Name::new(format!("{}{}", HIDDEN_PREFIX, idx))
+}
+
+fn hidden_oid_key(idx: usize) -> Name {
+    Name::new(format!("{}{}{}", HIDDEN_PREFIX, idx, HIDDEN_OID_SUFFIX))
+}
+
+/// `(idx, is_oid)` of hidden key
+fn parse_hidden_key(key: &str) -> Option<(usize, bool)> {
+    let key = key.strip_prefix(HIDDEN_PREFIX)?;
+    match key.strip_suffix(HIDDEN_OID_SUFFIX) {
+        Some(idx) => idx.parse().ok().map(|idx| (idx, true)),
+        None => key.parse().ok().map(|idx| (idx, false)),
+    }
+}
+
+/// restores response keys of guarded fields in objects of `value` and returns directives to check
+///
+/// only objects directly returned by the field are visited, nested objects are handled by their own field
+pub(crate) fn take_parent_oids(
+    value: &mut Value,
+    bindings: &[ParentOidBinding],
+    indexes: &mut Vec<usize>,
+    found: &mut Vec<ParentOidCheck>,
+) {
+    match value {
+        Value::List(items) => {
+            for (idx, item) in items.iter_mut().enumerate() {
+                indexes.push(idx);
+                take_parent_oids(item, bindings, indexes, found);
+                indexes.pop();
+            }
+        }
+        Value::Object(map) if map.keys().any(|k| k.starts_with(HIDDEN_PREFIX)) => {
+            let mut guarded = Vec::new();
+            let mut oids = HashMap::new();
+            for (key, value) in std::mem::take(map) {
+                match parse_hidden_key(&key).filter(|(idx, _)| *idx < bindings.len()) {
+                    Some((idx, true)) => {
+                        oids.insert(idx, value);
+                    }
+                    Some((idx, false)) => {
+                        map.insert(bindings[idx].response_key.clone(), value);
+                        guarded.push(idx);
+                    }
+                    None => {
+                        map.insert(key, value);
+                    }
+                }
+            }
+            for idx in guarded {
+                let binding = &bindings[idx];
+                let directive = &binding.directive;
+                found.push(ParentOidCheck {
+                    indexes: indexes.clone(),
+                    key: binding.response_key.clone(),
+                    nullable: binding.nullable,
+                    mode: directive.mode,
+                    directive: const_to_oid(&directive.oid, oids.get(&idx))
+                        .map(|oid| directive.resolved(oid)),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// applies `null` or `omit` mode of failed check to the parent object
+pub(crate) fn redact_parent(value: &mut Value, check: &ParentOidCheck) {
+    let mut target = value;
+    for idx in &check.indexes {
+        target = match target {
+            Value::List(items) => match items.get_mut(*idx) {
+                Some(item) => item,
+                None => return,
+            },
+            _ => return,
+        };
+    }
+    match (check.mode, target) {
+        (RebacMode::Null, Value::Object(map)) if check.nullable => {
+            map.insert(check.key.clone(), Value::Null);
+        }
+        // non-null field, the parent object becomes null
+        (RebacMode::Null, target) => *target = Value::Null,
+        (RebacMode::Omit, Value::Object(map)) => {
+            map.shift_remove(&check.key);
+        }
+        _ => {}
+    }
+}
+
+/// removes the field at `path` from response data
+pub(crate) fn remove_path(value: &mut Value, path: &[PathSegment]) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut target = value;
+    for segment in parents {
+        target = match (segment, target) {
+            (PathSegment::Field(key), Value::Object(map)) => match map.get_mut(key.as_str()) {
+                Some(x) => x,
+                None => return,
+            },
+            (PathSegment::Index(idx), Value::List(items)) => match items.get_mut(*idx) {
+                Some(x) => x,
+                None => return,
+            },
+            _ => return,
+        };
+    }
+    if let (PathSegment::Field(key), Value::Object(map)) = (last, target) {
+        map.shift_remove(key.as_str());
+    }
+}
+
+/// replaces hidden keys of guarded fields in error path by their response keys
+pub(crate) fn restore_path(
+    path: Vec<PathSegment>,
+    bindings: &[ParentOidBinding],
+) -> Vec<PathSegment> {
+    path.into_iter()
+        .map(|segment| match &segment {
+            PathSegment::Field(key) => match parse_hidden_key(key) {
+                Some((idx, false)) if idx < bindings.len() => {
+                    PathSegment::Field(bindings[idx].response_key.to_string())
+                }
+                _ => segment,
+            },
+            PathSegment::Index(_) => segment,
+        })
+        .collect()
+}
+
+/// oid from `$args.<path>`, variables must already contain operation defaults
+///
+/// omitted arguments, or arguments of omitted variables, take the default value of `meta_field`
+pub(crate) fn resolve_args_oid(
+    directive: &RebacTypeDirective,
+    field: &Field,
+    meta_field: &MetaField,
+    variables: &Variables,
+) -> Result<RebacTypeDirective, crate::Error> {
+    let RebacOid::Args(path) = &directive.oid else {
+        return Ok(directive.clone());
+    };
+    let value = path.split_first().and_then(|(name, rest)| {
+        let value = field
+            .get_argument(name)
+            .and_then(|value| {
+                value
+                    .node
+                    .clone()
+                    .into_const_with(|var| variables.get(&var).cloned().ok_or(()))
+                    .ok()
+            })
+            .or_else(|| default_value(meta_field.args.get(name.as_str())?))?;
+        rest.iter().try_fold(value, |value, key| match value {
+            Value::Object(mut map) => map.swap_remove(key.as_str()),
+            _ => None,
+        })
+    });
+    const_to_oid(&directive.oid, value.as_ref()).map(|oid| directive.resolved(oid))
+}
+
+/// default value of an argument, the registry keeps it as graphql literal
+fn default_value(meta: &MetaInputValue) -> Option<Value> {
+    let literal = meta.default_value.as_ref()?;
+    let doc = async_graphql::parser::parse_query(format!("{{ f(v: {}) }}", literal)).ok()?;
+    let DocumentOperations::Single(operation) = doc.operations else {
+        return None;
+    };
+    let Selection::Field(field) = &operation.node.selection_set.node.items.first()?.node else {
+        return None;
+    };
+    field.node.get_argument("v")?.node.clone().into_const()
+}
+
+fn const_to_oid(oid: &RebacOid, value: Option<&Value>) -> Result<String, crate::Error> {
+    let unresolved = |reason: &str| crate::Error::RuntimeUnresolvedOid {
+        oid: oid.to_string(),
+        reason: reason.to_string(),
+    };
+    let value = match value {
+        Some(Value::String(x)) => x.clone(),
+        Some(Value::Number(x)) => x.to_string(),
+        Some(Value::Boolean(x)) => x.to_string(),
+        Some(Value::Enum(x)) => x.to_string(),
+        Some(Value::Null) => return Err(unresolved("value is null")),
+        Some(_) => return Err(unresolved("value is not a scalar")),
+        None => return Err(unresolved("value is not provided")),
+    };
+    if value.contains(':') {
+        return Err(unresolved("value cannot contain ':'"));
+    }
+    Ok(value)
+}
+
+/// `ServerError` located at the field, resolve extensions have no access to `Context::set_error_path`
+pub(crate) fn error_at(path_node: Option<&QueryPathNode<'_>>, message: String) -> ServerError {
+    let mut path = path_node
+        .into_iter()
+        .flat_map(|x| std::iter::once(x).chain(x.parents()))
+        .map(|node| match node.segment {
+            QueryPathSegment::Index(idx) => PathSegment::Index(idx),
+            QueryPathSegment::Name(name) => PathSegment::Field(name.to_string()),
+        })
+        .collect::<Vec<_>>();
+    path.reverse();
+    let mut error = ServerError::new(message, None);
+    error.path = path;
+    error
+}
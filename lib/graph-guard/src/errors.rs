@@ -12,13 +12,52 @@ pub enum Error {
     DirectiveUnknownArgument(String, async_graphql_value::ConstValue),
     #[error("graph_guard::rebac No required field {0:?}")]
     DirectiveNoRequiredField(Vec<String>),
+    #[error("graph_guard::rebac Invalid oid template {0}")]
+    DirectiveInvalidOidTemplate(String),
+    #[error("graph_guard::rebac Invalid mode {0}, expected deny, null or omit")]
+    DirectiveInvalidMode(String),
+    #[error("graph_guard::rebac Oid template {oid} is not supported on {location}")]
+    DirectiveUnsupportedOidTemplate { oid: String, location: String },
 
     #[error("graph_guard::runtime Unavailable Operation type {0}")]
     RuntimeUnavailableOperationType(String),
+    #[error("graph_guard::runtime Unknown operation {0}")]
+    RuntimeUnknownOperation(String),
+    #[error("graph_guard::runtime Operation name required")]
+    RuntimeOperationNameRequired,
     #[error("graph_guard::runtime Unknown type {otype}")]
     RuntimeUnknownType { otype: String },
     #[error("graph_guard::runtime Unknown field {field} type {otype}")]
     RuntimeUnknownTypeField { otype: String, field: String },
+    #[error("graph_guard::runtime Unresolved oid {oid}, {reason}")]
+    RuntimeUnresolvedOid { oid: String, reason: String },
+    #[error("graph_guard::runtime Alias {0} is reserved, response keys cannot start with _rebac_")]
+    RuntimeReservedAlias(String),
+
+    #[error("graph_guard::auth Malformed token, {0}")]
+    AuthMalformedToken(String),
+    #[error("graph_guard::auth Unsupported algorithm {0}")]
+    AuthUnsupportedAlgorithm(String),
+    #[error("graph_guard::auth No key {0:?} in the key set")]
+    AuthUnknownKey(Option<String>),
+    #[error("graph_guard::auth Invalid signature")]
+    AuthInvalidSignature,
+    #[error("graph_guard::auth Invalid issuer {0}")]
+    AuthInvalidIssuer(String),
+    #[error("graph_guard::auth Invalid audience {0:?}")]
+    AuthInvalidAudience(Vec<String>),
+    #[error("graph_guard::auth Token expired")]
+    AuthExpired,
+    #[error("graph_guard::auth Token not valid yet")]
+    AuthNotYetValid,
+    #[error("graph_guard::auth Unavailable key set, {0}")]
+    AuthUnavailableJwks(String),
+    #[error("graph_guard::auth Invalid principal {0}, expected user:<id>, service:<id>, api_key:<id> or <otype>:<id>#<relation>")]
+    AuthInvalidPrincipal(String),
+    #[error("graph_guard::auth Invalid api key")]
+    AuthInvalidApiKey,
+    #[error("graph_guard::auth Unavailable api keys, {0}")]
+    AuthUnavailableApiKeys(String),
 
     #[error(transparent)]
     OpenFGA(#[from] openfga_client::Error),
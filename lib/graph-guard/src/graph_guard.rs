@@ -1,48 +1,142 @@
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_graphql::extensions::{
-    ExtensionContext, NextParseQuery, NextValidation,
+    ExtensionContext, NextExecute, NextParseQuery, NextPrepareRequest, NextResolve, NextValidation,
+    ResolveInfo,
 };
 use async_graphql::parser::types::ExecutableDocument;
-use async_graphql::{ServerError, ServerResult, ValidationResult, Variables};
+use async_graphql::{
+    PathSegment, QueryPathNode, Request, Response, ServerError, ServerResult, ValidationResult,
+    Value, Variables,
+};
 use tokio::sync::Mutex;
-use tokio::task::JoinSet;
-use tracing::Instrument;
 
-use openfga_client::{CheckResponse, OpenFGA};
+use openfga_client::{AuthorizationEngine, CheckResponse, OpenFGA, Tuple};
 
-use crate::directive_searcher::{DirectiveSearcher, FoundRebacTypeDirective};
-use crate::{RebacTypeDirective, User};
+use crate::directive_searcher::{
+    find_operation, with_defaults, DirectiveSearcher, FoundRebacTypeDirective,
+};
+use crate::dynamic_oid::{
+    error_at, redact_parent, remove_path, resolve_args_oid, restore_path, take_parent_oids,
+    ParentOidBinding, ParentOidRewriter,
+};
+use crate::{RebacDecisions, RebacMode, RebacOid, RebacTypeDirective, User};
 
 pub struct GraphGuard {
-    openfga: OpenFGA,
+    engine: Arc<dyn AuthorizationEngine>,
+    group_tuples: Option<(String, String)>,
+    failure_policy: FailurePolicy,
+}
+
+/// decision of a check the engine failed to answer, after the retries of `OpenFGA`
+///
+/// answers of OpenFGA, including `InvalidInput` of a wrong tuple, are never failures
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// the check is denied and `mode` of its directive applies
+    #[default]
+    Deny,
+    /// the check passes, the field resolves as if allowed
+    Allow,
+    /// the request fails as in `deny` mode, whatever `mode` of the directive is
+    Error,
 }
 
 impl GraphGuard {
     pub fn new<T: Into<OpenFGA>>(openfga: T) -> Self {
+        Self::from_engine(openfga.into())
+    }
+
+    /// guard deciding by any `AuthorizationEngine`, `InMemoryEngine` to test directives without OpenFGA
+    pub fn from_engine<E: AuthorizationEngine + 'static>(engine: E) -> Self {
+        Self {
+            engine: Arc::new(engine),
+            group_tuples: None,
+            failure_policy: FailurePolicy::default(),
+        }
+    }
+
+    /// checks with `User.groups` as contextual tuples `<principal> <relation> <otype>:<group>`
+    pub fn with_group_tuples(self, otype: &str, relation: &str) -> Self {
+        Self {
+            group_tuples: Some((otype.to_string(), relation.to_string())),
+            ..self
+        }
+    }
+
+    /// outage behavior, `FailurePolicy::Deny` unless given
+    pub fn with_failure_policy(self, failure_policy: FailurePolicy) -> Self {
         Self {
-            openfga: openfga.into(),
+            failure_policy,
+            ..self
         }
     }
 }
 
+/// reason a check did not pass
+#[derive(Clone)]
+enum Denial {
+    /// decided by the engine, or the directive cannot be checked
+    Denied(String),
+    /// the engine failed to decide, `FailurePolicy` applies
+    Failed(String),
+}
+
+/// `(parent type, field) -> (mode, message)`
+type RedactedFields = HashMap<(String, String), (RebacMode, String)>;
+
 pub struct GraphGuardExtension {
-    openfga: OpenFGA,
+    engine: Arc<dyn AuthorizationEngine>,
+    group_tuples: Option<(String, String)>,
+    failure_policy: FailurePolicy,
+    decisions: RebacDecisions,
+    operation_name: Mutex<RefCell<Option<String>>>,
     shared: Mutex<RefCell<Vec<FoundRebacTypeDirective>>>,
+    variables: Mutex<RefCell<Variables>>,
+    bindings: Mutex<RefCell<Vec<ParentOidBinding>>>,
+    denied: Mutex<RefCell<Vec<ServerError>>>,
+    /// `(parent type, field)` of static directives failed at validation with `null` or `omit` mode
+    redacted: Mutex<RefCell<RedactedFields>>,
+    omitted: Mutex<RefCell<Vec<Vec<PathSegment>>>>,
+    errors: Mutex<RefCell<Vec<ServerError>>>,
 }
 
 impl async_graphql::extensions::ExtensionFactory for GraphGuard {
     fn create(&self) -> Arc<dyn async_graphql::extensions::Extension> {
         Arc::new(GraphGuardExtension {
-            openfga: self.openfga.clone(),
+            engine: self.engine.clone(),
+            group_tuples: self.group_tuples.clone(),
+            failure_policy: self.failure_policy,
+            decisions: RebacDecisions::default(),
+            operation_name: Mutex::new(RefCell::new(None)),
             shared: Mutex::new(RefCell::new(Vec::new())),
+            variables: Mutex::new(RefCell::new(Variables::default())),
+            bindings: Mutex::new(RefCell::new(Vec::new())),
+            denied: Mutex::new(RefCell::new(Vec::new())),
+            redacted: Mutex::new(RefCell::new(HashMap::new())),
+            omitted: Mutex::new(RefCell::new(Vec::new())),
+            errors: Mutex::new(RefCell::new(Vec::new())),
         })
     }
 }
 
 #[async_trait::async_trait]
 impl async_graphql::extensions::Extension for GraphGuardExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        self.operation_name
+            .lock()
+            .await
+            .replace(request.operation_name.clone());
+        next.run(ctx, request.data(self.decisions.clone())).await
+    }
+
     async fn parse_query(
         &self,
         ctx: &ExtensionContext<'_>,
@@ -50,11 +144,20 @@ impl async_graphql::extensions::Extension for GraphGuardExtension {
         variables: &Variables,
         next: NextParseQuery<'_>,
     ) -> ServerResult<ExecutableDocument> {
-        let parsed = next.run(ctx, query, variables).await?;
-        let directives = DirectiveSearcher::new(&ctx.schema_env.registry)
-            .search(&parsed)
+        let mut parsed = next.run(ctx, query, variables).await?;
+        let operation_name = self.operation_name.lock().await.take();
+        let operation = find_operation(&parsed, operation_name.as_deref())?;
+        let directives = DirectiveSearcher::new(&ctx.schema_env.registry, variables)
+            .search(&parsed, &operation.node)
+            .end()?;
+        // `$args` may refer variables omitted by the request
+        let variables = with_defaults(variables, &operation.node);
+        let bindings = ParentOidRewriter::new(&ctx.schema_env.registry)
+            .rewrite(&mut parsed)
             .end()?;
         self.shared.lock().await.replace(directives);
+        self.bindings.lock().await.replace(bindings);
+        self.variables.lock().await.replace(variables);
         Ok(parsed)
     }
 
@@ -64,85 +167,830 @@ impl async_graphql::extensions::Extension for GraphGuardExtension {
         ctx: &ExtensionContext<'_>,
         next: NextValidation<'_>,
     ) -> async_graphql::Result<ValidationResult, Vec<ServerError>> {
-        let span = tracing::Span::current();
         let user = ctx
             .data_opt::<User>()
             .cloned()
             .unwrap_or_else(Default::default);
-        let mut directives = JoinSet::new();
-        for x in self.shared.lock().await.take() {
-            let tuple = x.type_directive.tuple(&user);
-            let openfga = self.openfga.clone();
-            directives.spawn(
-                async move {
-                    openfga
-                        .check(tuple.clone(), None)
-                        .await
-                        .map(|r| (tuple, x, r))
-                        .map_err::<crate::Error, _>(|err| err.into())
-                }
-                .instrument(span.clone()),
-            );
-        }
+        let found = self.shared.lock().await.take();
+        let tuples = found
+            .iter()
+            .map(|x| x.type_directive.tuple(&user))
+            .collect::<Vec<_>>();
+        let decided = self.decide(&user, tuples.iter().cloned()).await;
         let mut errors: Vec<ServerError> = Vec::new();
-        while let Some(result) = directives.join_next().await {
-            let result = match result {
-                Ok(ok) => ok,
-                Err(_) => {
-                    continue;
-                }
+        let mut redacted = HashMap::new();
+        for (
+            tuple,
+            FoundRebacTypeDirective {
+                pos,
+                type_directive,
+                otype,
+                field,
+            },
+        ) in tuples.into_iter().zip(found)
+        {
+            let denial = denial(&tuple, type_directive.result, decided.get(&tuple));
+            let Some((mode, message)) = self.apply_policy(type_directive.mode, denial) else {
+                continue;
             };
-            let (
-                tuple,
-                FoundRebacTypeDirective {
-                    pos,
-                    type_directive,
-                },
-                result,
-            ) = match result {
-                Ok(ok) => ok,
-                Err(err) => {
-                    errors.push(ServerError::new(
-                        format!("Access denied for user, {}", err),
-                        None,
-                    ));
-                    continue;
-                }
-            };
-            match (type_directive, result) {
-                (
-                    RebacTypeDirective {
-                        result: expected, ..
-                    },
-                    CheckResponse::Ok {
-                        allowed: actual, ..
-                    },
-                ) if expected == actual => {}
-                (
-                    RebacTypeDirective {
-                        result: expected, ..
-                    },
-                    CheckResponse::Ok {
-                        allowed: actual, ..
-                    },
-                ) => {
-                    errors.push(ServerError::new(
-                        format!(
-                            "Access denied for user, {:?} expected {:?}, actual {:?}",
-                            tuple.to_string(),
-                            if expected { "allow" } else { "deny" },
-                            if actual { "allow" } else { "deny" },
-                        ),
-                        Some(pos),
-                    ));
+            match mode {
+                RebacMode::Deny => errors.push(ServerError::new(message, Some(pos))),
+                mode => {
+                    redacted.insert((otype, field), (mode, message));
                 }
-                _ => {}
             }
         }
         if errors.is_empty() {
+            self.redacted.lock().await.replace(redacted);
             Ok(next.run(ctx).await?)
         } else {
             Err(errors)
         }
     }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let mut response = next.run(ctx, operation_name).await;
+        // guarded fields selected directly on the root type
+        self.guard_parent_oids(ctx, None, &mut response.data).await;
+        let bindings = self.bindings.lock().await.take();
+        let denied = self.denied.lock().await.take();
+        if !denied.is_empty() {
+            return Response::from_errors(
+                denied
+                    .into_iter()
+                    .map(|mut err| {
+                        err.path = restore_path(err.path, &bindings);
+                        err
+                    })
+                    .collect(),
+            );
+        }
+        for path in self.omitted.lock().await.take() {
+            remove_path(&mut response.data, &restore_path(path, &bindings));
+        }
+        response.errors.extend(self.errors.lock().await.take());
+        for err in response.errors.iter_mut() {
+            err.path = restore_path(std::mem::take(&mut err.path), &bindings);
+        }
+        response
+    }
+
+    /// checks `@rebac` with templated oid and applies `mode` of failed checks
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let path_node = info.path_node;
+        let nullable = !info.return_type.ends_with('!');
+        let redacted = {
+            let redacted = self.redacted.lock().await;
+            let redacted = redacted.borrow();
+            if redacted.is_empty() {
+                None
+            } else {
+                redacted
+                    .get(&(info.parent_type.to_string(), info.name.to_string()))
+                    .cloned()
+            }
+        };
+        if let Some((mode, message)) = redacted {
+            return self.redact_field(path_node, nullable, mode, message).await;
+        }
+
+        let denied = match self.args_directives(ctx, &info).await {
+            Ok(directives) if directives.is_empty() => None,
+            Ok(directives) => {
+                let results = self
+                    .check_resolved(ctx, directives.iter().map(|(_, x)| x))
+                    .await;
+                directives
+                    .into_iter()
+                    .zip(results)
+                    .filter_map(|((mode, _), denial)| self.apply_policy(mode, denial))
+                    .reduce(|a, b| {
+                        if a.0.strictness() >= b.0.strictness() {
+                            a
+                        } else {
+                            b
+                        }
+                    })
+            }
+            Err(err) => Some((RebacMode::Deny, format!("Access denied for user, {}", err))),
+        };
+        if let Some((mode, message)) = denied {
+            return self.redact_field(path_node, nullable, mode, message).await;
+        }
+
+        let mut value = next.run(ctx, info).await?;
+        if let Some(value) = value.as_mut() {
+            self.guard_parent_oids(ctx, Some(path_node), value).await;
+        }
+        Ok(value)
+    }
+}
+
+impl GraphGuardExtension {
+    /// value of denied field, resolver of the field is never run
+    async fn redact_field(
+        &self,
+        path_node: &QueryPathNode<'_>,
+        nullable: bool,
+        mode: RebacMode,
+        message: String,
+    ) -> ServerResult<Option<Value>> {
+        let error = error_at(Some(path_node), message);
+        match mode {
+            RebacMode::Deny => {
+                self.denied.lock().await.borrow_mut().push(error.clone());
+                Err(error)
+            }
+            RebacMode::Null if nullable => {
+                self.errors.lock().await.borrow_mut().push(error);
+                Ok(Some(Value::Null))
+            }
+            // propagates null to the nearest nullable parent
+            RebacMode::Null => Err(error),
+            RebacMode::Omit => {
+                self.omitted.lock().await.borrow_mut().push(error.path);
+                Ok(Some(Value::Null))
+            }
+        }
+    }
+
+    /// `@rebac(oid: "$args...")` of the resolving field, resolved against its arguments
+    async fn args_directives(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: &ResolveInfo<'_>,
+    ) -> Result<Vec<(RebacMode, Result<RebacTypeDirective, crate::Error>)>, crate::Error> {
+        let Some(meta_field) = ctx
+            .schema_env
+            .registry
+            .types
+            .get(info.parent_type)
+            .and_then(|t| t.field_by_name(info.name))
+        else {
+            return Ok(Vec::new());
+        };
+        let directives = meta_field
+            .directive_invocations
+            .iter()
+            .filter(|d| d.name == "rebac")
+            .map(RebacTypeDirective::try_from)
+            // static oids and `$args` in deny mode are checked at validation
+            .filter(|d| {
+                !matches!(
+                    d,
+                    Ok(RebacTypeDirective {
+                        oid: RebacOid::Static(_) | RebacOid::Parent(_),
+                        ..
+                    }) | Ok(RebacTypeDirective {
+                        oid: RebacOid::Args(_),
+                        mode: RebacMode::Deny,
+                        ..
+                    })
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if directives.is_empty() {
+            return Ok(Vec::new());
+        }
+        let variables = self.variables.lock().await;
+        let variables = variables.borrow();
+        Ok(directives
+            .iter()
+            .map(|x| {
+                (
+                    x.mode,
+                    resolve_args_oid(x, info.field, meta_field, &variables),
+                )
+            })
+            .collect())
+    }
+
+    /// restores guarded fields of `value`, checks their `$parent` oids and applies `mode` of failed checks
+    async fn guard_parent_oids(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        path_node: Option<&QueryPathNode<'_>>,
+        value: &mut Value,
+    ) {
+        let mut found = Vec::new();
+        {
+            let bindings = self.bindings.lock().await;
+            let bindings = bindings.borrow();
+            if bindings.is_empty() {
+                return;
+            }
+            take_parent_oids(value, &bindings, &mut Vec::new(), &mut found);
+        }
+        if found.is_empty() {
+            return;
+        }
+        let results = self
+            .check_resolved(ctx, found.iter().map(|x| &x.directive))
+            .await;
+        for (check, denial) in found.iter().zip(results) {
+            let Some((mode, message)) = self.apply_policy(check.mode, denial) else {
+                continue;
+            };
+            let mut error = error_at(path_node, message);
+            error
+                .path
+                .extend(check.indexes.iter().map(|x| PathSegment::Index(*x)));
+            error.path.push(PathSegment::Field(check.key.to_string()));
+            match mode {
+                RebacMode::Deny => self.denied.lock().await.borrow_mut().push(error),
+                RebacMode::Null => {
+                    redact_parent(value, check);
+                    self.errors.lock().await.borrow_mut().push(error);
+                }
+                RebacMode::Omit => redact_parent(value, check),
+            }
+        }
+    }
+
+    /// mode and message of a denial, `None` when the check passed or the failure policy lets it pass
+    fn apply_policy(&self, mode: RebacMode, denial: Option<Denial>) -> Option<(RebacMode, String)> {
+        match (denial?, self.failure_policy) {
+            (Denial::Denied(message), _) | (Denial::Failed(message), FailurePolicy::Deny) => {
+                Some((mode, message))
+            }
+            (Denial::Failed(message), FailurePolicy::Allow) => {
+                tracing::warn!("allowed by failure policy, {}", message);
+                None
+            }
+            (Denial::Failed(message), FailurePolicy::Error) => Some((RebacMode::Deny, message)),
+        }
+    }
+
+    /// denial of every resolved directive, same tuple is checked once
+    async fn check_resolved<'b>(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        directives: impl IntoIterator<Item = &'b Result<RebacTypeDirective, crate::Error>>,
+    ) -> Vec<Option<Denial>> {
+        let user = ctx
+            .data_opt::<User>()
+            .cloned()
+            .unwrap_or_else(Default::default);
+        let checks = directives
+            .into_iter()
+            .map(|directive| match directive {
+                Ok(directive) => Ok((directive.tuple(&user), directive.result)),
+                Err(err) => Err(format!("Access denied for user, {}", err)),
+            })
+            .collect::<Vec<_>>();
+        let decided = self
+            .decide(
+                &user,
+                checks.iter().flatten().map(|(tuple, _)| tuple.clone()),
+            )
+            .await;
+        checks
+            .into_iter()
+            .map(|check| match check {
+                Ok((tuple, expected)) => denial(&tuple, expected, decided.get(&tuple)),
+                Err(message) => Some(Denial::Denied(message)),
+            })
+            .collect()
+    }
+
+    /// `allowed` of every tuple, or the reason it could not be checked
+    ///
+    /// tuples decided earlier in the request are reused, the rest are deduplicated into a `BatchCheck`
+    /// with the condition context and group tuples of `user`
+    async fn decide(
+        &self,
+        user: &User,
+        tuples: impl IntoIterator<Item = Tuple>,
+    ) -> HashMap<Tuple, Result<bool, Denial>> {
+        let mut decided = HashMap::new();
+        let mut pending = Vec::new();
+        let mut seen = HashSet::new();
+        for tuple in tuples {
+            if !seen.insert(tuple.clone()) {
+                continue;
+            }
+            match self.decisions.get(&tuple) {
+                Some(allowed) => {
+                    decided.insert(tuple, Ok(allowed));
+                }
+                None => pending.push(tuple),
+            }
+        }
+        if pending.is_empty() {
+            return decided;
+        }
+        let contextual_tuples = match &self.group_tuples {
+            Some((otype, relation)) => user.group_tuples(otype, relation),
+            None => Vec::new(),
+        };
+        match self
+            .engine
+            .batch_check(
+                pending.clone(),
+                Some(user.condition_context()),
+                contextual_tuples,
+            )
+            .await
+        {
+            Ok(responses) => {
+                for (tuple, response) in pending.into_iter().zip(responses) {
+                    let result = match response {
+                        CheckResponse::Ok { allowed, .. } => {
+                            self.decisions.insert(tuple.clone(), allowed);
+                            Ok(allowed)
+                        }
+                        response @ (CheckResponse::InternalServerError { .. }
+                        | CheckResponse::TransactionalConflict { .. }) => {
+                            Err(Denial::Failed(format!(
+                                "Access denied for user, {:?} {:?}",
+                                tuple.to_string(),
+                                response
+                            )))
+                        }
+                        response => Err(Denial::Denied(format!(
+                            "Access denied for user, {:?} {:?}",
+                            tuple.to_string(),
+                            response
+                        ))),
+                    };
+                    decided.insert(tuple, result);
+                }
+            }
+            Err(err) => {
+                let message = format!("Access denied for user, {}", crate::Error::from(err));
+                for tuple in pending {
+                    decided.insert(tuple, Err(Denial::Failed(message.clone())));
+                }
+            }
+        }
+        decided
+    }
+}
+
+/// denial of a check expecting `expected`, `None` when allowed
+fn denial(tuple: &Tuple, expected: bool, decided: Option<&Result<bool, Denial>>) -> Option<Denial> {
+    match decided {
+        Some(Ok(actual)) if *actual == expected => None,
+        Some(Ok(actual)) => Some(Denial::Denied(format!(
+            "Access denied for user, {:?} expected {:?}, actual {:?}",
+            tuple.to_string(),
+            if expected { "allow" } else { "deny" },
+            if *actual { "allow" } else { "deny" },
+        ))),
+        Some(Err(denial)) => Some(denial.clone()),
+        None => Some(Denial::Failed(format!(
+            "Access denied for user, {:?} unchecked",
+            tuple.to_string()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_graphql::{
+        Context, EmptySubscription, Enum, InputObject, Object, Schema, SimpleObject, Variables,
+    };
+    use openfga_client::InMemoryEngine;
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value as Json};
+
+    use super::*;
+    use crate::{rebac, Principal};
+
+    const MODEL: &str = r#"model
+  schema 1.1
+
+type user
+
+type service
+
+type api_key
+
+type group
+  relations
+    define member: [user, service, api_key]
+
+type item
+  relations
+    define viewer: [user, user:*, service, api_key, group#member]
+    define editor: [user, service]
+"#;
+
+    #[derive(SimpleObject, Clone)]
+    struct Item {
+        item_id: String,
+        #[graphql(directive = rebac::apply("viewer", "item", "$parent.item_id", RebacMode::Deny))]
+        secret: String,
+        #[graphql(directive = rebac::apply("viewer", "item", "$parent.item_id", RebacMode::Null))]
+        soft: Option<String>,
+        #[graphql(directive = rebac::apply("viewer", "item", "$parent.item_id", RebacMode::Omit))]
+        hidden: String,
+        #[graphql(directive = rebac::apply("editor", "item", "public", RebacMode::Null))]
+        note: Option<String>,
+    }
+
+    impl Item {
+        fn new(item_id: &str) -> Self {
+            Self {
+                item_id: item_id.to_string(),
+                secret: format!("secret {}", item_id),
+                soft: Some(format!("soft {}", item_id)),
+                hidden: format!("hidden {}", item_id),
+                note: Some(format!("note {}", item_id)),
+            }
+        }
+    }
+
+    #[derive(InputObject)]
+    struct ItemInput {
+        id: String,
+        #[graphql(directive = rebac::apply("editor", "item", "$args.input.id", RebacMode::Deny))]
+        label: Option<String>,
+        kind: Option<Kind>,
+    }
+
+    #[derive(Enum, Clone, Copy, PartialEq, Eq)]
+    enum Kind {
+        Open,
+        #[graphql(directive = rebac::apply("editor", "item", "public", RebacMode::Deny))]
+        Closed,
+    }
+
+    /// resolvers run, a denied field must never be resolved
+    #[derive(Default)]
+    struct Resolved(AtomicUsize);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn items(&self) -> Vec<Item> {
+            ["a", "b"].into_iter().map(Item::new).collect()
+        }
+
+        #[graphql(directive = rebac::apply("viewer", "item", "$args.id", RebacMode::Deny))]
+        async fn item(&self, ctx: &Context<'_>, #[graphql(default = "a")] id: String) -> Item {
+            ctx.data_unchecked::<Arc<Resolved>>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            Item::new(&id)
+        }
+
+        #[graphql(directive = rebac::apply("viewer", "item", "$args.id", RebacMode::Null))]
+        async fn label(&self, ctx: &Context<'_>, id: String) -> Option<String> {
+            ctx.data_unchecked::<Arc<Resolved>>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            Some(format!("label {}", id))
+        }
+
+        /// decision of `user:1 viewer <object>` made for the request
+        async fn decided(&self, ctx: &Context<'_>, object: String) -> Option<bool> {
+            ctx.data_unchecked::<RebacDecisions>().get(&Tuple::new(
+                "user:1".to_string(),
+                "viewer".to_string(),
+                object,
+            ))
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        #[graphql(directive = rebac::apply("editor", "item", "$args.id", RebacMode::Deny))]
+        async fn touch(&self, ctx: &Context<'_>, id: String) -> String {
+            ctx.data_unchecked::<Arc<Resolved>>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            id
+        }
+
+        async fn update(&self, ctx: &Context<'_>, input: ItemInput) -> String {
+            ctx.data_unchecked::<Arc<Resolved>>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            input.id
+        }
+
+        async fn tag(
+            &self,
+            ctx: &Context<'_>,
+            #[graphql(directive = rebac::apply("editor", "item", "$args.id", RebacMode::Deny))]
+            id: Option<String>,
+        ) -> Option<String> {
+            ctx.data_unchecked::<Arc<Resolved>>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            id
+        }
+    }
+
+    type TestSchema = Schema<Query, Mutation, EmptySubscription>;
+
+    fn schema(tuples: &[(&str, &str, &str)]) -> TestSchema {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        engine
+            .write(
+                tuples
+                    .iter()
+                    .map(|(user, relation, object)| {
+                        Tuple::new(user.to_string(), relation.to_string(), object.to_string())
+                            .into()
+                    })
+                    .collect(),
+            )
+            .unwrap();
+        Schema::build(Query, Mutation, EmptySubscription)
+            .extension(GraphGuard::from_engine(engine).with_group_tuples("group", "member"))
+            .finish()
+    }
+
+    /// `(data, [(path, message)], resolvers run)` of `request` executed as `user`
+    async fn execute(
+        schema: &TestSchema,
+        user: User,
+        request: impl Into<Request>,
+    ) -> (Json, Vec<(Json, String)>, usize) {
+        let resolved = Arc::new(Resolved::default());
+        let response = schema
+            .execute(request.into().data(user).data(resolved.clone()))
+            .await;
+        let errors = response
+            .errors
+            .iter()
+            .map(|x| (serde_json::to_value(&x.path).unwrap(), x.message.clone()))
+            .collect();
+        (
+            response.data.into_json().unwrap(),
+            errors,
+            resolved.0.load(Ordering::SeqCst),
+        )
+    }
+
+    fn user(principal: &str) -> User {
+        User {
+            principal: principal.parse().unwrap(),
+            ..User::default()
+        }
+    }
+
+    fn paths(errors: &[(Json, String)]) -> Vec<Json> {
+        errors.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn parent_oid_modes() {
+        let schema = schema(&[("user:1", "viewer", "item:a")]);
+        let (data, errors, _) =
+            execute(&schema, user("user:1"), "{ items { itemId soft hidden } }").await;
+        assert_eq!(
+            data,
+            json!({"items": [
+                {"itemId": "a", "soft": "soft a", "hidden": "hidden a"},
+                {"itemId": "b", "soft": null},
+            ]})
+        );
+        assert_eq!(paths(&errors), vec![json!(["items", 1, "soft"])]);
+
+        // deny mode fails the whole request
+        let (data, errors, _) =
+            execute(&schema, user("user:1"), "{ items { itemId s: secret } }").await;
+        assert_eq!(data, Json::Null);
+        assert_eq!(paths(&errors), vec![json!(["items", 1, "s"])]);
+
+        let (data, errors, _) = execute(&schema, user("user:2"), "{ items { itemId soft } }").await;
+        assert_eq!(
+            data,
+            json!({"items": [{"itemId": "a", "soft": null}, {"itemId": "b", "soft": null}]})
+        );
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn static_oid_modes() {
+        let schema = schema(&[("service:batch", "editor", "item:public")]);
+        let (data, errors, _) = execute(&schema, user("user:1"), "{ items { itemId note } }").await;
+        assert_eq!(
+            data,
+            json!({"items": [{"itemId": "a", "note": null}, {"itemId": "b", "note": null}]})
+        );
+        assert_eq!(
+            paths(&errors),
+            vec![json!(["items", 0, "note"]), json!(["items", 1, "note"])]
+        );
+
+        let (data, errors, _) = execute(&schema, user("service:batch"), "{ items { note } }").await;
+        assert_eq!(
+            data,
+            json!({"items": [{"note": "note a"}, {"note": "note b"}]})
+        );
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn args_oid() {
+        let schema = schema(&[("user:1", "viewer", "item:a")]);
+        let (data, errors, resolved) =
+            execute(&schema, user("user:1"), r#"{ item(id: "a") { itemId } }"#).await;
+        assert_eq!(
+            (data, errors, resolved),
+            (json!({"item": {"itemId": "a"}}), Vec::new(), 1)
+        );
+
+        // denied before the resolver runs
+        let (data, errors, resolved) =
+            execute(&schema, user("user:1"), r#"{ item(id: "b") { itemId } }"#).await;
+        assert_eq!((data, errors.len(), resolved), (Json::Null, 1, 0));
+
+        // omitted argument takes its default
+        let (data, _, _) = execute(&schema, user("user:1"), "{ item { itemId } }").await;
+        assert_eq!(data, json!({"item": {"itemId": "a"}}));
+
+        // omitted variable takes the default of the operation
+        let query = r#"query($id: String! = "b") { item(id: $id) { itemId } }"#;
+        let (data, _, _) = execute(&schema, user("user:1"), query).await;
+        assert_eq!(data, Json::Null);
+        let request = Request::new(query).variables(Variables::from_json(json!({"id": "a"})));
+        let (data, _, _) = execute(&schema, user("user:1"), request).await;
+        assert_eq!(data, json!({"item": {"itemId": "a"}}));
+
+        // null mode is checked at resolve
+        let (data, errors, resolved) = execute(
+            &schema,
+            user("user:1"),
+            r#"{ a: label(id: "a") b: label(id: "b") }"#,
+        )
+        .await;
+        assert_eq!(data, json!({"a": "label a", "b": null}));
+        assert_eq!(paths(&errors), vec![json!(["b"])]);
+        assert_eq!(resolved, 1);
+    }
+
+    #[tokio::test]
+    async fn fragments_and_operations() {
+        let schema = schema(&[("user:1", "viewer", "item:a")]);
+        // fragments of other operations and skipped fields are not checked
+        let query = r#"
+            query A($skip: Boolean!) { items { itemId ...Secret @skip(if: $skip) } }
+            query B { items { itemId ...Secret } }
+            fragment Secret on Item { secret }
+        "#;
+        let request = |operation: &str, skip: bool| {
+            Request::new(query)
+                .operation_name(operation)
+                .variables(Variables::from_json(json!({"skip": skip})))
+        };
+        let (data, errors, _) = execute(&schema, user("user:1"), request("A", true)).await;
+        assert_eq!(
+            (data, errors),
+            (
+                json!({"items": [{"itemId": "a"}, {"itemId": "b"}]}),
+                Vec::new()
+            )
+        );
+        let (data, errors, _) = execute(&schema, user("user:1"), request("A", false)).await;
+        assert_eq!(
+            (data, paths(&errors)),
+            (Json::Null, vec![json!(["items", 1, "secret"])])
+        );
+        let (data, _, _) = execute(&schema, user("user:1"), request("B", true)).await;
+        assert_eq!(data, Json::Null);
+
+        let (data, errors, _) = execute(
+            &schema,
+            user("user:1"),
+            "{ items { ... on Item { itemId soft @include(if: false) } } }",
+        )
+        .await;
+        assert_eq!(
+            (data, errors),
+            (
+                json!({"items": [{"itemId": "a"}, {"itemId": "b"}]}),
+                Vec::new()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn input_directives() {
+        let schema = schema(&[("user:1", "editor", "item:a")]);
+        let (data, errors, resolved) = execute(
+            &schema,
+            user("user:1"),
+            r#"mutation { touch(id: "a") update(input: {id: "a", label: "x"}) tag(id: "a") }"#,
+        )
+        .await;
+        assert_eq!(
+            (data, errors, resolved),
+            (
+                json!({"touch": "a", "update": "a", "tag": "a"}),
+                Vec::new(),
+                3
+            )
+        );
+
+        // an input field or argument is only checked when it is given
+        let (data, errors, resolved) = execute(
+            &schema,
+            user("user:1"),
+            r#"mutation { update(input: {id: "b"}) tag }"#,
+        )
+        .await;
+        assert_eq!(
+            (data, errors, resolved),
+            (json!({"update": "b", "tag": null}), Vec::new(), 2)
+        );
+
+        for mutation in [
+            r#"mutation { touch(id: "b") }"#,
+            r#"mutation { update(input: {id: "b", label: "x"}) }"#,
+            r#"mutation { update(input: {id: "a", kind: CLOSED}) }"#,
+            r#"mutation { tag(id: "b") }"#,
+        ] {
+            let (data, errors, resolved) = execute(&schema, user("user:1"), mutation).await;
+            assert_eq!(
+                (data, errors.len(), resolved),
+                (Json::Null, 1, 0),
+                "{}",
+                mutation
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn principals() {
+        let schema = schema(&[
+            ("user:*", "viewer", "item:a"),
+            ("service:batch", "viewer", "item:b"),
+            ("api_key:k1", "viewer", "item:b"),
+            ("group:ops#member", "viewer", "item:b"),
+        ]);
+        let soft = |data: Json| data["items"].clone();
+        let query = "{ items { soft } }";
+        // `user:*` is not granted to services and api keys
+        for principal in ["service:batch", "api_key:k1"] {
+            let (data, _, _) = execute(&schema, user(principal), query).await;
+            assert_eq!(
+                soft(data),
+                json!([{"soft": null}, {"soft": "soft b"}]),
+                "{}",
+                principal
+            );
+        }
+        // wildcard tuples hold for anonymous requests and every user
+        let (data, _, _) = execute(&schema, User::default(), query).await;
+        assert_eq!(soft(data), json!([{"soft": "soft a"}, {"soft": null}]));
+
+        // group claims are checked as contextual tuples
+        let member = User {
+            principal: Principal::User("2".to_string()),
+            groups: vec!["ops".to_string()],
+            ..User::default()
+        };
+        let (data, _, _) = execute(&schema, member, query).await;
+        assert_eq!(soft(data), json!([{"soft": "soft a"}, {"soft": "soft b"}]));
+        let (data, _, _) = execute(&schema, user("user:2"), query).await;
+        assert_eq!(soft(data), json!([{"soft": "soft a"}, {"soft": null}]));
+    }
+
+    #[tokio::test]
+    async fn decisions() {
+        let schema = schema(&[("user:1", "viewer", "item:a")]);
+        let (data, errors, _) = execute(
+            &schema,
+            user("user:1"),
+            r#"{ item(id: "a") { itemId } a: decided(object: "item:a") b: decided(object: "item:b") }"#,
+        )
+        .await;
+        assert_eq!(
+            (data, errors),
+            (
+                json!({"item": {"itemId": "a"}, "a": true, "b": null}),
+                Vec::new()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn reserved_alias() {
+        let schema = schema(&[]);
+        let (data, errors, _) =
+            execute(&schema, user("user:1"), "{ items { _rebac_0: itemId } }").await;
+        assert_eq!(data, Json::Null);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].1.contains("_rebac_0"), "{}", errors[0].1);
+    }
 }
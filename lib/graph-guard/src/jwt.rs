@@ -0,0 +1,719 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::signature::{self, RsaPublicKeyComponents, UnparsedPublicKey};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::{Error, Principal, User};
+
+/// JSON web key set, served at `jwks_uri` of an OIDC provider (`/keys` of Dex)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+impl FromStr for Jwks {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|err| Error::AuthUnavailableJwks(err.to_string()))
+    }
+}
+
+/// public key of `Jwks`, RSA keys have `n` and `e`, EC keys have `crv`, `x` and `y`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(default)]
+    pub kid: Option<String>,
+    #[serde(default)]
+    pub alg: Option<String>,
+    #[serde(default)]
+    pub n: Option<String>,
+    #[serde(default)]
+    pub e: Option<String>,
+    #[serde(default)]
+    pub crv: Option<String>,
+    #[serde(default)]
+    pub x: Option<String>,
+    #[serde(default)]
+    pub y: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum JwksSource {
+    /// fixed keys, for tests
+    Static(Jwks),
+    /// json file of the key set, read again when a token is signed by an unknown key
+    File(PathBuf),
+    /// `jwks_uri`, fetched again when a token is signed by an unknown key, at most once per `refresh_interval`
+    Url(String),
+}
+
+/// claims of an OIDC id token
+#[derive(Debug, Clone, Deserialize)]
+pub struct Claims {
+    pub iss: String,
+    pub sub: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub aud: Vec<String>,
+    pub exp: u64,
+    #[serde(default)]
+    pub nbf: Option<u64>,
+    /// client the token was issued to
+    #[serde(default)]
+    pub azp: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub preferred_username: Option<String>,
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// `aud` is either a string or an array of strings
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(aud) => vec![aud],
+        OneOrMany::Many(aud) => aud,
+    })
+}
+
+impl From<Claims> for User {
+    /// `user:<sub>` as principal, a readable name as hint and the client the token was issued to as agent
+    fn from(claims: Claims) -> Self {
+        User {
+            hint: claims.preferred_username.or(claims.email).or(claims.name),
+            principal: Principal::User(claims.sub),
+            ip: None,
+            agent: claims.azp.or(claims.aud.into_iter().next()),
+            groups: claims.groups,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct KeyCache {
+    jwks: Jwks,
+    loaded: Option<Instant>,
+}
+
+/// validates bearer tokens signed by an OIDC provider, issuer, audience, expiry and signature are checked
+///
+/// RS256, RS384, RS512, PS256, PS384, PS512, ES256 and ES384 are supported, Dex signs with RS256
+#[derive(Debug, Clone)]
+pub struct JwtAuthenticator {
+    source: JwksSource,
+    issuer: String,
+    audience: String,
+    leeway: Duration,
+    refresh_interval: Duration,
+    keys: Arc<RwLock<KeyCache>>,
+    /// held while the key set loads, `keys` stays readable for tokens of known keys meanwhile
+    reload: Arc<Mutex<()>>,
+    client: reqwest::Client,
+}
+
+/// whole request of the key set, a hanging provider fails tokens of unknown keys instead of holding them
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn jwks_client(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+impl JwtAuthenticator {
+    pub fn new(source: JwksSource, issuer: &str, audience: &str) -> Self {
+        let keys = match &source {
+            JwksSource::Static(jwks) => KeyCache {
+                jwks: jwks.clone(),
+                loaded: Some(Instant::now()),
+            },
+            _ => KeyCache::default(),
+        };
+        Self {
+            source,
+            issuer: issuer.to_string(),
+            audience: audience.to_string(),
+            leeway: Duration::from_secs(60),
+            refresh_interval: Duration::from_secs(60),
+            keys: Arc::new(RwLock::new(keys)),
+            reload: Arc::new(Mutex::new(())),
+            client: jwks_client(FETCH_TIMEOUT),
+        }
+    }
+
+    /// clock skew tolerated on `exp` and `nbf`, 60 seconds unless given
+    pub fn with_leeway(self, leeway: Duration) -> Self {
+        Self { leeway, ..self }
+    }
+
+    /// minimum interval between loads of the key set, 60 seconds unless given
+    pub fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
+        Self {
+            refresh_interval,
+            ..self
+        }
+    }
+
+    /// timeout of fetching `JwksSource::Url`, 5 seconds unless given
+    pub fn with_fetch_timeout(self, timeout: Duration) -> Self {
+        Self {
+            client: jwks_client(timeout),
+            ..self
+        }
+    }
+
+    /// user of a bearer token, see `From<Claims> for User`
+    pub async fn user(&self, token: &str) -> Result<User, Error> {
+        self.authenticate(token).await.map(User::from)
+    }
+
+    /// claims of a valid token
+    pub async fn authenticate(&self, token: &str) -> Result<Claims, Error> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(Error::AuthMalformedToken(
+                "expected header.payload.signature".to_string(),
+            ));
+        };
+        let header: Header = decode_json(header)?;
+        if key_type(&header.alg).is_none() {
+            return Err(Error::AuthUnsupportedAlgorithm(header.alg));
+        }
+        let signature = decode(signature)?;
+        let message = &token[..header_payload_len(token)];
+        let jwk = self.find_key(&header).await?;
+        verify(&jwk, &header.alg, message.as_bytes(), &signature)?;
+
+        let claims: Claims = decode_json(payload)?;
+        if claims.iss != self.issuer {
+            return Err(Error::AuthInvalidIssuer(claims.iss));
+        }
+        if !claims.aud.contains(&self.audience) {
+            return Err(Error::AuthInvalidAudience(claims.aud));
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        if Duration::from_secs(claims.exp) + self.leeway <= now {
+            return Err(Error::AuthExpired);
+        }
+        if claims
+            .nbf
+            .is_some_and(|nbf| Duration::from_secs(nbf) > now + self.leeway)
+        {
+            return Err(Error::AuthNotYetValid);
+        }
+        Ok(claims)
+    }
+
+    /// key of `kid`, or the only key usable with `alg` when the token has no `kid`, EC keys are usable with the algorithm of their curve only
+    ///
+    /// an unknown key reloads the key set, providers rotate keys and publish the new one before signing with it
+    async fn find_key(&self, header: &Header) -> Result<Jwk, Error> {
+        let refreshed = {
+            let keys = self.keys.read().await;
+            if let Some(jwk) = select_key(&keys.jwks, header) {
+                return Ok(jwk);
+            }
+            keys.loaded
+        };
+        let stale = refreshed.is_none_or(|x| x.elapsed() >= self.refresh_interval);
+        if matches!(self.source, JwksSource::Static(_)) || !stale {
+            return Err(Error::AuthUnknownKey(header.kid.clone()));
+        }
+        let _reload = self.reload.lock().await;
+        // another request may have reloaded meanwhile
+        if self.keys.read().await.loaded == refreshed {
+            let jwks = self.load().await?;
+            *self.keys.write().await = KeyCache {
+                jwks,
+                loaded: Some(Instant::now()),
+            };
+        }
+        select_key(&self.keys.read().await.jwks, header)
+            .ok_or_else(|| Error::AuthUnknownKey(header.kid.clone()))
+    }
+
+    async fn load(&self) -> Result<Jwks, Error> {
+        let unavailable = |err: &dyn std::fmt::Display| Error::AuthUnavailableJwks(err.to_string());
+        match &self.source {
+            JwksSource::Static(jwks) => Ok(jwks.clone()),
+            JwksSource::File(path) => tokio::fs::read_to_string(path)
+                .await
+                .map_err(|err| unavailable(&err))?
+                .parse(),
+            JwksSource::Url(url) => self
+                .client
+                .get(url)
+                .send()
+                .await
+                .and_then(|x| x.error_for_status())
+                .map_err(|err| unavailable(&err))?
+                .json()
+                .await
+                .map_err(|err| unavailable(&err)),
+        }
+    }
+}
+
+/// token of an `Authorization: Bearer <token>` header value
+pub fn bearer_token(authorization: &str) -> Option<&str> {
+    let (scheme, token) = authorization.trim().split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim())
+        .filter(|x| !x.is_empty())
+}
+
+/// token of a graphql websocket `connection_init` payload
+///
+/// clients send it as `{"Authorization": "Bearer <token>"}`, `{"headers": {"Authorization": ..}}` or `{"token": "<token>"}`
+pub fn connection_init_token(payload: &Value) -> Option<&str> {
+    let object = payload.as_object()?;
+    fn authorization(object: &serde_json::Map<String, Value>) -> Option<&str> {
+        object
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("authorization"))
+            .and_then(|(_, value)| bearer_token(value.as_str()?))
+    }
+    authorization(object)
+        .or_else(|| authorization(object.get("headers")?.as_object()?))
+        .or_else(|| object.get("token")?.as_str())
+}
+
+fn select_key(jwks: &Jwks, header: &Header) -> Option<Jwk> {
+    let usable = |jwk: &&Jwk| {
+        jwk.alg.as_ref().is_none_or(|alg| *alg == header.alg)
+            && key_type(&header.alg) == Some(jwk.kty.as_str())
+            && (jwk.kty != "EC" || jwk.crv.as_deref() == curve(&header.alg))
+    };
+    match &header.kid {
+        Some(kid) => jwks
+            .keys
+            .iter()
+            .filter(usable)
+            .find(|jwk| jwk.kid.as_ref() == Some(kid))
+            .cloned(),
+        None => {
+            let mut usable = jwks.keys.iter().filter(usable);
+            match (usable.next(), usable.next()) {
+                (Some(jwk), None) => Some(jwk.clone()),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn key_type(alg: &str) -> Option<&'static str> {
+    match alg {
+        "RS256" | "RS384" | "RS512" | "PS256" | "PS384" | "PS512" => Some("RSA"),
+        "ES256" | "ES384" => Some("EC"),
+        _ => None,
+    }
+}
+
+/// curve of an ECDSA algorithm
+fn curve(alg: &str) -> Option<&'static str> {
+    match alg {
+        "ES256" => Some("P-256"),
+        "ES384" => Some("P-384"),
+        _ => None,
+    }
+}
+
+fn verify(jwk: &Jwk, alg: &str, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+    let rsa = |params: &'static signature::RsaParameters| -> Result<(), Error> {
+        let (Some(n), Some(e)) = (&jwk.n, &jwk.e) else {
+            return Err(Error::AuthUnknownKey(jwk.kid.clone()));
+        };
+        RsaPublicKeyComponents {
+            n: decode(n)?,
+            e: decode(e)?,
+        }
+        .verify(params, message, signature)
+        .map_err(|_| Error::AuthInvalidSignature)
+    };
+    let ec = |crv: &str,
+              algorithm: &'static signature::EcdsaVerificationAlgorithm|
+     -> Result<(), Error> {
+        let (Some(x), Some(y)) = (&jwk.x, &jwk.y) else {
+            return Err(Error::AuthUnknownKey(jwk.kid.clone()));
+        };
+        if jwk.crv.as_deref() != Some(crv) {
+            return Err(Error::AuthUnknownKey(jwk.kid.clone()));
+        }
+        // uncompressed point
+        let point = [vec![0x04], decode(x)?, decode(y)?].concat();
+        UnparsedPublicKey::new(algorithm, point)
+            .verify(message, signature)
+            .map_err(|_| Error::AuthInvalidSignature)
+    };
+    match alg {
+        "RS256" => rsa(&signature::RSA_PKCS1_2048_8192_SHA256),
+        "RS384" => rsa(&signature::RSA_PKCS1_2048_8192_SHA384),
+        "RS512" => rsa(&signature::RSA_PKCS1_2048_8192_SHA512),
+        "PS256" => rsa(&signature::RSA_PSS_2048_8192_SHA256),
+        "PS384" => rsa(&signature::RSA_PSS_2048_8192_SHA384),
+        "PS512" => rsa(&signature::RSA_PSS_2048_8192_SHA512),
+        "ES256" => ec("P-256", &signature::ECDSA_P256_SHA256_FIXED),
+        "ES384" => ec("P-384", &signature::ECDSA_P384_SHA384_FIXED),
+        alg => Err(Error::AuthUnsupportedAlgorithm(alg.to_string())),
+    }
+}
+
+/// length of `header.payload`, the signed part of the token
+fn header_payload_len(token: &str) -> usize {
+    token.rfind('.').unwrap_or(token.len())
+}
+
+fn decode(part: &str) -> Result<Vec<u8>, Error> {
+    URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|err| Error::AuthMalformedToken(err.to_string()))
+}
+
+fn decode_json<T: for<'de> Deserialize<'de>>(part: &str) -> Result<T, Error> {
+    serde_json::from_slice(&decode(part)?).map_err(|err| Error::AuthMalformedToken(err.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair};
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const ISSUER: &str = "https://dex.example.com/";
+    const AUDIENCE: &str = "graph-hmb";
+
+    struct Signer {
+        kid: &'static str,
+        alg: &'static str,
+        key: EcdsaKeyPair,
+    }
+
+    impl Signer {
+        fn new(kid: &'static str, alg: &'static str) -> Self {
+            let algorithm = match alg {
+                "ES256" => &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+                _ => &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+            };
+            let rng = SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(algorithm, &rng).unwrap();
+            let key = EcdsaKeyPair::from_pkcs8(algorithm, pkcs8.as_ref(), &rng).unwrap();
+            Self { kid, alg, key }
+        }
+
+        fn jwk(&self) -> Value {
+            // uncompressed point, 0x04 followed by x and y
+            let point = &self.key.public_key().as_ref()[1..];
+            let (x, y) = point.split_at(point.len() / 2);
+            json!({
+                "kty": "EC",
+                "kid": self.kid,
+                "crv": if self.alg == "ES256" { "P-256" } else { "P-384" },
+                "x": URL_SAFE_NO_PAD.encode(x),
+                "y": URL_SAFE_NO_PAD.encode(y),
+            })
+        }
+
+        fn sign(&self, header: Value, claims: Value) -> String {
+            let message = format!("{}.{}", encode(&header), encode(&claims));
+            let signature = self
+                .key
+                .sign(&SystemRandom::new(), message.as_bytes())
+                .unwrap();
+            format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature))
+        }
+
+        fn token(&self, claims: Value) -> String {
+            self.sign(json!({"alg": self.alg, "kid": self.kid}), claims)
+        }
+    }
+
+    fn encode(value: &Value) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(value).unwrap())
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    /// valid claims overridden by `fields`
+    fn claims(fields: Value) -> Value {
+        let mut claims = json!({"iss": ISSUER, "sub": "abc", "aud": AUDIENCE, "exp": now() + 300});
+        for (key, value) in fields.as_object().unwrap() {
+            claims[key] = value.clone();
+        }
+        claims
+    }
+
+    fn jwks(keys: Vec<Value>) -> Jwks {
+        json!({ "keys": keys }).to_string().parse().unwrap()
+    }
+
+    fn authenticator(signers: &[&Signer]) -> JwtAuthenticator {
+        let jwks = jwks(signers.iter().map(|x| x.jwk()).collect());
+        JwtAuthenticator::new(JwksSource::Static(jwks), ISSUER, AUDIENCE)
+    }
+
+    #[tokio::test]
+    async fn valid_token() {
+        let signer = Signer::new("ec1", "ES256");
+        let auth = authenticator(&[&signer]);
+        let token = signer.token(claims(json!({
+            "aud": ["other", AUDIENCE],
+            "azp": "cli",
+            "email": "abc@example.com",
+            "preferred_username": "abc",
+            "groups": ["ops"],
+        })));
+        let user = auth.user(&token).await.unwrap();
+        assert_eq!(user.principal, Principal::User("abc".to_string()));
+        assert_eq!(user.hint.as_deref(), Some("abc"));
+        assert_eq!(user.agent.as_deref(), Some("cli"));
+        assert_eq!(user.groups, vec!["ops".to_string()]);
+
+        let signer = Signer::new("ec2", "ES384");
+        let auth = authenticator(&[&signer]);
+        let claims = auth.authenticate(&signer.token(claims(json!({})))).await;
+        assert_eq!(claims.unwrap().aud, vec![AUDIENCE.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn invalid_claims() {
+        let signer = Signer::new("ec1", "ES256");
+        let auth = authenticator(&[&signer]).with_leeway(Duration::from_secs(60));
+        let auth = &auth;
+        let authenticate = |fields: Value| {
+            let token = signer.token(claims(fields));
+            async move { auth.authenticate(&token).await }
+        };
+
+        let result = authenticate(json!({"iss": "https://other.example.com/"})).await;
+        assert!(
+            matches!(result, Err(Error::AuthInvalidIssuer(iss)) if iss == "https://other.example.com/")
+        );
+        let result = authenticate(json!({"aud": ["other"]})).await;
+        assert!(matches!(result, Err(Error::AuthInvalidAudience(aud)) if aud == vec!["other"]));
+        let result = authenticate(json!({"exp": now() - 120})).await;
+        assert!(matches!(result, Err(Error::AuthExpired)));
+        let result = authenticate(json!({"nbf": now() + 120})).await;
+        assert!(matches!(result, Err(Error::AuthNotYetValid)));
+
+        // within leeway
+        assert!(authenticate(json!({"exp": now() - 30})).await.is_ok());
+        assert!(authenticate(json!({"nbf": now() + 30})).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn invalid_signature() {
+        let signer = Signer::new("ec1", "ES256");
+        let other = Signer::new("ec1", "ES256");
+        let auth = authenticator(&[&signer]);
+
+        let result = auth.authenticate(&other.token(claims(json!({})))).await;
+        assert!(matches!(result, Err(Error::AuthInvalidSignature)));
+
+        // payload replaced after signing
+        let token = signer.token(claims(json!({})));
+        let parts = token.split('.').collect::<Vec<_>>();
+        let forged = encode(&claims(json!({"sub": "admin"})));
+        let token = [parts[0], &forged, parts[2]].join(".");
+        let result = auth.authenticate(&token).await;
+        assert!(matches!(result, Err(Error::AuthInvalidSignature)));
+
+        for token in ["abc", "a.b", "a.b.c.d", "!.!.!"] {
+            let result = auth.authenticate(token).await;
+            assert!(
+                matches!(result, Err(Error::AuthMalformedToken(_))),
+                "{}",
+                token
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn key_selection() {
+        let ec1 = Signer::new("ec1", "ES256");
+        let ec2 = Signer::new("ec2", "ES256");
+        let ec3 = Signer::new("ec3", "ES384");
+        let auth = authenticator(&[&ec1, &ec2, &ec3]);
+        for signer in [&ec1, &ec2, &ec3] {
+            let result = auth.authenticate(&signer.token(claims(json!({})))).await;
+            assert!(result.is_ok(), "{}", signer.kid);
+        }
+        let result = auth
+            .authenticate(&ec1.sign(json!({"alg": "ES256", "kid": "ec4"}), claims(json!({}))))
+            .await;
+        assert!(matches!(result, Err(Error::AuthUnknownKey(Some(kid))) if kid == "ec4"));
+
+        // without `kid` the only key usable with `alg` is taken
+        let result = auth
+            .authenticate(&ec3.sign(json!({"alg": "ES384"}), claims(json!({}))))
+            .await;
+        assert!(result.is_ok());
+        let result = auth
+            .authenticate(&ec1.sign(json!({"alg": "ES256"}), claims(json!({}))))
+            .await;
+        assert!(matches!(result, Err(Error::AuthUnknownKey(None))));
+    }
+
+    #[tokio::test]
+    async fn algorithm_mismatch() {
+        let ec1 = Signer::new("ec1", "ES256");
+        let ec2 = Signer::new("ec2", "ES384");
+        let mut jwk = ec1.jwk();
+        jwk["alg"] = json!("ES384");
+        let mut curve = ec2.jwk();
+        curve["kid"] = json!("p384");
+        let jwks = jwks(vec![jwk, curve]);
+        let auth = JwtAuthenticator::new(JwksSource::Static(jwks), ISSUER, AUDIENCE);
+
+        // `alg` of the key differs from the token
+        let result = auth.authenticate(&ec1.token(claims(json!({})))).await;
+        assert!(matches!(result, Err(Error::AuthUnknownKey(Some(_)))));
+        // RSA algorithm with an EC key
+        let result = auth
+            .authenticate(&ec1.sign(json!({"alg": "RS256", "kid": "ec1"}), claims(json!({}))))
+            .await;
+        assert!(matches!(result, Err(Error::AuthUnknownKey(Some(_)))));
+        // P-384 key for ES256
+        let result = auth
+            .authenticate(&ec1.sign(json!({"alg": "ES256", "kid": "p384"}), claims(json!({}))))
+            .await;
+        assert!(matches!(result, Err(Error::AuthUnknownKey(Some(_)))));
+
+        for alg in ["none", "HS256", "EdDSA"] {
+            let token = ec1.sign(json!({"alg": alg, "kid": "ec1"}), claims(json!({})));
+            let result = auth.authenticate(&token).await;
+            assert!(matches!(result, Err(Error::AuthUnsupportedAlgorithm(x)) if x == alg));
+        }
+    }
+
+    #[tokio::test]
+    async fn file_rotation() {
+        let ec1 = Signer::new("ec1", "ES256");
+        let ec2 = Signer::new("ec2", "ES256");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let write = |keys: Vec<Value>| {
+            std::fs::write(file.path(), json!({ "keys": keys }).to_string()).unwrap()
+        };
+        write(vec![ec1.jwk()]);
+        let source = JwksSource::File(file.path().to_path_buf());
+        let auth = JwtAuthenticator::new(source.clone(), ISSUER, AUDIENCE);
+        let reloading =
+            JwtAuthenticator::new(source, ISSUER, AUDIENCE).with_refresh_interval(Duration::ZERO);
+        for auth in [&auth, &reloading] {
+            assert!(auth
+                .authenticate(&ec1.token(claims(json!({}))))
+                .await
+                .is_ok());
+        }
+
+        write(vec![ec1.jwk(), ec2.jwk()]);
+        // loaded within `refresh_interval`
+        let result = auth.authenticate(&ec2.token(claims(json!({})))).await;
+        assert!(matches!(result, Err(Error::AuthUnknownKey(_))));
+        let result = reloading.authenticate(&ec2.token(claims(json!({})))).await;
+        assert!(result.is_ok());
+    }
+
+    /// serves `jwks` to the first request, later requests are never answered
+    async fn serve_once(jwks: Value) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/keys", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut hanging = Vec::new();
+            let mut body = Some(jwks.to_string());
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = Vec::new();
+                let mut read = [0; 1024];
+                while !buffer.ends_with(b"\r\n\r\n") {
+                    let n = socket.read(&mut read).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    buffer.extend_from_slice(&read[..n]);
+                }
+                match body.take() {
+                    Some(body) => {
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        socket.write_all(response.as_bytes()).await.unwrap();
+                    }
+                    None => hanging.push(socket),
+                }
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn url_reload_timeout() {
+        let ec1 = Signer::new("ec1", "ES256");
+        let ec2 = Signer::new("ec2", "ES256");
+        let url = serve_once(json!({ "keys": [ec1.jwk()] })).await;
+        let auth = JwtAuthenticator::new(JwksSource::Url(url), ISSUER, AUDIENCE)
+            .with_refresh_interval(Duration::ZERO)
+            .with_fetch_timeout(Duration::from_millis(500));
+        assert!(auth
+            .authenticate(&ec1.token(claims(json!({}))))
+            .await
+            .is_ok());
+
+        let reloading = {
+            let auth = auth.clone();
+            let token = ec2.token(claims(json!({})));
+            tokio::spawn(async move { auth.authenticate(&token).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // tokens of known keys are not held by the hanging reload
+        let known = tokio::time::timeout(
+            Duration::from_millis(200),
+            auth.authenticate(&ec1.token(claims(json!({})))),
+        )
+        .await;
+        assert!(matches!(known, Ok(Ok(_))));
+        let result = reloading.await.unwrap();
+        assert!(matches!(result, Err(Error::AuthUnavailableJwks(_))));
+    }
+}
@@ -1,10 +1,21 @@
+pub use api_key::*;
+pub use decision::*;
 pub use directive::*;
 pub use errors::*;
 pub use graph_guard::*;
+pub use jwt::*;
+pub use principal::*;
+pub use row_authorizer::*;
 pub use user::*;
 
+mod api_key;
+mod decision;
 mod directive;
 mod directive_searcher;
+mod dynamic_oid;
 mod errors;
 mod graph_guard;
+mod jwt;
+mod principal;
+mod row_authorizer;
 mod user;
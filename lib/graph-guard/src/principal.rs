@@ -0,0 +1,91 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::Error;
+
+/// who a request acts as, checked against OpenFGA by `fga_notation`
+///
+/// written and parsed as its notation, `user:*`, `user:<id>`, `service:<id>`, `api_key:<id>` or `<otype>:<id>#<relation>`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum Principal {
+    /// unauthenticated request, `user:*`
+    #[default]
+    Anonymous,
+    /// human user of an OIDC token, `user:<id>`
+    User(String),
+    /// batch job or worker, `service:<id>`
+    ServiceAccount(String),
+    /// api key granted relations by itself, `api_key:<id>`
+    ApiKey(String),
+    /// every member of a userset, `<otype>:<id>#<relation>` such as `group:<id>#member`
+    Userset {
+        otype: String,
+        id: String,
+        relation: String,
+    },
+}
+
+impl Principal {
+    pub fn fga_notation(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self, Principal::Anonymous)
+    }
+
+    /// id of the principal, `None` for anonymous requests
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Principal::Anonymous => None,
+            Principal::User(id) | Principal::ServiceAccount(id) | Principal::ApiKey(id) => Some(id),
+            Principal::Userset { id, .. } => Some(id),
+        }
+    }
+}
+
+impl Display for Principal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Principal::Anonymous => write!(f, "user:*"),
+            Principal::User(id) => write!(f, "user:{}", id),
+            Principal::ServiceAccount(id) => write!(f, "service:{}", id),
+            Principal::ApiKey(id) => write!(f, "api_key:{}", id),
+            Principal::Userset {
+                otype,
+                id,
+                relation,
+            } => write!(f, "{}:{}#{}", otype, id, relation),
+        }
+    }
+}
+
+impl FromStr for Principal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::AuthInvalidPrincipal(s.to_string());
+        let (otype, id) = s.split_once(':').ok_or_else(invalid)?;
+        if otype.is_empty() || id.is_empty() {
+            return Err(invalid());
+        }
+        if let Some((id, relation)) = id.split_once('#') {
+            if id.is_empty() || id == "*" || relation.is_empty() {
+                return Err(invalid());
+            }
+            return Ok(Principal::Userset {
+                otype: otype.to_string(),
+                id: id.to_string(),
+                relation: relation.to_string(),
+            });
+        }
+        match (otype, id) {
+            ("user", "*") => Ok(Principal::Anonymous),
+            (_, "*") => Err(invalid()),
+            ("user", id) => Ok(Principal::User(id.to_string())),
+            ("service", id) => Ok(Principal::ServiceAccount(id.to_string())),
+            ("api_key", id) => Ok(Principal::ApiKey(id.to_string())),
+            _ => Err(invalid()),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use openfga_client::{AuthorizationEngine, CheckResponse, ContextualTuple, OpenFGA, Tuple};
+use table_traits::types::RowAuthError;
+use table_traits::{AllowedObjects, RowAuthorizer};
+
+use crate::User;
+
+/// `RowAuthorizer` of table rebac, resolved by OpenFGA for `user`
+///
+/// more than `max_objects` allowed objects are not inlined into `id IN (..)`, fetched rows are checked one by one instead
+#[derive(Clone, Debug)]
+pub struct OpenFgaRowAuthorizer {
+    engine: Arc<dyn AuthorizationEngine>,
+    user: User,
+    max_objects: usize,
+    group_tuples: Vec<ContextualTuple>,
+}
+
+impl OpenFgaRowAuthorizer {
+    pub fn new(openfga: OpenFGA, user: User) -> Self {
+        Self::from_engine(openfga, user)
+    }
+
+    /// same as `GraphGuard::from_engine`
+    pub fn from_engine<E: AuthorizationEngine + 'static>(engine: E, user: User) -> Self {
+        Self {
+            engine: Arc::new(engine),
+            user,
+            max_objects: 1000,
+            group_tuples: Vec::new(),
+        }
+    }
+
+    pub fn with_max_objects(self, max_objects: usize) -> Self {
+        Self {
+            max_objects,
+            ..self
+        }
+    }
+
+    /// same as `GraphGuard::with_group_tuples`
+    pub fn with_group_tuples(self, otype: &str, relation: &str) -> Self {
+        Self {
+            group_tuples: self.user.group_tuples(otype, relation),
+            ..self
+        }
+    }
+}
+
+#[async_trait]
+impl RowAuthorizer for OpenFgaRowAuthorizer {
+    async fn list_objects(&self, otype: &str, rel: &str) -> Result<AllowedObjects, RowAuthError> {
+        let objects = self
+            .engine
+            .list_objects(
+                otype,
+                rel,
+                &self.user.fga_notation(),
+                Some(self.user.condition_context()),
+                self.group_tuples.clone(),
+            )
+            .await
+            .map_err(|e| RowAuthError::Authorizer(e.to_string()))?;
+        // openfga truncates list objects by its own limit, a full result can not be trusted as complete
+        if objects.len() >= self.max_objects {
+            return Ok(AllowedObjects::TooMany);
+        }
+        let prefix = format!("{}:", otype);
+        Ok(AllowedObjects::Ids(
+            objects
+                .into_iter()
+                .filter_map(|x| x.strip_prefix(&prefix).map(ToString::to_string))
+                .collect(),
+        ))
+    }
+
+    async fn check_objects(
+        &self,
+        otype: &str,
+        rel: &str,
+        oids: Vec<String>,
+    ) -> Result<HashSet<String>, RowAuthError> {
+        let oids = oids
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let tuples = oids
+            .iter()
+            .map(|oid| {
+                Tuple::new(
+                    self.user.fga_notation(),
+                    rel.to_string(),
+                    format!("{}:{}", otype, oid),
+                )
+            })
+            .collect();
+        let responses = self
+            .engine
+            .batch_check(
+                tuples,
+                Some(self.user.condition_context()),
+                self.group_tuples.clone(),
+            )
+            .await
+            .map_err(|e| RowAuthError::Authorizer(e.to_string()))?;
+        let mut allowed = HashSet::new();
+        for (oid, response) in oids.into_iter().zip(responses) {
+            match response {
+                CheckResponse::Ok { allowed: true, .. } => {
+                    allowed.insert(oid);
+                }
+                CheckResponse::Ok { allowed: false, .. } => {}
+                response => return Err(RowAuthError::Authorizer(format!("{:?}", response))),
+            }
+        }
+        Ok(allowed)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::needless_update)] // struct update emitted by `#[derive(Table)]`
+mod test {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use async_graphql::SimpleObject;
+    use openfga_client::InMemoryEngine;
+    use pretty_assertions::assert_eq;
+    use sea_query::{Alias, PostgresQueryBuilder, Query, SelectStatement, TableRef};
+    use table_traits::types::{ConnectionError, DEFAULT_CONFIG};
+    use table_traits::{Field, Page, PageByCursor, RowAccess, Table, TableFilter};
+
+    use super::*;
+    use crate::Principal;
+
+    const MODEL: &str = r#"model
+  schema 1.1
+
+type user
+
+type doc
+  relations
+    define viewer: [user]
+"#;
+
+    const DOCS: [&str; 6] = ["a", "b", "c", "d", "e", "f"];
+
+    #[derive(Debug, Clone, PartialEq, SimpleObject, Table)]
+    #[table(schema = "public", table = "doc", rebac(otype = "doc", rel = "viewer"))]
+    pub struct Doc {
+        #[column(id, filter, sorter)]
+        pub doc_id: String,
+    }
+
+    /// `user:1` viewing `visible` docs
+    fn authorizer(visible: &[&str]) -> OpenFgaRowAuthorizer {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        engine
+            .write(
+                visible
+                    .iter()
+                    .map(|x| {
+                        Tuple::new(
+                            "user:1".to_string(),
+                            "viewer".to_string(),
+                            format!("doc:{}", x),
+                        )
+                        .into()
+                    })
+                    .collect(),
+            )
+            .unwrap();
+        let user = User {
+            principal: Principal::User("1".to_string()),
+            ..Default::default()
+        };
+        OpenFgaRowAuthorizer::from_engine(engine, user)
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    fn sql(query: &SelectStatement) -> String {
+        query.to_string(PostgresQueryBuilder)
+    }
+
+    #[tokio::test]
+    async fn list_objects() {
+        let authorizer = authorizer(&["a", "c"]);
+        let mut allowed = match authorizer.list_objects("doc", "viewer").await.unwrap() {
+            AllowedObjects::Ids(ids) => ids,
+            AllowedObjects::TooMany => panic!("expected ids"),
+        };
+        allowed.sort();
+        assert_eq!(allowed, ids(&["a", "c"]));
+        assert_eq!(
+            authorizer
+                .with_max_objects(2)
+                .list_objects("doc", "viewer")
+                .await
+                .unwrap(),
+            AllowedObjects::TooMany
+        );
+    }
+
+    #[tokio::test]
+    async fn check_objects() {
+        let allowed = authorizer(&["a", "c"])
+            .check_objects("doc", "viewer", ids(&["a", "b", "c", "a"]))
+            .await
+            .unwrap();
+        assert_eq!(allowed, HashSet::from(["a".to_string(), "c".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn rebac_condition_injects_visible_ids() {
+        let authorizer = authorizer(&["a", "c"]);
+        let mut context = DEFAULT_CONFIG
+            .context_as::<Doc, _>(None)
+            .with_authorizer(&authorizer);
+        // not authorized yet, fails closed
+        assert_eq!(
+            sql(Query::select()
+                .column(Alias::new("doc_id"))
+                .from(Doc::table())
+                .cond_where(DocFilter::default().to_condition(&mut context).unwrap())),
+            r#"SELECT "doc_id" FROM "public"."doc" WHERE 1 = 2"#
+        );
+
+        context.authorize().await.unwrap();
+        let Some(cond) = context.rebac_condition() else {
+            panic!("expected id condition");
+        };
+        let query = sql(Query::select()
+            .column(Alias::new("doc_id"))
+            .from(Doc::table())
+            .and_where(cond));
+        assert!(
+            query == r#"SELECT "doc_id" FROM "public"."doc" WHERE "doc_id" IN ('a', 'c')"#
+                || query == r#"SELECT "doc_id" FROM "public"."doc" WHERE "doc_id" IN ('c', 'a')"#,
+            "{}",
+            query
+        );
+        // rows are already filtered by the query
+        let rows = vec![Doc {
+            doc_id: "b".to_string(),
+        }];
+        assert_eq!(context.check_rows(rows.clone()).await.unwrap(), rows);
+        assert_eq!(context.check_loaded(rows).await.unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn too_many_objects_checks_rows() {
+        let authorizer = authorizer(&["a", "c"]).with_max_objects(2);
+        let mut context = DEFAULT_CONFIG
+            .context_as::<Doc, _>(None)
+            .with_authorizer(&authorizer);
+        context.authorize().await.unwrap();
+        assert!(matches!(context.access, RowAccess::CheckRows));
+        assert_eq!(context.rebac_condition().map(|x| format!("{:?}", x)), None);
+        let rows = DOCS
+            .iter()
+            .map(|x| Doc {
+                doc_id: x.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let visible = context.check_rows(rows).await.unwrap();
+        assert_eq!(
+            visible.into_iter().map(|x| x.doc_id).collect::<Vec<_>>(),
+            ids(&["a", "c"])
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_authorizer() {
+        let mut context = DEFAULT_CONFIG.context_as::<Doc, _>(None);
+        assert!(matches!(
+            context.authorize().await,
+            Err(RowAuthError::MissingAuthorizer { .. })
+        ));
+    }
+
+    /// runs `Context::connection` over `DOCS` served in pages of `limit`, returns visible ids and every id query
+    async fn connection(
+        authorizer: &OpenFgaRowAuthorizer,
+        limit: u64,
+    ) -> (Vec<String>, Vec<String>) {
+        let queries = Mutex::new(Vec::new());
+        let mut context = DEFAULT_CONFIG
+            .context_as::<Doc, _>(None)
+            .with_authorizer(authorizer);
+        let page = Page::Cursor(PageByCursor {
+            first: Some(limit),
+            ..Default::default()
+        });
+        let connection = context
+            .connection(
+                Some(page),
+                DocFilter::default(),
+                DocSorter(Vec::new()),
+                |_| async { Ok(None) },
+                |query| {
+                    let mut queries = queries.lock().unwrap();
+                    queries.push(sql(&query));
+                    let offset = (queries.len() - 1) * limit as usize;
+                    let ids = DOCS
+                        .iter()
+                        .skip(offset)
+                        .take(limit as usize)
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>();
+                    async move { Ok(ids) }
+                },
+                |ids| async move {
+                    Ok::<_, ConnectionError>(
+                        ids.into_iter()
+                            .map(|doc_id| (doc_id.clone(), Doc { doc_id }))
+                            .collect::<HashMap<_, _>>(),
+                    )
+                },
+            )
+            .await
+            .unwrap();
+        let visible = connection
+            .edges
+            .into_iter()
+            .map(|x| x.node.doc_id)
+            .collect();
+        (visible, queries.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn connection_injects_visible_ids() {
+        let (visible, queries) = connection(&authorizer(&["c"]), 2).await;
+        assert_eq!(visible, ids(&["a", "b"]));
+        assert_eq!(
+            queries,
+            vec![
+                r#"SELECT "doc_id" FROM "public"."doc" WHERE "doc_id" IN ('c') LIMIT 2"#
+                    .to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn connection_refills_checked_rows() {
+        let authorizer = authorizer(&["a", "d", "e", "f"]).with_max_objects(1);
+        let (visible, queries) = connection(&authorizer, 2).await;
+        assert_eq!(visible, ids(&["a", "d"]));
+        assert_eq!(queries.len(), 2);
+        // rows are checked after loading, the id query is not restricted
+        assert_eq!(
+            queries[0],
+            r#"SELECT "doc_id" FROM "public"."doc" WHERE TRUE LIMIT 2"#
+        );
+    }
+
+    #[tokio::test]
+    async fn connection_refill_stops_when_exhausted() {
+        let authorizer = authorizer(&["a", "b"]).with_max_objects(1);
+        let (visible, queries) = connection(&authorizer, 4).await;
+        assert_eq!(visible, ids(&["a", "b"]));
+        assert_eq!(queries.len(), 2);
+    }
+}
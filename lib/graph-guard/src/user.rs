@@ -1,30 +1,70 @@
 use std::net::IpAddr;
 
+use openfga_client::{ConditionContext, ContextualTuple, Tuple};
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, Time};
+
+use crate::Principal;
+
+/// caller of a request, the principal is checked against OpenFGA and the rest is given to conditions and logs
 #[derive(Debug, Default, Clone)]
 pub struct User {
     pub hint: Option<String>,
-    pub id: Option<String>,
+    pub principal: Principal,
     pub ip: Option<IpAddr>,
     pub agent: Option<String>,
+    /// group claims of the authenticated token
+    pub groups: Vec<String>,
 }
 
 impl User {
     pub fn fga_notation(&self) -> String {
-        if let Some(id) = &self.id {
-            return format!("user:{}", id);
+        self.principal.fga_notation()
+    }
+
+    /// context of OpenFGA conditions, `ip`, `user_agent` and `current_time`
+    ///
+    /// `current_time` is truncated to minutes, so decisions can be cached across requests
+    pub fn condition_context(&self) -> ConditionContext {
+        let mut context = ConditionContext::new();
+        if let Some(ip) = &self.ip {
+            context.insert("ip".to_string(), ip.to_string().into());
         }
+        if let Some(agent) = &self.agent {
+            context.insert("user_agent".to_string(), agent.clone().into());
+        }
+        let now = OffsetDateTime::now_utc();
+        let now =
+            now.replace_time(Time::from_hms(now.hour(), now.minute(), 0).unwrap_or(now.time()));
+        if let Ok(now) = now.format(&Rfc3339) {
+            context.insert("current_time".to_string(), now.into());
+        }
+        context
+    }
 
-        "user:*".to_string()
+    /// `<principal> <relation> <otype>:<group>` of every group, sent as contextual tuples instead of written to the store
+    pub fn group_tuples(&self, otype: &str, relation: &str) -> Vec<ContextualTuple> {
+        if self.principal.is_anonymous() {
+            return Vec::new();
+        }
+        self.groups
+            .iter()
+            .map(|group| {
+                Tuple::new(
+                    self.fga_notation(),
+                    relation.to_string(),
+                    format!("{}:{}", otype, group),
+                )
+                .into()
+            })
+            .collect()
     }
 }
 
 impl std::fmt::Display for User {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut debug_struct = if let Some(id) = &self.id {
-            f.debug_struct(id)
-        } else {
-            f.debug_struct("*")
-        };
+        let principal = self.principal.to_string();
+        let mut debug_struct = f.debug_struct(&principal);
         if let Some(hint) = &self.hint {
             debug_struct.field("hint", hint);
         }
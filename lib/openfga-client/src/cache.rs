@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use moka::sync::Cache;
+use opentelemetry::metrics::Counter;
+
+use crate::{ConditionContext, ContextualTuple, Tuple};
+
+#[derive(Debug, Clone)]
+pub struct DecisionCacheConfig {
+    /// maximum number of cached decisions, least recently used decisions are evicted first
+    pub max_capacity: u64,
+    /// decisions older than ttl are checked again even without invalidation
+    pub ttl: Duration,
+}
+
+impl Default for DecisionCacheConfig {
+    fn default() -> Self {
+        DecisionCacheConfig {
+            max_capacity: 10_000,
+            ttl: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct DecisionKey {
+    store_id: String,
+    authorization_model_id: String,
+    tuple: Tuple,
+    /// contextual tuples serialized in the order given
+    contextual_tuples: Option<String>,
+    /// condition context serialized with sorted keys
+    context: Option<String>,
+}
+
+impl DecisionKey {
+    pub(crate) fn new(
+        store_id: &str,
+        authorization_model_id: &str,
+        tuple: &Tuple,
+        contextual_tuples: &[ContextualTuple],
+        context: Option<&ConditionContext>,
+    ) -> Self {
+        DecisionKey {
+            store_id: store_id.to_string(),
+            authorization_model_id: authorization_model_id.to_string(),
+            tuple: tuple.clone(),
+            contextual_tuples: (!contextual_tuples.is_empty())
+                .then(|| serde_json::to_string(contextual_tuples).unwrap_or_default()),
+            context: context.map(|x| {
+                serde_json::to_string(&x.iter().collect::<BTreeMap<_, _>>()).unwrap_or_default()
+            }),
+        }
+    }
+}
+
+/// `allowed` of checked tuples shared between requests, cheap to clone
+///
+/// every invalidation bumps `generation`, decisions checked before the latest invalidation are not stored,
+/// so a check racing with our own tuple write can't put stale decision back into cache
+///
+/// `clear` holds the write lock of `generation`, inserts the read lock from the generation check to the insert,
+/// so a clear can't land between the check and the insert
+#[derive(Clone)]
+pub struct DecisionCache {
+    decisions: Cache<DecisionKey, bool>,
+    generation: Arc<RwLock<u64>>,
+    hits: Counter<u64>,
+    misses: Counter<u64>,
+}
+
+impl std::fmt::Debug for DecisionCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecisionCache")
+            .field("entries", &self.decisions.entry_count())
+            .field("generation", &self.generation())
+            .finish()
+    }
+}
+
+impl DecisionCache {
+    pub fn new(config: &DecisionCacheConfig) -> Self {
+        let meter = opentelemetry::global::meter("openfga-client");
+        DecisionCache {
+            decisions: Cache::builder()
+                .max_capacity(config.max_capacity)
+                .time_to_live(config.ttl)
+                .build(),
+            generation: Arc::new(RwLock::new(0)),
+            hits: meter
+                .u64_counter("openfga.decision_cache.hits")
+                .with_description("checks answered by the decision cache")
+                .init(),
+            misses: meter
+                .u64_counter("openfga.decision_cache.misses")
+                .with_description("checks sent to OpenFGA with the decision cache enabled")
+                .init(),
+        }
+    }
+
+    /// drops every decision, call after writing or deleting tuples
+    ///
+    /// a written tuple may change decisions of any object related to it, so decisions are not invalidated one by one
+    pub fn clear(&self) {
+        let mut generation = self
+            .generation
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        *generation += 1;
+        self.decisions.invalidate_all();
+    }
+
+    pub(crate) fn get(&self, key: &DecisionKey) -> Option<bool> {
+        let allowed = self.decisions.get(key);
+        match allowed {
+            Some(_) => self.hits.add(1, &[]),
+            None => self.misses.add(1, &[]),
+        }
+        allowed
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        *self
+            .generation
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn insert_if_current(&self, generation: u64, key: DecisionKey, allowed: bool) {
+        let current = self
+            .generation
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        if *current != generation {
+            return;
+        }
+        self.decisions.insert(key, allowed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::stub::Stub;
+    use crate::{CheckResponse, Consistency, OpenFGA};
+
+    const ALLOWED: &str = r#"{"allowed":true,"resolution":""}"#;
+    const DENIED: &str = r#"{"allowed":false,"resolution":""}"#;
+
+    fn tuple(user: &str) -> Tuple {
+        Tuple::new(user.to_string(), "viewer".to_string(), "doc:a".to_string())
+    }
+
+    fn key(
+        contextual_tuples: &[ContextualTuple],
+        context: Option<&ConditionContext>,
+    ) -> DecisionKey {
+        DecisionKey::new(
+            "store",
+            "model",
+            &tuple("user:1"),
+            contextual_tuples,
+            context,
+        )
+    }
+
+    fn context(entries: &[(&str, serde_json::Value)]) -> ConditionContext {
+        entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    fn allowed(response: CheckResponse) -> bool {
+        match response {
+            CheckResponse::Ok { allowed, .. } => allowed,
+            response => panic!("unexpected {:?}", response),
+        }
+    }
+
+    #[test]
+    fn key_separation() {
+        let ip = context(&[("ip", json!("10.0.0.1")), ("at", json!(1))]);
+        let other_ip = context(&[("ip", json!("10.0.0.2")), ("at", json!(1))]);
+        let group = ContextualTuple::from(Tuple::new(
+            "user:1".to_string(),
+            "member".to_string(),
+            "group:a".to_string(),
+        ));
+        let other_group = ContextualTuple::from(Tuple::new(
+            "user:1".to_string(),
+            "member".to_string(),
+            "group:b".to_string(),
+        ));
+        assert_eq!(key(&[], Some(&ip)), key(&[], Some(&ip.clone())));
+        assert!(key(&[], None) != key(&[], Some(&ip)));
+        assert!(key(&[], Some(&ip)) != key(&[], Some(&other_ip)));
+        assert!(key(&[], None) != key(std::slice::from_ref(&group), None));
+        assert!(key(&[group], None) != key(&[other_group], None));
+        assert!(key(&[], None) != DecisionKey::new("store", "model", &tuple("user:2"), &[], None));
+        assert!(key(&[], None) != DecisionKey::new("store", "other", &tuple("user:1"), &[], None));
+    }
+
+    #[test]
+    fn stale_generation_is_not_stored() {
+        let cache = DecisionCache::new(&DecisionCacheConfig::default());
+        let generation = cache.generation();
+        cache.clear();
+        cache.insert_if_current(generation, key(&[], None), true);
+        assert_eq!(cache.get(&key(&[], None)), None);
+
+        cache.insert_if_current(cache.generation(), key(&[], None), true);
+        assert_eq!(cache.get(&key(&[], None)), Some(true));
+        cache.clear();
+        assert_eq!(cache.get(&key(&[], None)), None);
+    }
+
+    fn openfga(stub: &Stub, cache: &DecisionCache) -> OpenFGA {
+        OpenFGA::new(stub.url.clone(), "store".to_string(), "model".to_string())
+            .with_decision_cache(cache.clone())
+    }
+
+    #[tokio::test]
+    async fn cached_until_write() {
+        let stub = Stub::serve(vec![(200, ALLOWED), (200, "{}"), (200, DENIED)]).await;
+        let cache = DecisionCache::new(&DecisionCacheConfig::default());
+        let openfga = openfga(&stub, &cache);
+        assert!(allowed(openfga.check(tuple("user:1"), None).await.unwrap()));
+        assert!(allowed(openfga.check(tuple("user:1"), None).await.unwrap()));
+        assert_eq!(stub.requests().len(), 1);
+
+        openfga
+            .write(vec![tuple("user:2").into()], Vec::new())
+            .await
+            .unwrap();
+        assert!(!allowed(
+            openfga.check(tuple("user:1"), None).await.unwrap()
+        ));
+        let paths = stub
+            .requests()
+            .into_iter()
+            .map(|x| x.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/stores/store/check",
+                "/stores/store/write",
+                "/stores/store/check"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cached_by_context_and_contextual_tuples() {
+        let stub = Stub::serve(vec![(200, ALLOWED)]).await;
+        let cache = DecisionCache::new(&DecisionCacheConfig::default());
+        let openfga = openfga(&stub, &cache);
+        let ip = context(&[("ip", json!("10.0.0.1"))]);
+        let grouped = openfga.clone().with_contextual_tuples(vec![Tuple::new(
+            "user:1".to_string(),
+            "member".to_string(),
+            "group:a".to_string(),
+        )
+        .into()]);
+        openfga.check(tuple("user:1"), None).await.unwrap();
+        openfga
+            .check(tuple("user:1"), Some(ip.clone()))
+            .await
+            .unwrap();
+        grouped.check(tuple("user:1"), None).await.unwrap();
+        assert_eq!(stub.requests().len(), 3);
+
+        openfga.check(tuple("user:1"), Some(ip)).await.unwrap();
+        grouped.check(tuple("user:1"), None).await.unwrap();
+        openfga
+            .batch_check(vec![tuple("user:1")], None)
+            .await
+            .unwrap();
+        assert_eq!(stub.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn higher_consistency_bypasses_cache() {
+        let stub = Stub::serve(vec![(200, ALLOWED), (200, DENIED)]).await;
+        let cache = DecisionCache::new(&DecisionCacheConfig::default());
+        openfga(&stub, &cache)
+            .check(tuple("user:1"), None)
+            .await
+            .unwrap();
+        let openfga = openfga(&stub, &cache).with_consistency(Consistency::HigherConsistency);
+        assert!(!allowed(
+            openfga.check(tuple("user:1"), None).await.unwrap()
+        ));
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].body["consistency"], json!("HIGHER_CONSISTENCY"));
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+
+use crate::{CheckResponse, ConditionContext, ContextualTuple, Error, OpenFGA, Tuple};
+
+/// decisions `GraphGuard` and `OpenFgaRowAuthorizer` are made by, `OpenFGA` or `InMemoryEngine` for tests
+///
+/// `contextual_tuples` are evaluated in addition to the contextual tuples of the engine itself
+#[async_trait]
+pub trait AuthorizationEngine: std::fmt::Debug + Send + Sync {
+    /// same as `OpenFGA::batch_check`, responses are in the order of `tuple_keys`
+    async fn batch_check(
+        &self,
+        tuple_keys: Vec<Tuple>,
+        context: Option<ConditionContext>,
+        contextual_tuples: Vec<ContextualTuple>,
+    ) -> Result<Vec<CheckResponse>, Error>;
+
+    /// same as `OpenFGA::list_objects`, objects are returned as `otype:<id>`
+    async fn list_objects(
+        &self,
+        otype: &str,
+        relation: &str,
+        user: &str,
+        context: Option<ConditionContext>,
+        contextual_tuples: Vec<ContextualTuple>,
+    ) -> Result<Vec<String>, Error>;
+}
+
+impl OpenFGA {
+    fn with_additional_tuples(&self, contextual_tuples: Vec<ContextualTuple>) -> OpenFGA {
+        let mut openfga = self.clone();
+        openfga.contextual_tuples.extend(contextual_tuples);
+        openfga
+    }
+}
+
+#[async_trait]
+impl AuthorizationEngine for OpenFGA {
+    async fn batch_check(
+        &self,
+        tuple_keys: Vec<Tuple>,
+        context: Option<ConditionContext>,
+        contextual_tuples: Vec<ContextualTuple>,
+    ) -> Result<Vec<CheckResponse>, Error> {
+        if contextual_tuples.is_empty() {
+            return OpenFGA::batch_check(self, tuple_keys, context).await;
+        }
+        OpenFGA::batch_check(
+            &self.with_additional_tuples(contextual_tuples),
+            tuple_keys,
+            context,
+        )
+        .await
+    }
+
+    async fn list_objects(
+        &self,
+        otype: &str,
+        relation: &str,
+        user: &str,
+        context: Option<ConditionContext>,
+        contextual_tuples: Vec<ContextualTuple>,
+    ) -> Result<Vec<String>, Error> {
+        if contextual_tuples.is_empty() {
+            return OpenFGA::list_objects(self, otype, relation, user, context).await;
+        }
+        OpenFGA::list_objects(
+            &self.with_additional_tuples(contextual_tuples),
+            otype,
+            relation,
+            user,
+            context,
+        )
+        .await
+    }
+}
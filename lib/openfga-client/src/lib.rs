@@ -1,19 +1,40 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
-use opentelemetry_http::HeaderInjector;
-use reqwest::{Client, StatusCode};
+pub use cache::*;
+pub use engine::*;
+pub use management::*;
+pub use memory::*;
+pub use resilience::*;
+pub use tree::*;
+
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tokio::task::JoinSet;
+use tracing::{info, Instrument};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
+use crate::propagation::HeaderInjector;
+
+mod cache;
+mod engine;
+mod management;
+mod memory;
+mod propagation;
+mod resilience;
+#[cfg(test)]
+mod stub;
+mod tree;
+
 pub type ConditionContext = HashMap<String, serde_json::Value>;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ContextualTuples {
     tuple_keys: Vec<ContextualTuple>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Tuple {
     pub user: String,
     pub relation: String,
@@ -40,18 +61,155 @@ impl Tuple {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// consistency preference of queries, see <https://openfga.dev/docs/interacting/consistency>
+///
+/// `HigherConsistency` also bypasses `DecisionCache`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Consistency {
+    #[default]
+    Unspecified,
+    MinimizeLatency,
+    HigherConsistency,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Condition {
-    name: String,
-    context: ConditionContext,
+    pub name: String,
+    #[serde(default)]
+    pub context: ConditionContext,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl Condition {
+    pub fn new(name: String, context: ConditionContext) -> Self {
+        Self { name, context }
+    }
+}
+
+/// tuple with optional condition, as written to or read from OpenFGA
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ContextualTuple {
-    user: String,
-    relation: String,
-    object: String,
-    condition: Option<Vec<Condition>>,
+    pub user: String,
+    pub relation: String,
+    pub object: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+}
+
+impl ContextualTuple {
+    pub fn new(tuple: Tuple, condition: Option<Condition>) -> Self {
+        Self {
+            user: tuple.user,
+            relation: tuple.relation,
+            object: tuple.object,
+            condition,
+        }
+    }
+
+    pub fn tuple(&self) -> Tuple {
+        Tuple::new(
+            self.user.clone(),
+            self.relation.clone(),
+            self.object.clone(),
+        )
+    }
+}
+
+impl From<Tuple> for ContextualTuple {
+    fn from(tuple: Tuple) -> Self {
+        Self::new(tuple, None)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WriteTuples {
+    tuple_keys: Vec<ContextualTuple>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct DeleteTuples {
+    tuple_keys: Vec<Tuple>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WriteRequest {
+    authorization_model_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    writes: Option<WriteTuples>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deletes: Option<DeleteTuples>,
+}
+
+/// filter of `read`, `object` is either `type:id` or `type:` with `user` given
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TupleFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tuple_key: Option<TupleFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    continuation_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consistency: Option<Consistency>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoredTuple {
+    pub key: ContextualTuple,
+    /// RFC 3339 time the tuple was written
+    pub timestamp: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ReadResponseOk {
+    #[serde(default)]
+    tuples: Vec<StoredTuple>,
+    #[serde(default)]
+    continuation_token: String,
+}
+
+/// a page of `read`, `continuation_token` is `None` on the last page
+#[derive(Debug, Clone)]
+pub struct ReadResponse {
+    pub tuples: Vec<StoredTuple>,
+    pub continuation_token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TupleOperation {
+    #[serde(rename = "TUPLE_OPERATION_WRITE")]
+    Write,
+    #[serde(rename = "TUPLE_OPERATION_DELETE")]
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TupleChange {
+    pub tuple_key: ContextualTuple,
+    pub operation: TupleOperation,
+    /// RFC 3339 time of the change
+    pub timestamp: String,
+}
+
+/// a page of `read_changes`
+///
+/// `continuation_token` is returned even when there are no more changes, keep it to poll later changes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReadChangesResponse {
+    #[serde(default)]
+    pub changes: Vec<TupleChange>,
+    #[serde(default)]
+    pub continuation_token: String,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -60,6 +218,185 @@ pub struct CheckRequest {
     tuple_key: Tuple,
     contextual_tuples: Option<ContextualTuples>,
     context: Option<ConditionContext>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consistency: Option<Consistency>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BatchCheckItem {
+    tuple_key: Tuple,
+    contextual_tuples: Option<ContextualTuples>,
+    context: Option<ConditionContext>,
+    correlation_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BatchCheckRequest {
+    authorization_model_id: String,
+    checks: Vec<BatchCheckItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consistency: Option<Consistency>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct BatchCheckResponseOk {
+    result: HashMap<String, BatchCheckSingleResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct BatchCheckSingleResult {
+    #[serde(default)]
+    allowed: bool,
+    error: Option<BatchCheckError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct BatchCheckError {
+    input_error: Option<String>,
+    internal_error: Option<String>,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListObjectsRequest {
+    authorization_model_id: String,
+    #[serde(rename = "type")]
+    otype: String,
+    relation: String,
+    user: String,
+    contextual_tuples: Option<ContextualTuples>,
+    context: Option<ConditionContext>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consistency: Option<Consistency>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ListObjectsResponseOk {
+    objects: Vec<String>,
+}
+
+/// a line of `streamed_list_objects`, either a result or an error
+#[derive(Serialize, Deserialize, Debug)]
+struct StreamedListObjectsLine {
+    result: Option<StreamedListObjectsResult>,
+    error: Option<StreamedListObjectsError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct StreamedListObjectsResult {
+    object: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct StreamedListObjectsError {
+    #[serde(default)]
+    message: String,
+}
+
+/// `type:id` of an object
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FgaObject {
+    #[serde(rename = "type")]
+    pub otype: String,
+    pub id: String,
+}
+
+impl FgaObject {
+    pub fn new(otype: String, id: String) -> Self {
+        Self { otype, id }
+    }
+}
+
+impl std::fmt::Display for FgaObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.otype, self.id)
+    }
+}
+
+/// users of `list_users`, `user` or `group#member` with relation
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UserTypeFilter {
+    #[serde(rename = "type")]
+    pub otype: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+}
+
+impl UserTypeFilter {
+    pub fn new(otype: String, relation: Option<String>) -> Self {
+        Self { otype, relation }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListUsersRequest {
+    authorization_model_id: String,
+    object: FgaObject,
+    relation: String,
+    user_filters: Vec<UserTypeFilter>,
+    contextual_tuples: Option<Vec<ContextualTuple>>,
+    context: Option<ConditionContext>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consistency: Option<Consistency>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ListUsersResponseOk {
+    #[serde(default)]
+    users: Vec<ListUser>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UsersetUser {
+    #[serde(rename = "type")]
+    pub otype: String,
+    pub id: String,
+    pub relation: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TypedWildcard {
+    #[serde(rename = "type")]
+    pub otype: String,
+}
+
+/// a user of `list_users`, displayed in the notation of tuples
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ListUser {
+    /// `user:1`
+    Object(FgaObject),
+    /// `group:1#member`
+    Userset(UsersetUser),
+    /// `user:*`
+    Wildcard(TypedWildcard),
+}
+
+impl std::fmt::Display for ListUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListUser::Object(object) => write!(f, "{}", object),
+            ListUser::Userset(userset) => {
+                write!(f, "{}:{}#{}", userset.otype, userset.id, userset.relation)
+            }
+            ListUser::Wildcard(wildcard) => write!(f, "{}:*", wildcard.otype),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ExpandTupleKey {
+    relation: String,
+    object: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExpandRequest {
+    authorization_model_id: String,
+    tuple_key: ExpandTupleKey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consistency: Option<Consistency>,
 }
 
 #[derive(Debug)]
@@ -78,6 +415,48 @@ pub enum Error {
 
     #[error("OpenFGA::Check unreachable status code: {0}")]
     CheckUnexpectedStatusCode(StatusCode),
+
+    #[error("OpenFGA::ListObjects failed with status code {0}: {1}")]
+    ListObjectsFailed(StatusCode, String),
+
+    #[error("OpenFGA::BatchCheck failed with status code {0}: {1}")]
+    BatchCheckFailed(StatusCode, String),
+
+    #[error("OpenFGA::BatchCheck request aborted: {0}")]
+    BatchCheckAborted(String),
+
+    #[error("OpenFGA::Write failed with status code {0}, {1}: {2}")]
+    WriteFailed(StatusCode, String, String),
+
+    #[error("OpenFGA::Read failed with status code {0}, {1}: {2}")]
+    ReadFailed(StatusCode, String, String),
+
+    #[error("OpenFGA::ReadChanges failed with status code {0}, {1}: {2}")]
+    ReadChangesFailed(StatusCode, String, String),
+
+    #[error("OpenFGA::StreamedListObjects failed: {0}")]
+    StreamedListObjectsFailed(String),
+
+    #[error("OpenFGA::ListUsers failed with status code {0}, {1}: {2}")]
+    ListUsersFailed(StatusCode, String, String),
+
+    #[error("OpenFGA::Expand failed with status code {0}, {1}: {2}")]
+    ExpandFailed(StatusCode, String, String),
+
+    #[error("OpenFGA::{0} failed with status code {1}, {2}: {3}")]
+    ManagementFailed(&'static str, StatusCode, String, String),
+
+    #[error("OpenFGA::{0} returned no content")]
+    ManagementUnexpectedEmpty(&'static str),
+
+    #[error("OpenFGA invalid authorization model: {0}")]
+    InvalidAuthorizationModel(String),
+
+    #[error("OpenFGA in-memory engine failed: {0}")]
+    InMemoryFailed(String),
+
+    #[error("OpenFGA circuit is open, retry in {0:?}")]
+    CircuitOpen(std::time::Duration),
 }
 
 impl From<reqwest::Error> for Error {
@@ -93,9 +472,9 @@ struct CheckResponseOk {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct CheckResponseFail {
-    code: String,
-    message: String,
+pub(crate) struct CheckResponseFail {
+    pub(crate) code: String,
+    pub(crate) message: String,
 }
 
 #[derive(Clone, Debug)]
@@ -104,6 +483,16 @@ pub struct OpenFGA {
     store_id: String,
     authorization_model_id: String,
     client: Client,
+    /// checks sent in a single `BatchCheck` request, OpenFGA rejects more than 50 by default
+    max_checks_per_batch: usize,
+    /// `BatchCheck` requests in flight for a single `batch_check`
+    max_parallel_requests: usize,
+    consistency: Consistency,
+    cache: Option<DecisionCache>,
+    /// evaluated as if written to the store by every query, never stored
+    contextual_tuples: Vec<ContextualTuple>,
+    resilience: ResilienceConfig,
+    circuit: CircuitBreaker,
 }
 
 impl OpenFGA {
@@ -112,11 +501,91 @@ impl OpenFGA {
         store_id: String,
         authorization_model_id: String,
     ) -> Self {
+        let resilience = ResilienceConfig::default();
         Self {
             url: url.into(),
             store_id,
             authorization_model_id,
-            client: Client::new(),
+            client: resilient_client(&resilience),
+            max_checks_per_batch: 50,
+            max_parallel_requests: 10,
+            consistency: Consistency::Unspecified,
+            cache: None,
+            contextual_tuples: Vec::new(),
+            circuit: CircuitBreaker::new(&resilience),
+            resilience,
+        }
+    }
+    pub fn with_batch_check(
+        mut self,
+        max_checks_per_batch: usize,
+        max_parallel_requests: usize,
+    ) -> Self {
+        self.max_checks_per_batch = max_checks_per_batch.max(1);
+        self.max_parallel_requests = max_parallel_requests.max(1);
+        self
+    }
+    pub fn with_consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = consistency;
+        self
+    }
+    /// reuses `allowed` of `check` and `batch_check` until `ttl` or `DecisionCache::clear`
+    pub fn with_decision_cache(mut self, cache: DecisionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    pub fn decision_cache(&self) -> Option<&DecisionCache> {
+        self.cache.as_ref()
+    }
+    /// sends `contextual_tuples` with `check`, `batch_check`, `list_objects` and `list_users`
+    ///
+    /// cheap to call per request, e.g. with tuples derived from group claims of the authenticated token
+    pub fn with_contextual_tuples(mut self, contextual_tuples: Vec<ContextualTuple>) -> Self {
+        self.contextual_tuples = contextual_tuples;
+        self
+    }
+    fn contextual_tuples(&self) -> Option<ContextualTuples> {
+        if self.contextual_tuples.is_empty() {
+            return None;
+        }
+        Some(ContextualTuples {
+            tuple_keys: self.contextual_tuples.clone(),
+        })
+    }
+    fn consistency(&self) -> Option<Consistency> {
+        match self.consistency {
+            Consistency::Unspecified => None,
+            consistency => Some(consistency),
+        }
+    }
+    fn decision_key(
+        &self,
+        tuple_key: &Tuple,
+        context: Option<&ConditionContext>,
+    ) -> Option<DecisionKey> {
+        self.cache.as_ref().map(|_| {
+            DecisionKey::new(
+                &self.store_id,
+                &self.authorization_model_id,
+                tuple_key,
+                &self.contextual_tuples,
+                context,
+            )
+        })
+    }
+    fn cached(&self, key: Option<&DecisionKey>) -> Option<bool> {
+        match (&self.cache, key) {
+            (Some(cache), Some(key)) if self.consistency != Consistency::HigherConsistency => {
+                cache.get(key)
+            }
+            _ => None,
+        }
+    }
+    fn store(&self, generation: Option<u64>, key: Option<DecisionKey>, response: &CheckResponse) {
+        if let (Some(cache), Some(generation), Some(key), CheckResponse::Ok { allowed, .. }) =
+            (&self.cache, generation, key, response)
+        {
+            cache.insert_if_current(generation, key, *allowed);
         }
     }
     #[tracing::instrument(skip_all, fields(tuple.user = tuple_key.user, tuple.relation = tuple_key.relation, tuple.object = tuple_key.object))]
@@ -125,6 +594,15 @@ impl OpenFGA {
         tuple_key: Tuple,
         context: Option<ConditionContext>,
     ) -> Result<CheckResponse, Error> {
+        let key = self.decision_key(&tuple_key, context.as_ref());
+        if let Some(allowed) = self.cached(key.as_ref()) {
+            info!("OpenFGA::Check {:?} is cached {}", tuple_key, allowed);
+            return Ok(CheckResponse::Ok {
+                allowed,
+                resolution: String::new(),
+            });
+        }
+        let generation = self.cache.as_ref().map(DecisionCache::generation);
         let span = tracing::Span::current();
         let mut request = self
             .client
@@ -132,14 +610,15 @@ impl OpenFGA {
             .json(&CheckRequest {
                 authorization_model_id: self.authorization_model_id.clone(),
                 tuple_key: tuple_key.clone(),
-                contextual_tuples: None,
+                contextual_tuples: self.contextual_tuples(),
                 context,
+                consistency: self.consistency(),
             })
             .build()?;
         opentelemetry::global::get_text_map_propagator(|propagator| {
             propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
         });
-        let response = self.client.execute(request).await?;
+        let response = self.execute(request).await?;
         let result = match response.status() {
             StatusCode::OK => {
                 let body: CheckResponseOk = response.json().await?;
@@ -179,10 +658,533 @@ impl OpenFGA {
             _ => Err(Error::CheckUnexpectedStatusCode(response.status())),
         };
         info!("OpenFGA::Check {:?} is {:?}", tuple_key, result);
+        if let Ok(response) = &result {
+            self.store(generation, key, response);
+        }
+        result
+    }
+    /// checks every tuple, split into `BatchCheck` requests of at most `max_checks_per_batch`
+    ///
+    /// responses are in the order of `tuple_keys`, a check failed inside the batch is returned as its `CheckResponse`
+    #[tracing::instrument(skip_all, fields(checks = tuple_keys.len()))]
+    pub async fn batch_check(
+        &self,
+        tuple_keys: Vec<Tuple>,
+        context: Option<ConditionContext>,
+    ) -> Result<Vec<CheckResponse>, Error> {
+        let generation = self.cache.as_ref().map(DecisionCache::generation);
+        let keys = tuple_keys
+            .iter()
+            .map(|x| self.decision_key(x, context.as_ref()))
+            .collect::<Vec<_>>();
+        let mut responses = keys
+            .iter()
+            .map(|key| {
+                self.cached(key.as_ref()).map(|allowed| CheckResponse::Ok {
+                    allowed,
+                    resolution: String::new(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let pending = tuple_keys
+            .into_iter()
+            .zip(&responses)
+            .filter(|(_, response)| response.is_none())
+            .map(|(tuple_key, _)| tuple_key)
+            .collect::<Vec<_>>();
+        if !pending.is_empty() {
+            let mut checked = self.batch_check_chunks(pending, context).await?.into_iter();
+            for (key, response) in keys.into_iter().zip(responses.iter_mut()) {
+                if response.is_some() {
+                    continue;
+                }
+                if let Some(checked) = checked.next() {
+                    self.store(generation, key, &checked);
+                    response.replace(checked);
+                }
+            }
+        }
+        Ok(responses.into_iter().flatten().collect())
+    }
+    async fn batch_check_chunks(
+        &self,
+        tuple_keys: Vec<Tuple>,
+        context: Option<ConditionContext>,
+    ) -> Result<Vec<CheckResponse>, Error> {
+        let span = tracing::Span::current();
+        let mut requests = JoinSet::new();
+        let mut chunks = HashMap::new();
+        for (index, chunk) in tuple_keys.chunks(self.max_checks_per_batch).enumerate() {
+            if requests.len() >= self.max_parallel_requests {
+                if let Some(result) = requests.join_next().await {
+                    let (index, result) =
+                        result.map_err(|err| Error::BatchCheckAborted(err.to_string()))?;
+                    chunks.insert(index, result?);
+                }
+            }
+            let openfga = self.clone();
+            let chunk = chunk.to_vec();
+            let context = context.clone();
+            requests.spawn(
+                async move { (index, openfga.batch_check_chunk(chunk, context).await) }
+                    .instrument(span.clone()),
+            );
+        }
+        while let Some(result) = requests.join_next().await {
+            let (index, result) =
+                result.map_err(|err| Error::BatchCheckAborted(err.to_string()))?;
+            chunks.insert(index, result?);
+        }
+        Ok((0..chunks.len())
+            .flat_map(|index| chunks.remove(&index).unwrap_or_default())
+            .collect())
+    }
+    async fn batch_check_chunk(
+        &self,
+        tuple_keys: Vec<Tuple>,
+        context: Option<ConditionContext>,
+    ) -> Result<Vec<CheckResponse>, Error> {
+        let span = tracing::Span::current();
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/stores/{}/batch-check",
+                &self.url, &self.store_id
+            ))
+            .json(&BatchCheckRequest {
+                authorization_model_id: self.authorization_model_id.clone(),
+                checks: tuple_keys
+                    .iter()
+                    .enumerate()
+                    .map(|(index, tuple_key)| BatchCheckItem {
+                        tuple_key: tuple_key.clone(),
+                        contextual_tuples: self.contextual_tuples(),
+                        context: context.clone(),
+                        correlation_id: index.to_string(),
+                    })
+                    .collect(),
+                consistency: self.consistency(),
+            })
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute(request).await?;
+        let mut body: BatchCheckResponseOk = match response.status() {
+            StatusCode::OK => response.json().await?,
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                return Err(Error::BatchCheckFailed(status, body.message));
+            }
+        };
+        let result = tuple_keys
+            .iter()
+            .enumerate()
+            .map(|(index, tuple_key)| {
+                let result = match body.result.remove(&index.to_string()) {
+                    Some(BatchCheckSingleResult {
+                        error:
+                            Some(BatchCheckError {
+                                input_error: Some(code),
+                                message,
+                                ..
+                            }),
+                        ..
+                    }) => CheckResponse::InvalidInput { code, message },
+                    Some(BatchCheckSingleResult {
+                        error:
+                            Some(BatchCheckError {
+                                internal_error,
+                                message,
+                                ..
+                            }),
+                        ..
+                    }) => CheckResponse::InternalServerError {
+                        code: internal_error.unwrap_or_default(),
+                        message,
+                    },
+                    Some(BatchCheckSingleResult { allowed, .. }) => CheckResponse::Ok {
+                        allowed,
+                        resolution: String::new(),
+                    },
+                    None => CheckResponse::InternalServerError {
+                        code: "missing_result".to_string(),
+                        message: format!("no result for correlation id {}", index),
+                    },
+                };
+                info!("OpenFGA::BatchCheck {:?} is {:?}", tuple_key, result);
+                result
+            })
+            .collect();
+        Ok(result)
+    }
+    /// writes and deletes tuples in a single transaction, nothing is applied when any of them fails
+    ///
+    /// clears the decision cache, decisions of related objects may change
+    #[tracing::instrument(skip_all, fields(writes = writes.len(), deletes = deletes.len()))]
+    pub async fn write(
+        &self,
+        writes: Vec<ContextualTuple>,
+        deletes: Vec<Tuple>,
+    ) -> Result<(), Error> {
+        if writes.is_empty() && deletes.is_empty() {
+            return Ok(());
+        }
+        let span = tracing::Span::current();
+        let mut request = self
+            .client
+            .post(format!("{}/stores/{}/write", &self.url, &self.store_id))
+            .json(&WriteRequest {
+                authorization_model_id: self.authorization_model_id.clone(),
+                writes: (!writes.is_empty()).then(|| WriteTuples {
+                    tuple_keys: writes.clone(),
+                }),
+                deletes: (!deletes.is_empty()).then(|| DeleteTuples {
+                    tuple_keys: deletes.clone(),
+                }),
+            })
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute_once(request).await;
+        // a timed out write may still be applied
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+        let response = response?;
+        let result = match response.status() {
+            StatusCode::OK => Ok(()),
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                Err(Error::WriteFailed(status, body.code, body.message))
+            }
+        };
+        info!(
+            "OpenFGA::Write {:?} deletes {:?} is {:?}",
+            writes, deletes, result
+        );
+        result
+    }
+    /// a page of tuples matching `filter`, every tuple of the store without filter
+    #[tracing::instrument(skip_all, fields(filter = ?filter))]
+    pub async fn read(
+        &self,
+        filter: Option<TupleFilter>,
+        page_size: Option<u32>,
+        continuation_token: Option<String>,
+    ) -> Result<ReadResponse, Error> {
+        let span = tracing::Span::current();
+        let mut request = self
+            .client
+            .post(format!("{}/stores/{}/read", &self.url, &self.store_id))
+            .json(&ReadRequest {
+                tuple_key: filter,
+                page_size,
+                continuation_token,
+                consistency: self.consistency(),
+            })
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute(request).await?;
+        let result = match response.status() {
+            StatusCode::OK => {
+                let body: ReadResponseOk = response.json().await?;
+                Ok(ReadResponse {
+                    tuples: body.tuples,
+                    continuation_token: Some(body.continuation_token).filter(|x| !x.is_empty()),
+                })
+            }
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                Err(Error::ReadFailed(status, body.code, body.message))
+            }
+        };
+        info!(
+            "OpenFGA::Read is {:?}",
+            result.as_ref().map(|x| x.tuples.len())
+        );
+        result
+    }
+    /// every tuple matching `filter`, following continuation tokens of `read`
+    pub async fn read_all(&self, filter: Option<TupleFilter>) -> Result<Vec<StoredTuple>, Error> {
+        let mut tuples = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self.read(filter.clone(), None, continuation_token).await?;
+            tuples.extend(page.tuples);
+            match page.continuation_token {
+                Some(token) => continuation_token = Some(token),
+                None => return Ok(tuples),
+            }
+        }
+    }
+    /// a page of tuple changes of the store in the order they happened, only changes of `otype` if given
+    #[tracing::instrument(skip_all, fields(otype = otype))]
+    pub async fn read_changes(
+        &self,
+        otype: Option<&str>,
+        page_size: Option<u32>,
+        continuation_token: Option<String>,
+    ) -> Result<ReadChangesResponse, Error> {
+        let span = tracing::Span::current();
+        let mut query = Vec::new();
+        if let Some(otype) = otype {
+            query.push(("type", otype.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            query.push(("page_size", page_size.to_string()));
+        }
+        if let Some(continuation_token) = continuation_token {
+            query.push(("continuation_token", continuation_token));
+        }
+        let mut request = self
+            .client
+            .get(format!("{}/stores/{}/changes", &self.url, &self.store_id))
+            .query(&query)
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute(request).await?;
+        let result = match response.status() {
+            StatusCode::OK => Ok(response.json::<ReadChangesResponse>().await?),
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                Err(Error::ReadChangesFailed(status, body.code, body.message))
+            }
+        };
+        info!(
+            "OpenFGA::ReadChanges is {:?}",
+            result.as_ref().map(|x| x.changes.len())
+        );
+        result
+    }
+    /// objects of `otype` which `user` has `relation` with, returned as `otype:<id>`
+    #[tracing::instrument(skip_all, fields(otype = otype, relation = relation, user = user))]
+    pub async fn list_objects(
+        &self,
+        otype: &str,
+        relation: &str,
+        user: &str,
+        context: Option<ConditionContext>,
+    ) -> Result<Vec<String>, Error> {
+        let span = tracing::Span::current();
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/stores/{}/list-objects",
+                &self.url, &self.store_id
+            ))
+            .json(&ListObjectsRequest {
+                authorization_model_id: self.authorization_model_id.clone(),
+                otype: otype.to_string(),
+                relation: relation.to_string(),
+                user: user.to_string(),
+                contextual_tuples: self.contextual_tuples(),
+                context,
+                consistency: self.consistency(),
+            })
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute(request).await?;
+        let result = match response.status() {
+            StatusCode::OK => {
+                let body: ListObjectsResponseOk = response.json().await?;
+                Ok(body.objects)
+            }
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                Err(Error::ListObjectsFailed(status, body.message))
+            }
+        };
+        info!(
+            "OpenFGA::ListObjects {}#{}@{} is {:?}",
+            otype,
+            relation,
+            user,
+            result.as_ref().map(|x| x.len())
+        );
+        result
+    }
+    /// objects of `otype` which `user` has `relation` with, streamed as OpenFGA finds them
+    ///
+    /// unlike `list_objects`, the result is not truncated by the list objects limit of OpenFGA
+    #[tracing::instrument(skip_all, fields(otype = otype, relation = relation, user = user))]
+    pub async fn streamed_list_objects(
+        &self,
+        otype: &str,
+        relation: &str,
+        user: &str,
+        context: Option<ConditionContext>,
+    ) -> Result<BoxStream<'static, Result<String, Error>>, Error> {
+        let span = tracing::Span::current();
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/stores/{}/streamed-list-objects",
+                &self.url, &self.store_id
+            ))
+            .json(&ListObjectsRequest {
+                authorization_model_id: self.authorization_model_id.clone(),
+                otype: otype.to_string(),
+                relation: relation.to_string(),
+                user: user.to_string(),
+                contextual_tuples: self.contextual_tuples(),
+                context,
+                consistency: self.consistency(),
+            })
+            .timeout(self.resilience.stream_timeout)
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute(request).await?;
+        if response.status() != StatusCode::OK {
+            let status = response.status();
+            let body: CheckResponseFail = response.json().await?;
+            return Err(Error::ListObjectsFailed(status, body.message));
+        }
+        info!(
+            "OpenFGA::StreamedListObjects {}#{}@{} started",
+            otype, relation, user
+        );
+        // results are sent as newline delimited json
+        let state = (Some(response), Vec::new(), VecDeque::new());
+        let stream = futures::stream::unfold(
+            state,
+            |(mut response, mut buffer, mut objects): (Option<Response>, Vec<u8>, VecDeque<_>)| async move {
+                loop {
+                    if let Some(object) = objects.pop_front() {
+                        return Some((object, (response, buffer, objects)));
+                    }
+                    match response.as_mut()?.chunk().await {
+                        Ok(Some(chunk)) => {
+                            buffer.extend_from_slice(&chunk);
+                            while let Some(end) = buffer.iter().position(|x| *x == b'\n') {
+                                let line = buffer.drain(..=end).collect::<Vec<_>>();
+                                objects.extend(parse_streamed_line(&line));
+                            }
+                        }
+                        Ok(None) => {
+                            response = None;
+                            objects.extend(parse_streamed_line(&std::mem::take(&mut buffer)));
+                        }
+                        Err(err) => {
+                            response = None;
+                            objects.push_back(Err(err.into()));
+                        }
+                    }
+                }
+            },
+        );
+        Ok(stream.boxed())
+    }
+    /// users related to `object` as `relation`, limited to the types of `user_filters`
+    #[tracing::instrument(skip_all, fields(object = %object, relation = relation))]
+    pub async fn list_users(
+        &self,
+        object: FgaObject,
+        relation: &str,
+        user_filters: Vec<UserTypeFilter>,
+        context: Option<ConditionContext>,
+    ) -> Result<Vec<ListUser>, Error> {
+        let span = tracing::Span::current();
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/stores/{}/list-users",
+                &self.url, &self.store_id
+            ))
+            .json(&ListUsersRequest {
+                authorization_model_id: self.authorization_model_id.clone(),
+                object: object.clone(),
+                relation: relation.to_string(),
+                user_filters,
+                contextual_tuples: Some(self.contextual_tuples.clone()).filter(|x| !x.is_empty()),
+                context,
+                consistency: self.consistency(),
+            })
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute(request).await?;
+        let result = match response.status() {
+            StatusCode::OK => {
+                let body: ListUsersResponseOk = response.json().await?;
+                Ok(body.users)
+            }
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                Err(Error::ListUsersFailed(status, body.code, body.message))
+            }
+        };
+        info!(
+            "OpenFGA::ListUsers {}#{} is {:?}",
+            object,
+            relation,
+            result.as_ref().map(|x| x.len())
+        );
+        result
+    }
+    /// relation tree of `object#relation`, one level of usersets at a time
+    #[tracing::instrument(skip_all, fields(object = object, relation = relation))]
+    pub async fn expand(&self, object: &str, relation: &str) -> Result<UsersetTree, Error> {
+        let span = tracing::Span::current();
+        let mut request = self
+            .client
+            .post(format!("{}/stores/{}/expand", &self.url, &self.store_id))
+            .json(&ExpandRequest {
+                authorization_model_id: self.authorization_model_id.clone(),
+                tuple_key: ExpandTupleKey {
+                    relation: relation.to_string(),
+                    object: object.to_string(),
+                },
+                consistency: self.consistency(),
+            })
+            .build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute(request).await?;
+        let result = match response.status() {
+            StatusCode::OK => {
+                let body: ExpandResponseOk = response.json().await?;
+                Ok(UsersetTree::from(body))
+            }
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                Err(Error::ExpandFailed(status, body.code, body.message))
+            }
+        };
+        info!("OpenFGA::Expand {}#{} is {:?}", object, relation, result);
         result
     }
 }
 
+fn parse_streamed_line(line: &[u8]) -> Option<Result<String, Error>> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    match serde_json::from_slice::<StreamedListObjectsLine>(line) {
+        Ok(StreamedListObjectsLine {
+            result: Some(result),
+            ..
+        }) => Some(Ok(result.object)),
+        Ok(StreamedListObjectsLine {
+            error: Some(error), ..
+        }) => Some(Err(Error::StreamedListObjectsFailed(error.message))),
+        Ok(_) => None,
+        Err(err) => Some(Err(Error::StreamedListObjectsFailed(err.to_string()))),
+    }
+}
+
+/// client of the pinned store and model, see `reconcile` to resolve them by store name and model
 pub async fn init() -> OpenFGA {
     OpenFGA::new(
         "http://openfga.auth.svc:8080",
@@ -190,3 +1192,475 @@ pub async fn init() -> OpenFGA {
         "01HM13RRGHG855RS2QVGFECF6Y".to_string(),
     )
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::stub::Stub;
+
+    fn openfga(stub: &Stub) -> OpenFGA {
+        OpenFGA::new(stub.url.clone(), "store".to_string(), "model".to_string())
+    }
+
+    fn tuple(user: &str, relation: &str, object: &str) -> Tuple {
+        Tuple::new(user.to_string(), relation.to_string(), object.to_string())
+    }
+
+    #[tokio::test]
+    async fn write() {
+        let stub = Stub::serve(vec![(200, "{}")]).await;
+        let openfga = openfga(&stub);
+        let condition = Condition::new(
+            "in_hours".to_string(),
+            ConditionContext::from([("start".to_string(), json!(9))]),
+        );
+        openfga
+            .write(
+                vec![
+                    ContextualTuple::new(tuple("user:anne", "viewer", "doc:a"), Some(condition)),
+                    tuple("user:bob", "viewer", "doc:a").into(),
+                ],
+                vec![tuple("user:carl", "viewer", "doc:a")],
+            )
+            .await
+            .unwrap();
+        openfga.write(Vec::new(), Vec::new()).await.unwrap();
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/stores/store/write");
+        assert_eq!(
+            requests[0].body,
+            json!({
+                "authorization_model_id": "model",
+                "writes": {
+                    "tuple_keys": [
+                        {
+                            "user": "user:anne",
+                            "relation": "viewer",
+                            "object": "doc:a",
+                            "condition": { "name": "in_hours", "context": { "start": 9 } }
+                        },
+                        { "user": "user:bob", "relation": "viewer", "object": "doc:a" }
+                    ]
+                },
+                "deletes": {
+                    "tuple_keys": [{ "user": "user:carl", "relation": "viewer", "object": "doc:a" }]
+                }
+            })
+        );
+
+        // only deletes, `writes` is left out rather than sent empty
+        openfga
+            .write(Vec::new(), vec![tuple("user:carl", "viewer", "doc:a")])
+            .await
+            .unwrap();
+        assert_eq!(stub.requests()[1].body.get("writes"), None);
+    }
+
+    #[tokio::test]
+    async fn write_failed() {
+        let stub = Stub::serve(vec![(
+            400,
+            r#"{"code":"write_failed_due_to_invalid_input","message":"cannot write a tuple which already exists: user: 'user:anne', relation: 'viewer', object: 'doc:a': tuple to be written already existed or the tuple to be deleted did not exist"}"#,
+        )])
+        .await;
+        let result = openfga(&stub)
+            .write(
+                vec![tuple("user:anne", "viewer", "doc:a").into()],
+                Vec::new(),
+            )
+            .await;
+        match result {
+            Err(Error::WriteFailed(status, code, message)) => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(code, "write_failed_due_to_invalid_input");
+                assert!(message.starts_with("cannot write a tuple which already exists"));
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+        // writes are not retried
+        assert_eq!(stub.requests().len(), 1);
+    }
+
+    const PAGE: &str = r#"{
+        "tuples": [
+            {
+                "key": {
+                    "user": "user:anne",
+                    "relation": "viewer",
+                    "object": "doc:a",
+                    "condition": { "name": "in_hours", "context": { "start": 9 } }
+                },
+                "timestamp": "2024-01-15T09:30:00.123456Z"
+            }
+        ],
+        "continuation_token": "eyJwayI6IkxBVEVTVF9OU0NPTkZJR19hdXRoMHN0b3JlIiwic2siOiIxem1qbXF3MWZLZExTcUoyN01MdTdqTjh0cWgifQ=="
+    }"#;
+
+    const LAST_PAGE: &str = r#"{
+        "tuples": [
+            {
+                "key": { "user": "user:bob", "relation": "viewer", "object": "doc:a" },
+                "timestamp": "2024-01-15T09:31:00Z"
+            }
+        ],
+        "continuation_token": ""
+    }"#;
+
+    #[tokio::test]
+    async fn read() {
+        let stub = Stub::serve(vec![(200, PAGE), (200, LAST_PAGE)]).await;
+        let openfga = openfga(&stub);
+        let filter = TupleFilter {
+            object: Some("doc:a".to_string()),
+            ..Default::default()
+        };
+        let page = openfga
+            .read(Some(filter.clone()), Some(1), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            page.tuples
+                .iter()
+                .map(|x| (x.key.clone(), x.timestamp.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(
+                ContextualTuple::new(
+                    tuple("user:anne", "viewer", "doc:a"),
+                    Some(Condition::new(
+                        "in_hours".to_string(),
+                        ConditionContext::from([("start".to_string(), json!(9))]),
+                    )),
+                ),
+                "2024-01-15T09:30:00.123456Z"
+            )]
+        );
+        let token = page.continuation_token.unwrap();
+        let page = openfga
+            .read(Some(filter), Some(1), Some(token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(page.tuples.len(), 1);
+        // the last page has an empty token
+        assert_eq!(page.continuation_token, None);
+
+        let requests = stub.requests();
+        assert_eq!(requests[0].path, "/stores/store/read");
+        assert_eq!(
+            requests[0].body,
+            json!({ "tuple_key": { "object": "doc:a" }, "page_size": 1 })
+        );
+        assert_eq!(
+            requests[1].body,
+            json!({ "tuple_key": { "object": "doc:a" }, "page_size": 1, "continuation_token": token })
+        );
+    }
+
+    #[tokio::test]
+    async fn read_all() {
+        let stub = Stub::serve(vec![(200, PAGE), (200, LAST_PAGE)]).await;
+        let tuples = openfga(&stub)
+            .with_consistency(Consistency::HigherConsistency)
+            .read_all(None)
+            .await
+            .unwrap();
+        assert_eq!(
+            tuples.into_iter().map(|x| x.key.user).collect::<Vec<_>>(),
+            vec!["user:anne", "user:bob"]
+        );
+        let bodies = stub
+            .requests()
+            .into_iter()
+            .map(|x| x.body)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bodies,
+            vec![
+                json!({ "consistency": "HIGHER_CONSISTENCY" }),
+                json!({
+                    "continuation_token": "eyJwayI6IkxBVEVTVF9OU0NPTkZJR19hdXRoMHN0b3JlIiwic2siOiIxem1qbXF3MWZLZExTcUoyN01MdTdqTjh0cWgifQ==",
+                    "consistency": "HIGHER_CONSISTENCY"
+                }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_failed() {
+        let stub = Stub::serve(vec![(
+            400,
+            r#"{"code":"invalid_continuation_token","message":"Invalid continuation token"}"#,
+        )])
+        .await;
+        let result = openfga(&stub)
+            .read(None, None, Some("garbage".to_string()))
+            .await;
+        match result {
+            Err(Error::ReadFailed(status, code, message)) => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(code, "invalid_continuation_token");
+                assert_eq!(message, "Invalid continuation token");
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn read_changes() {
+        let stub = Stub::serve(vec![
+            (
+                200,
+                r#"{
+                    "changes": [
+                        {
+                            "tuple_key": { "user": "user:anne", "relation": "viewer", "object": "doc:a" },
+                            "operation": "TUPLE_OPERATION_WRITE",
+                            "timestamp": "2024-01-15T09:30:00.123456Z"
+                        },
+                        {
+                            "tuple_key": { "user": "user:anne", "relation": "viewer", "object": "doc:a" },
+                            "operation": "TUPLE_OPERATION_DELETE",
+                            "timestamp": "2024-01-15T09:31:00Z"
+                        }
+                    ],
+                    "continuation_token": "MDFIS0ZQQkI4UU0wV0E2MkVLRDAxRDJNUkE6ZG9j"
+                }"#,
+            ),
+            (
+                200,
+                r#"{"changes":[],"continuation_token":"MDFIS0ZQQkI4UU0wV0E2MkVLRDAxRDJNUkE6ZG9j"}"#,
+            ),
+        ])
+        .await;
+        let openfga = openfga(&stub);
+        let page = openfga
+            .read_changes(Some("doc"), Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            page.changes
+                .iter()
+                .map(|x| (x.tuple_key.tuple(), x.operation))
+                .collect::<Vec<_>>(),
+            vec![
+                (tuple("user:anne", "viewer", "doc:a"), TupleOperation::Write),
+                (
+                    tuple("user:anne", "viewer", "doc:a"),
+                    TupleOperation::Delete
+                ),
+            ]
+        );
+        // no more changes still returns the token to poll with
+        let page = openfga
+            .read_changes(Some("doc"), None, Some(page.continuation_token))
+            .await
+            .unwrap();
+        assert_eq!(page.changes.len(), 0);
+        assert_eq!(
+            page.continuation_token,
+            "MDFIS0ZQQkI4UU0wV0E2MkVLRDAxRDJNUkE6ZG9j"
+        );
+
+        let requests = stub.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(
+            requests[0].path,
+            "/stores/store/changes?type=doc&page_size=2"
+        );
+        assert_eq!(
+            requests[1].path,
+            "/stores/store/changes?type=doc&continuation_token=MDFIS0ZQQkI4UU0wV0E2MkVLRDAxRDJNUkE6ZG9j"
+        );
+    }
+
+    #[tokio::test]
+    async fn read_changes_failed() {
+        let stub = Stub::serve(vec![(
+            400,
+            r#"{"code":"invalid_continuation_token","message":"Invalid continuation token"}"#,
+        )])
+        .await;
+        let result = openfga(&stub).read_changes(None, None, None).await;
+        assert!(matches!(
+            result,
+            Err(Error::ReadChangesFailed(StatusCode::BAD_REQUEST, code, _)) if code == "invalid_continuation_token"
+        ));
+        assert_eq!(stub.requests()[0].path, "/stores/store/changes");
+    }
+
+    #[tokio::test]
+    async fn list_objects() {
+        let stub = Stub::serve(vec![
+            (200, r#"{"objects":["document:roadmap","document:budget"]}"#),
+            (
+                400,
+                r#"{"code":"validation_error","message":"invalid ListObjectsRequest.Type: value does not match regex pattern \"^[^:#@\\s]{1,254}$\""}"#,
+            ),
+        ])
+        .await;
+        let openfga = openfga(&stub).with_contextual_tuples(vec![tuple(
+            "user:anne",
+            "member",
+            "team:product",
+        )
+        .into()]);
+        let objects = openfga
+            .list_objects("document", "viewer", "user:anne", None)
+            .await
+            .unwrap();
+        assert_eq!(objects, vec!["document:roadmap", "document:budget"]);
+        assert_eq!(
+            stub.requests()[0].body,
+            json!({
+                "authorization_model_id": "model",
+                "type": "document",
+                "relation": "viewer",
+                "user": "user:anne",
+                "contextual_tuples": {
+                    "tuple_keys": [{ "user": "user:anne", "relation": "member", "object": "team:product" }]
+                },
+                "context": null
+            })
+        );
+        let result = openfga
+            .list_objects("document:", "viewer", "user:anne", None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::ListObjectsFailed(StatusCode::BAD_REQUEST, message)) if message.starts_with("invalid ListObjectsRequest.Type")
+        ));
+    }
+
+    #[tokio::test]
+    async fn streamed_list_objects() {
+        // newline delimited json, the last line without newline
+        let stub = Stub::serve(vec![(
+            200,
+            "{\"result\":{\"object\":\"document:roadmap\"}}\n\n{\"result\":{\"object\":\"document:budget\"}}\n{\"error\":{\"code\":4,\"message\":\"context deadline exceeded\"}}\n{\"result\":{\"object\":\"document:plan\"}}",
+        )])
+        .await;
+        let objects = openfga(&stub)
+            .streamed_list_objects("document", "viewer", "user:anne", None)
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        let objects = objects
+            .into_iter()
+            .map(|x| x.map_err(|err| err.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            objects,
+            vec![
+                Ok("document:roadmap".to_string()),
+                Ok("document:budget".to_string()),
+                Err(
+                    Error::StreamedListObjectsFailed("context deadline exceeded".to_string())
+                        .to_string()
+                ),
+                Ok("document:plan".to_string()),
+            ]
+        );
+        assert_eq!(
+            stub.requests()[0].path,
+            "/stores/store/streamed-list-objects"
+        );
+    }
+
+    #[test]
+    fn parse_streamed_lines() {
+        assert!(parse_streamed_line(b"  \r\n").is_none());
+        assert!(parse_streamed_line(b"{}\n").is_none());
+        assert!(matches!(
+            parse_streamed_line(b"{\"result\":{\"object\":\"document:roadmap\"}}\r\n"),
+            Some(Ok(object)) if object == "document:roadmap"
+        ));
+        assert!(matches!(
+            parse_streamed_line(b"{\"result\":"),
+            Some(Err(Error::StreamedListObjectsFailed(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_users() {
+        let stub = Stub::serve(vec![(
+            200,
+            r#"{"users":[
+                {"object":{"type":"user","id":"anne"}},
+                {"userset":{"type":"team","id":"product","relation":"member"}},
+                {"wildcard":{"type":"user"}}
+            ]}"#,
+        )])
+        .await;
+        let users = openfga(&stub)
+            .list_users(
+                FgaObject::new("document".to_string(), "roadmap".to_string()),
+                "viewer",
+                vec![
+                    UserTypeFilter::new("user".to_string(), None),
+                    UserTypeFilter::new("team".to_string(), Some("member".to_string())),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            users.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["user:anne", "team:product#member", "user:*"]
+        );
+        assert_eq!(
+            stub.requests()[0].body,
+            json!({
+                "authorization_model_id": "model",
+                "object": { "type": "document", "id": "roadmap" },
+                "relation": "viewer",
+                "user_filters": [{ "type": "user" }, { "type": "team", "relation": "member" }],
+                "contextual_tuples": null,
+                "context": null
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn expand() {
+        let stub = Stub::serve(vec![
+            (
+                200,
+                r#"{"tree":{"root":{"name":"document:roadmap#viewer","union":{"nodes":[
+                    {"name":"document:roadmap#viewer","leaf":{"users":{"users":["user:anne"]}}},
+                    {"name":"document:roadmap#viewer","leaf":{"computed":{"userset":"document:roadmap#editor"}}}
+                ]}}}}"#,
+            ),
+            (
+                400,
+                r#"{"code":"relation_not_found","message":"relation 'document#viewr' not found"}"#,
+            ),
+        ])
+        .await;
+        let openfga = openfga(&stub);
+        let tree = openfga.expand("document:roadmap", "viewer").await.unwrap();
+        assert_eq!(
+            tree.to_string(),
+            "document:roadmap#viewer (union)
+├── document:roadmap#viewer = user:anne
+└── document:roadmap#viewer = document:roadmap#editor
+"
+        );
+        assert_eq!(
+            stub.requests()[0].body,
+            json!({
+                "authorization_model_id": "model",
+                "tuple_key": { "relation": "viewer", "object": "document:roadmap" }
+            })
+        );
+        let result = openfga.expand("document:roadmap", "viewr").await;
+        assert!(matches!(
+            result,
+            Err(Error::ExpandFailed(StatusCode::BAD_REQUEST, code, _)) if code == "relation_not_found"
+        ));
+    }
+}
@@ -0,0 +1,650 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::info;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::propagation::HeaderInjector;
+use crate::{CheckResponseFail, Error, OpenFGA};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Store {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CreateStoreRequest {
+    name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ListStoresResponseOk {
+    #[serde(default)]
+    stores: Vec<Store>,
+    #[serde(default)]
+    continuation_token: String,
+}
+
+/// authorization model in the json format of the OpenFGA API, `fga model transform` converts the DSL into it
+///
+/// type definitions and conditions are kept as json, OpenFGA validates them on write
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuthorizationModel {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub schema_version: String,
+    pub type_definitions: Vec<Value>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub conditions: HashMap<String, Value>,
+}
+
+impl FromStr for AuthorizationModel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|err| Error::InvalidAuthorizationModel(err.to_string()))
+    }
+}
+
+impl AuthorizationModel {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        std::fs::read_to_string(path.as_ref())
+            .map_err(|err| {
+                Error::InvalidAuthorizationModel(format!("{}: {}", path.as_ref().display(), err))
+            })?
+            .parse()
+    }
+
+    /// same types, relations and conditions, ignoring the id and fields OpenFGA fills with defaults
+    pub fn is_equivalent(&self, other: &AuthorizationModel) -> bool {
+        let normalize = |model: &AuthorizationModel| {
+            normalized(serde_json::json!({
+                "schema_version": model.schema_version,
+                "type_definitions": model.type_definitions,
+                "conditions": model.conditions,
+            }))
+        };
+        normalize(self) == normalize(other)
+    }
+}
+
+/// drops null, empty strings, arrays and objects, which OpenFGA may add or omit on read
+fn normalized(value: Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::String(x) if x.is_empty() => None,
+        Value::Array(items) => {
+            let items = items.into_iter().filter_map(normalized).collect::<Vec<_>>();
+            (!items.is_empty()).then_some(Value::Array(items))
+        }
+        Value::Object(fields) => {
+            let fields = fields
+                .into_iter()
+                .filter_map(|(key, value)| normalized(value).map(|value| (key, value)))
+                .collect::<serde_json::Map<_, _>>();
+            (!fields.is_empty()).then_some(Value::Object(fields))
+        }
+        value => Some(value),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WriteAuthorizationModelResponseOk {
+    authorization_model_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ReadAuthorizationModelResponseOk {
+    authorization_model: AuthorizationModel,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ListAuthorizationModelsResponseOk {
+    #[serde(default)]
+    authorization_models: Vec<AuthorizationModel>,
+    #[serde(default)]
+    continuation_token: String,
+}
+
+/// a page of `list_stores` or `list_authorization_models`, `continuation_token` is `None` on the last page
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub continuation_token: Option<String>,
+}
+
+impl OpenFGA {
+    /// same server with another store, e.g. one returned by `create_store`
+    pub fn with_store(self, store_id: String) -> Self {
+        Self { store_id, ..self }
+    }
+    /// same store with another authorization model
+    pub fn with_authorization_model_id(self, authorization_model_id: String) -> Self {
+        Self {
+            authorization_model_id,
+            ..self
+        }
+    }
+    pub fn store_id(&self) -> &str {
+        &self.store_id
+    }
+    pub fn authorization_model_id(&self) -> &str {
+        &self.authorization_model_id
+    }
+    /// sends a management request, a status other than `200`, `201` or `204` is `Error::ManagementFailed`
+    async fn management<B: Serialize, R: DeserializeOwned>(
+        &self,
+        name: &'static str,
+        request: RequestBuilder,
+        body: Option<&B>,
+    ) -> Result<Option<R>, Error> {
+        let span = tracing::Span::current();
+        let request = match body {
+            Some(body) => request.json(body),
+            None => request,
+        };
+        let mut request = request.build()?;
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut HeaderInjector(request.headers_mut()));
+        });
+        let response = self.execute_method(request).await?;
+        match response.status() {
+            StatusCode::OK | StatusCode::CREATED => Ok(Some(response.json().await?)),
+            StatusCode::NO_CONTENT => Ok(None),
+            status => {
+                let body: CheckResponseFail = response.json().await?;
+                Err(Error::ManagementFailed(
+                    name,
+                    status,
+                    body.code,
+                    body.message,
+                ))
+            }
+        }
+    }
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", &self.url, path))
+    }
+    #[tracing::instrument(skip_all, fields(name = name))]
+    pub async fn create_store(&self, name: &str) -> Result<Store, Error> {
+        let store = self
+            .management::<_, Store>(
+                "CreateStore",
+                self.request(Method::POST, "/stores"),
+                Some(&CreateStoreRequest {
+                    name: name.to_string(),
+                }),
+            )
+            .await?
+            .ok_or_else(|| Error::ManagementUnexpectedEmpty("CreateStore"))?;
+        info!("OpenFGA::CreateStore {:?}", store);
+        Ok(store)
+    }
+    #[tracing::instrument(skip_all)]
+    pub async fn list_stores(
+        &self,
+        page_size: Option<u32>,
+        continuation_token: Option<String>,
+    ) -> Result<Page<Store>, Error> {
+        let mut query = Vec::new();
+        if let Some(page_size) = page_size {
+            query.push(("page_size", page_size.to_string()));
+        }
+        if let Some(continuation_token) = continuation_token {
+            query.push(("continuation_token", continuation_token));
+        }
+        let body = self
+            .management::<(), ListStoresResponseOk>(
+                "ListStores",
+                self.request(Method::GET, "/stores").query(&query),
+                None,
+            )
+            .await?
+            .ok_or_else(|| Error::ManagementUnexpectedEmpty("ListStores"))?;
+        Ok(Page {
+            items: body.stores,
+            continuation_token: Some(body.continuation_token).filter(|x| !x.is_empty()),
+        })
+    }
+    /// store of this client
+    #[tracing::instrument(skip_all, fields(store_id = self.store_id))]
+    pub async fn get_store(&self) -> Result<Store, Error> {
+        self.management::<(), Store>(
+            "GetStore",
+            self.request(Method::GET, &format!("/stores/{}", &self.store_id)),
+            None,
+        )
+        .await?
+        .ok_or_else(|| Error::ManagementUnexpectedEmpty("GetStore"))
+    }
+    /// deletes the store of this client with every tuple and model in it
+    #[tracing::instrument(skip_all, fields(store_id = self.store_id))]
+    pub async fn delete_store(&self) -> Result<(), Error> {
+        self.management::<(), Value>(
+            "DeleteStore",
+            self.request(Method::DELETE, &format!("/stores/{}", &self.store_id)),
+            None,
+        )
+        .await?;
+        info!("OpenFGA::DeleteStore {}", &self.store_id);
+        Ok(())
+    }
+    /// writes a new model into the store of this client and returns its id, models are immutable
+    #[tracing::instrument(skip_all, fields(store_id = self.store_id))]
+    pub async fn write_authorization_model(
+        &self,
+        model: &AuthorizationModel,
+    ) -> Result<String, Error> {
+        let model = AuthorizationModel {
+            id: None,
+            ..model.clone()
+        };
+        let body = self
+            .management::<_, WriteAuthorizationModelResponseOk>(
+                "WriteAuthorizationModel",
+                self.request(
+                    Method::POST,
+                    &format!("/stores/{}/authorization-models", &self.store_id),
+                ),
+                Some(&model),
+            )
+            .await?
+            .ok_or_else(|| Error::ManagementUnexpectedEmpty("WriteAuthorizationModel"))?;
+        info!(
+            "OpenFGA::WriteAuthorizationModel {}",
+            &body.authorization_model_id
+        );
+        Ok(body.authorization_model_id)
+    }
+    #[tracing::instrument(skip_all, fields(store_id = self.store_id, id = id))]
+    pub async fn read_authorization_model(&self, id: &str) -> Result<AuthorizationModel, Error> {
+        let body = self
+            .management::<(), ReadAuthorizationModelResponseOk>(
+                "ReadAuthorizationModel",
+                self.request(
+                    Method::GET,
+                    &format!("/stores/{}/authorization-models/{}", &self.store_id, id),
+                ),
+                None,
+            )
+            .await?
+            .ok_or_else(|| Error::ManagementUnexpectedEmpty("ReadAuthorizationModel"))?;
+        Ok(body.authorization_model)
+    }
+    /// models of the store of this client, newest first
+    #[tracing::instrument(skip_all, fields(store_id = self.store_id))]
+    pub async fn list_authorization_models(
+        &self,
+        page_size: Option<u32>,
+        continuation_token: Option<String>,
+    ) -> Result<Page<AuthorizationModel>, Error> {
+        let mut query = Vec::new();
+        if let Some(page_size) = page_size {
+            query.push(("page_size", page_size.to_string()));
+        }
+        if let Some(continuation_token) = continuation_token {
+            query.push(("continuation_token", continuation_token));
+        }
+        let body = self
+            .management::<(), ListAuthorizationModelsResponseOk>(
+                "ListAuthorizationModels",
+                self.request(
+                    Method::GET,
+                    &format!("/stores/{}/authorization-models", &self.store_id),
+                )
+                .query(&query),
+                None,
+            )
+            .await?
+            .ok_or_else(|| Error::ManagementUnexpectedEmpty("ListAuthorizationModels"))?;
+        Ok(Page {
+            items: body.authorization_models,
+            continuation_token: Some(body.continuation_token).filter(|x| !x.is_empty()),
+        })
+    }
+    /// newest model of the store of this client, `None` for a store without model
+    pub async fn latest_authorization_model(&self) -> Result<Option<AuthorizationModel>, Error> {
+        Ok(self
+            .list_authorization_models(Some(1), None)
+            .await?
+            .items
+            .into_iter()
+            .next())
+    }
+    /// first store named `name`, created when there is none
+    #[tracing::instrument(skip_all, fields(name = name))]
+    pub async fn reconcile_store(&self, name: &str) -> Result<Store, Error> {
+        let mut continuation_token = None;
+        loop {
+            let page = self.list_stores(None, continuation_token).await?;
+            if let Some(store) = page.items.into_iter().find(|x| x.name == name) {
+                return Ok(store);
+            }
+            match page.continuation_token {
+                Some(token) => continuation_token = Some(token),
+                None => return self.create_store(name).await,
+            }
+        }
+    }
+    /// id of the newest model when it is equivalent to `model`, otherwise `model` is written as the newest model
+    #[tracing::instrument(skip_all, fields(store_id = self.store_id))]
+    pub async fn reconcile_authorization_model(
+        &self,
+        model: &AuthorizationModel,
+    ) -> Result<String, Error> {
+        if let Some(AuthorizationModel { id: Some(id), .. }) = self
+            .latest_authorization_model()
+            .await?
+            .filter(|latest| latest.is_equivalent(model))
+        {
+            info!("OpenFGA::ReconcileAuthorizationModel {} is up to date", id);
+            return Ok(id);
+        }
+        self.write_authorization_model(model).await
+    }
+}
+
+/// client of the store named `store_name` with `model` as its newest model, both created when missing
+///
+/// ```ignore
+/// let model = include_str!("../openfga/model.json").parse()?;
+/// let openfga = openfga_client::reconcile("http://openfga.auth.svc:8080", "hmb", &model).await?;
+/// ```
+pub async fn reconcile<URL: Into<String>>(
+    url: URL,
+    store_name: &str,
+    model: &AuthorizationModel,
+) -> Result<OpenFGA, Error> {
+    let openfga = OpenFGA::new(url, String::new(), String::new());
+    let store = openfga.reconcile_store(store_name).await?;
+    let openfga = openfga.with_store(store.id);
+    let model_id = openfga.reconcile_authorization_model(model).await?;
+    Ok(openfga.with_authorization_model_id(model_id))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::stub::Stub;
+
+    /// model as written by `fga model transform`
+    const MODEL: &str = r#"{
+      "schema_version": "1.1",
+      "type_definitions": [
+        { "type": "user" },
+        {
+          "type": "document",
+          "relations": { "viewer": { "this": {} } },
+          "metadata": {
+            "relations": { "viewer": { "directly_related_user_types": [{ "type": "user" }] } }
+          }
+        }
+      ]
+    }"#;
+
+    /// `MODEL` as returned by OpenFGA, with its id and defaults filled in
+    const STORED: &str = r#"{
+      "authorization_models": [
+        {
+          "id": "01HM13RRGHG855RS2QVGFECF6Y",
+          "schema_version": "1.1",
+          "type_definitions": [
+            { "type": "user", "relations": {}, "metadata": null },
+            {
+              "type": "document",
+              "relations": { "viewer": { "this": {} } },
+              "metadata": {
+                "relations": {
+                  "viewer": {
+                    "directly_related_user_types": [{ "type": "user", "condition": "" }],
+                    "module": "",
+                    "source_info": null
+                  }
+                },
+                "module": "",
+                "source_info": null
+              }
+            }
+          ],
+          "conditions": {}
+        }
+      ],
+      "continuation_token": "eyJwayI6IkxBVEVTVF9OU0NPTkZJR19obWIifQ=="
+    }"#;
+
+    const STORES: &str = r#"{
+      "stores": [
+        {
+          "id": "01HKFPBB8QM0WA62EKD01D2MRA",
+          "name": "hmb",
+          "created_at": "2024-01-08T04:30:12.345Z",
+          "updated_at": "2024-01-08T04:30:12.345Z",
+          "deleted_at": null
+        }
+      ],
+      "continuation_token": ""
+    }"#;
+
+    fn model() -> AuthorizationModel {
+        MODEL.parse().unwrap()
+    }
+
+    fn changed() -> AuthorizationModel {
+        let mut model = model();
+        model.type_definitions[1]["relations"]["editor"] = json!({ "this": {} });
+        model.type_definitions[1]["relations"]["viewer"] =
+            json!({ "computedUserset": { "relation": "editor" } });
+        model
+    }
+
+    fn paths(stub: &Stub) -> Vec<String> {
+        stub.requests()
+            .into_iter()
+            .map(|x| format!("{} {}", x.method, x.path))
+            .collect()
+    }
+
+    #[test]
+    fn equivalent() {
+        let stored = serde_json::from_str::<ListAuthorizationModelsResponseOk>(STORED)
+            .unwrap()
+            .authorization_models
+            .remove(0);
+        assert!(model().is_equivalent(&stored));
+        assert!(!changed().is_equivalent(&stored));
+        let conditioned = AuthorizationModel {
+            conditions: HashMap::from([(
+                "in_hours".to_string(),
+                json!({ "name": "in_hours", "expression": "hour < 18" }),
+            )]),
+            ..model()
+        };
+        assert!(!conditioned.is_equivalent(&stored));
+    }
+
+    #[tokio::test]
+    async fn reconcile_unchanged_model() {
+        let stub = Stub::serve(vec![
+            (
+                200,
+                r#"{"stores":[{"id":"01HKFPBB8QM0WA62EKD01D2MRB","name":"other","created_at":"","updated_at":""}],"continuation_token":"c3RvcmVz"}"#,
+            ),
+            (200, STORES),
+            (200, STORED),
+        ])
+        .await;
+        let openfga = reconcile(stub.url.clone(), "hmb", &model()).await.unwrap();
+        assert_eq!(openfga.store_id(), "01HKFPBB8QM0WA62EKD01D2MRA");
+        assert_eq!(
+            openfga.authorization_model_id(),
+            "01HM13RRGHG855RS2QVGFECF6Y"
+        );
+        // the latest model is reused, nothing is written
+        assert_eq!(
+            paths(&stub),
+            vec![
+                "GET /stores",
+                "GET /stores?continuation_token=c3RvcmVz",
+                "GET /stores/01HKFPBB8QM0WA62EKD01D2MRA/authorization-models?page_size=1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reconcile_changed_model() {
+        let stub = Stub::serve(vec![
+            (200, STORES),
+            (200, STORED),
+            (
+                201,
+                r#"{"authorization_model_id":"01HVMMBCMGZNT3SED4Z17ECXCA"}"#,
+            ),
+        ])
+        .await;
+        let openfga = reconcile(stub.url.clone(), "hmb", &changed())
+            .await
+            .unwrap();
+        assert_eq!(
+            openfga.authorization_model_id(),
+            "01HVMMBCMGZNT3SED4Z17ECXCA"
+        );
+        let requests = stub.requests();
+        assert_eq!(
+            paths(&stub)[2],
+            "POST /stores/01HKFPBB8QM0WA62EKD01D2MRA/authorization-models"
+        );
+        assert_eq!(requests[2].body, serde_json::to_value(changed()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn reconcile_missing_store() {
+        let stub = Stub::serve(vec![
+            (200, r#"{"stores":[],"continuation_token":""}"#),
+            (
+                201,
+                r#"{"id":"01HKFPBB8QM0WA62EKD01D2MRA","name":"hmb","created_at":"2024-01-08T04:30:12.345Z","updated_at":"2024-01-08T04:30:12.345Z"}"#,
+            ),
+            (200, r#"{"authorization_models":[],"continuation_token":""}"#),
+            (
+                201,
+                r#"{"authorization_model_id":"01HM13RRGHG855RS2QVGFECF6Y"}"#,
+            ),
+        ])
+        .await;
+        let openfga = reconcile(stub.url.clone(), "hmb", &model()).await.unwrap();
+        assert_eq!(openfga.store_id(), "01HKFPBB8QM0WA62EKD01D2MRA");
+        assert_eq!(
+            openfga.authorization_model_id(),
+            "01HM13RRGHG855RS2QVGFECF6Y"
+        );
+        let requests = stub.requests();
+        assert_eq!(
+            paths(&stub),
+            vec![
+                "GET /stores",
+                "POST /stores",
+                "GET /stores/01HKFPBB8QM0WA62EKD01D2MRA/authorization-models?page_size=1",
+                "POST /stores/01HKFPBB8QM0WA62EKD01D2MRA/authorization-models",
+            ]
+        );
+        assert_eq!(requests[1].body, json!({ "name": "hmb" }));
+        // the id of a model read back is never written
+        let written = AuthorizationModel {
+            id: Some("01HM13RRGHG855RS2QVGFECF6Y".to_string()),
+            ..model()
+        };
+        let stub = Stub::serve(vec![(
+            201,
+            r#"{"authorization_model_id":"01HVMMBCMGZNT3SED4Z17ECXCA"}"#,
+        )])
+        .await;
+        OpenFGA::new(stub.url.clone(), "store".to_string(), String::new())
+            .write_authorization_model(&written)
+            .await
+            .unwrap();
+        assert_eq!(stub.requests()[0].body.get("id"), None);
+    }
+
+    #[tokio::test]
+    async fn store_crud() {
+        let stub = Stub::serve(vec![
+            (
+                200,
+                r#"{"id":"01HKFPBB8QM0WA62EKD01D2MRA","name":"hmb","created_at":"2024-01-08T04:30:12.345Z","updated_at":"2024-01-08T04:30:12.345Z"}"#,
+            ),
+            (204, ""),
+            (
+                404,
+                r#"{"code":"store_id_not_found","message":"store not found"}"#,
+            ),
+        ])
+        .await;
+        let openfga = OpenFGA::new(
+            stub.url.clone(),
+            "01HKFPBB8QM0WA62EKD01D2MRA".to_string(),
+            String::new(),
+        );
+        assert_eq!(openfga.get_store().await.unwrap().name, "hmb");
+        openfga.delete_store().await.unwrap();
+        let result = openfga.get_store().await;
+        assert!(matches!(
+            result,
+            Err(Error::ManagementFailed("GetStore", StatusCode::NOT_FOUND, code, _)) if code == "store_id_not_found"
+        ));
+        assert_eq!(
+            paths(&stub),
+            vec![
+                "GET /stores/01HKFPBB8QM0WA62EKD01D2MRA",
+                "DELETE /stores/01HKFPBB8QM0WA62EKD01D2MRA",
+                "GET /stores/01HKFPBB8QM0WA62EKD01D2MRA",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn model_crud() {
+        let stub = Stub::serve(vec![
+            (
+                200,
+                r#"{"authorization_model":{"id":"01HM13RRGHG855RS2QVGFECF6Y","schema_version":"1.1","type_definitions":[{"type":"user"}],"conditions":{}}}"#,
+            ),
+            (200, STORED),
+        ])
+        .await;
+        let openfga = OpenFGA::new(stub.url.clone(), "store".to_string(), String::new());
+        let model = openfga
+            .read_authorization_model("01HM13RRGHG855RS2QVGFECF6Y")
+            .await
+            .unwrap();
+        assert_eq!(model.id.as_deref(), Some("01HM13RRGHG855RS2QVGFECF6Y"));
+        assert_eq!(model.type_definitions, vec![json!({ "type": "user" })]);
+        let page = openfga
+            .list_authorization_models(Some(1), None)
+            .await
+            .unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(
+            page.continuation_token.as_deref(),
+            Some("eyJwayI6IkxBVEVTVF9OU0NPTkZJR19obWIifQ==")
+        );
+        assert_eq!(
+            paths(&stub),
+            vec![
+                "GET /stores/store/authorization-models/01HM13RRGHG855RS2QVGFECF6Y",
+                "GET /stores/store/authorization-models?page_size=1",
+            ]
+        );
+    }
+}
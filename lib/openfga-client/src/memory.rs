@@ -0,0 +1,599 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use openfga_dsl::{Model, RestrictionKind, Rewrite, TypeRestriction};
+use reqwest::StatusCode;
+
+use crate::{AuthorizationEngine, CheckResponse, ConditionContext, ContextualTuple, Error, Tuple};
+
+/// same limit as the default `resolve node limit` of OpenFGA
+const MAX_RESOLUTION_DEPTH: usize = 25;
+
+type ConditionFn = Arc<dyn Fn(&ConditionContext) -> bool + Send + Sync>;
+
+/// in-process `AuthorizationEngine` evaluating an authorization model over tuples kept in memory
+///
+/// for tests and local development without OpenFGA, clones share the written tuples
+///
+/// ```ignore
+/// let engine = InMemoryEngine::from_dsl(MODEL)?;
+/// engine.write(vec![Tuple::new("user:1".into(), "viewer".into(), "doc:1".into()).into()])?;
+/// let schema = Schema::build(..).extension(GraphGuard::from_engine(engine)).finish();
+/// ```
+///
+/// CEL expressions of conditions are not evaluated, a conditioned tuple holds when the function registered
+/// by `with_condition` returns true for the tuple context merged over the request context
+#[derive(Clone)]
+pub struct InMemoryEngine {
+    model: Arc<Model>,
+    tuples: Arc<RwLock<Vec<ContextualTuple>>>,
+    conditions: HashMap<String, ConditionFn>,
+}
+
+impl std::fmt::Debug for InMemoryEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryEngine")
+            .field("types", &self.model.types.len())
+            .field("tuples", &self.tuples.read().unwrap().len())
+            .field("conditions", &self.conditions.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl InMemoryEngine {
+    pub fn new(model: Model) -> Result<Self, Vec<openfga_dsl::Error>> {
+        model.validate()?;
+        Ok(Self {
+            model: Arc::new(model),
+            tuples: Arc::new(RwLock::new(Vec::new())),
+            conditions: HashMap::new(),
+        })
+    }
+
+    pub fn from_dsl(source: &str) -> Result<Self, Vec<openfga_dsl::Error>> {
+        Self::new(openfga_dsl::parse(source).map_err(|e| vec![e])?)
+    }
+
+    /// evaluates condition `name` of conditioned tuples
+    pub fn with_condition<F>(mut self, name: &str, condition: F) -> Self
+    where
+        F: Fn(&ConditionContext) -> bool + Send + Sync + 'static,
+    {
+        self.conditions
+            .insert(name.to_string(), Arc::new(condition));
+        self
+    }
+
+    /// writes tuples allowed by the model like `OpenFGA::write`, a tuple written again replaces its condition
+    pub fn write(&self, writes: Vec<ContextualTuple>) -> Result<(), Error> {
+        for tuple in &writes {
+            self.validate_tuple(tuple).map_err(|message| {
+                Error::WriteFailed(
+                    StatusCode::BAD_REQUEST,
+                    "validation_error".to_string(),
+                    message,
+                )
+            })?;
+        }
+        let mut tuples = self.tuples.write().unwrap();
+        for tuple in writes {
+            tuples.retain(|x| x.tuple() != tuple.tuple());
+            tuples.push(tuple);
+        }
+        Ok(())
+    }
+
+    /// deletes tuples, missing tuples are ignored
+    pub fn delete(&self, deletes: Vec<Tuple>) {
+        self.tuples
+            .write()
+            .unwrap()
+            .retain(|x| !deletes.contains(&x.tuple()));
+    }
+
+    /// every written tuple in the order written
+    pub fn tuples(&self) -> Vec<ContextualTuple> {
+        self.tuples.read().unwrap().clone()
+    }
+
+    /// `allowed` of `tuple`, or the reason it can not be evaluated
+    pub fn check(
+        &self,
+        tuple: &Tuple,
+        context: Option<&ConditionContext>,
+        contextual_tuples: &[ContextualTuple],
+    ) -> Result<bool, String> {
+        self.evaluation(context, contextual_tuples)?.check(
+            &tuple.object,
+            &tuple.relation,
+            &tuple.user,
+        )
+    }
+
+    fn evaluation<'a>(
+        &'a self,
+        context: Option<&'a ConditionContext>,
+        contextual_tuples: &[ContextualTuple],
+    ) -> Result<Evaluation<'a>, String> {
+        for tuple in contextual_tuples {
+            self.validate_tuple(tuple)?;
+        }
+        let mut tuples = self.tuples();
+        tuples.extend_from_slice(contextual_tuples);
+        Ok(Evaluation {
+            engine: self,
+            tuples,
+            context,
+            path: Vec::new(),
+        })
+    }
+
+    /// object type and relation must be defined, and user must be one of its directly related types
+    fn validate_tuple(&self, tuple: &ContextualTuple) -> Result<(), String> {
+        let (otype, _) = split_object(&tuple.object)?;
+        let relation = self
+            .model
+            .type_definition(otype)
+            .ok_or_else(|| format!("type {} is not defined", otype))?
+            .relation(&tuple.relation)
+            .ok_or_else(|| format!("relation {}#{} is not defined", otype, tuple.relation))?;
+        if !is_assignable(&relation.rewrite.direct(), tuple) {
+            return Err(format!(
+                "{} can't be assigned to {}#{}",
+                tuple.user, otype, tuple.relation
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `type` and `id` of `type:id`
+fn split_object(object: &str) -> Result<(&str, &str), String> {
+    object
+        .split_once(':')
+        .filter(|(otype, id)| !otype.is_empty() && !id.is_empty())
+        .ok_or_else(|| format!("{} is not an object of type:id", object))
+}
+
+/// whether the user of `tuple` and its condition match one of `restrictions`
+fn is_assignable(restrictions: &[&TypeRestriction], tuple: &ContextualTuple) -> bool {
+    let (user, userset) = match tuple.user.split_once('#') {
+        Some((user, relation)) => (user, Some(relation)),
+        None => (tuple.user.as_str(), None),
+    };
+    let Some((utype, id)) = user.split_once(':') else {
+        return false;
+    };
+    let condition = tuple.condition.as_ref().map(|x| x.name.as_str());
+    restrictions.iter().any(|x| {
+        x.otype == utype
+            && x.condition.as_deref() == condition
+            && match (&x.kind, userset) {
+                (RestrictionKind::Type, None) => id != "*",
+                (RestrictionKind::Wildcard, None) => id == "*",
+                (RestrictionKind::Userset(relation), Some(userset)) => relation == userset,
+                _ => false,
+            }
+    })
+}
+
+/// a single check or list objects over a snapshot of the tuples
+struct Evaluation<'a> {
+    engine: &'a InMemoryEngine,
+    tuples: Vec<ContextualTuple>,
+    context: Option<&'a ConditionContext>,
+    /// `(object, relation)` being resolved, a relation reached again through itself is not allowed by that path
+    path: Vec<(String, String)>,
+}
+
+impl Evaluation<'_> {
+    fn check(&mut self, object: &str, relation: &str, user: &str) -> Result<bool, String> {
+        if self.path.len() >= MAX_RESOLUTION_DEPTH {
+            return Err(format!(
+                "resolution of {}#{} exceeded depth {}",
+                object, relation, MAX_RESOLUTION_DEPTH
+            ));
+        }
+        if self.path.iter().any(|(o, r)| o == object && r == relation) {
+            return Ok(false);
+        }
+        let (otype, _) = split_object(object)?;
+        let rewrite = &self
+            .engine
+            .model
+            .type_definition(otype)
+            .ok_or_else(|| format!("type {} is not defined", otype))?
+            .relation(relation)
+            .ok_or_else(|| format!("relation {}#{} is not defined", otype, relation))?
+            .rewrite;
+        self.path.push((object.to_string(), relation.to_string()));
+        let result = self.rewrite(object, relation, rewrite, user);
+        self.path.pop();
+        result
+    }
+
+    fn rewrite(
+        &mut self,
+        object: &str,
+        relation: &str,
+        rewrite: &Rewrite,
+        user: &str,
+    ) -> Result<bool, String> {
+        match rewrite {
+            Rewrite::Direct(restrictions) => {
+                let restrictions = restrictions.iter().collect::<Vec<_>>();
+                for tuple in self.related(object, relation)? {
+                    if !is_assignable(&restrictions, &tuple) {
+                        continue;
+                    }
+                    if tuple.user == user {
+                        return Ok(true);
+                    }
+                    if let Some(wildcard) = tuple.user.strip_suffix(":*") {
+                        if !user.contains('#')
+                            && user.split_once(':').map(|x| x.0) == Some(wildcard)
+                        {
+                            return Ok(true);
+                        }
+                    }
+                    if let Some((userset, userset_relation)) = tuple.user.split_once('#') {
+                        if self.check(userset, userset_relation, user)? {
+                            return Ok(true);
+                        }
+                    }
+                }
+                Ok(false)
+            }
+            Rewrite::Computed { relation, .. } => self.check(object, relation, user),
+            Rewrite::TupleToUserset {
+                computed, tupleset, ..
+            } => {
+                for tuple in self.related(object, tupleset)? {
+                    let (otype, _) = split_object(&tuple.user)?;
+                    let defined = self
+                        .engine
+                        .model
+                        .type_definition(otype)
+                        .is_some_and(|x| x.relation(computed).is_some());
+                    if defined && self.check(&tuple.user, computed, user)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Rewrite::Union(children) => {
+                for child in children {
+                    if self.rewrite(object, relation, child, user)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Rewrite::Intersection(children) => {
+                for child in children {
+                    if !self.rewrite(object, relation, child, user)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Rewrite::Difference { base, subtract } => Ok(self
+                .rewrite(object, relation, base, user)?
+                && !self.rewrite(object, relation, subtract, user)?),
+        }
+    }
+
+    /// tuples of `object` and `relation` whose condition holds
+    fn related(&self, object: &str, relation: &str) -> Result<Vec<ContextualTuple>, String> {
+        let mut related = Vec::new();
+        for tuple in &self.tuples {
+            if tuple.object == object && tuple.relation == relation && self.holds(tuple)? {
+                related.push(tuple.clone());
+            }
+        }
+        Ok(related)
+    }
+
+    fn holds(&self, tuple: &ContextualTuple) -> Result<bool, String> {
+        let Some(condition) = &tuple.condition else {
+            return Ok(true);
+        };
+        let evaluate = self.engine.conditions.get(&condition.name).ok_or_else(|| {
+            format!(
+                "condition {} is not registered by InMemoryEngine::with_condition",
+                condition.name
+            )
+        })?;
+        let mut context = self.context.cloned().unwrap_or_default();
+        context.extend(condition.context.clone());
+        Ok(evaluate(&context))
+    }
+
+    /// objects of `otype` referred by any tuple
+    fn objects(&self, otype: &str) -> BTreeSet<String> {
+        let prefix = format!("{}:", otype);
+        self.tuples
+            .iter()
+            .flat_map(|x| {
+                [
+                    x.object.as_str(),
+                    x.user.split('#').next().unwrap_or_default(),
+                ]
+            })
+            .filter(|x| x.starts_with(&prefix) && !x.ends_with(":*"))
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AuthorizationEngine for InMemoryEngine {
+    async fn batch_check(
+        &self,
+        tuple_keys: Vec<Tuple>,
+        context: Option<ConditionContext>,
+        contextual_tuples: Vec<ContextualTuple>,
+    ) -> Result<Vec<CheckResponse>, Error> {
+        let mut evaluation = self.evaluation(context.as_ref(), &contextual_tuples);
+        Ok(tuple_keys
+            .iter()
+            .map(|tuple| {
+                let result = match &mut evaluation {
+                    Ok(evaluation) => evaluation.check(&tuple.object, &tuple.relation, &tuple.user),
+                    Err(message) => Err(message.clone()),
+                };
+                match result {
+                    Ok(allowed) => CheckResponse::Ok {
+                        allowed,
+                        resolution: String::new(),
+                    },
+                    Err(message) => CheckResponse::InvalidInput {
+                        code: "validation_error".to_string(),
+                        message,
+                    },
+                }
+            })
+            .collect())
+    }
+
+    async fn list_objects(
+        &self,
+        otype: &str,
+        relation: &str,
+        user: &str,
+        context: Option<ConditionContext>,
+        contextual_tuples: Vec<ContextualTuple>,
+    ) -> Result<Vec<String>, Error> {
+        let mut evaluation = self
+            .evaluation(context.as_ref(), &contextual_tuples)
+            .map_err(Error::InMemoryFailed)?;
+        let mut objects = Vec::new();
+        for object in evaluation.objects(otype) {
+            if evaluation
+                .check(&object, relation, user)
+                .map_err(Error::InMemoryFailed)?
+            {
+                objects.push(object);
+            }
+        }
+        Ok(objects)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::Condition;
+
+    const MODEL: &str = r#"model
+  schema 1.1
+
+type user
+
+type group
+  relations
+    define member: [user, user:*, group#member]
+
+type folder
+  relations
+    define owner: [user]
+    define viewer: [user, group#member] or owner
+
+type document
+  relations
+    define parent: [folder]
+    define owner: [user]
+    define blocked: [user]
+    define editor: [user] or owner
+    define viewer: [user, user with in_office] or editor or viewer from parent
+    define approver: [user] and editor
+    define reader: viewer but not blocked
+
+condition in_office(ip: string) {
+  ip == "10.0.0.1"
+}
+"#;
+
+    fn tuple(user: &str, relation: &str, object: &str) -> Tuple {
+        Tuple::new(user.to_string(), relation.to_string(), object.to_string())
+    }
+
+    fn engine(tuples: &[(&str, &str, &str)]) -> InMemoryEngine {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        engine
+            .write(
+                tuples
+                    .iter()
+                    .map(|(user, relation, object)| tuple(user, relation, object).into())
+                    .collect(),
+            )
+            .unwrap();
+        engine
+    }
+
+    fn check(engine: &InMemoryEngine, user: &str, relation: &str, object: &str) -> bool {
+        engine
+            .check(&tuple(user, relation, object), None, &[])
+            .unwrap()
+    }
+
+    #[test]
+    fn direct() {
+        let engine = engine(&[("user:1", "owner", "document:1")]);
+        assert!(check(&engine, "user:1", "owner", "document:1"));
+        assert!(!check(&engine, "user:2", "owner", "document:1"));
+        assert!(!check(&engine, "user:1", "owner", "document:2"));
+    }
+
+    #[test]
+    fn computed_userset() {
+        let engine = engine(&[("user:1", "owner", "document:1")]);
+        assert!(check(&engine, "user:1", "editor", "document:1"));
+        assert!(check(&engine, "user:1", "viewer", "document:1"));
+        assert!(!check(&engine, "user:1", "blocked", "document:1"));
+    }
+
+    #[test]
+    fn tuple_to_userset() {
+        let engine = engine(&[
+            ("folder:1", "parent", "document:1"),
+            ("user:1", "owner", "folder:1"),
+            ("group:1#member", "viewer", "folder:1"),
+            ("user:2", "member", "group:1"),
+        ]);
+        assert!(check(&engine, "user:1", "viewer", "document:1"));
+        assert!(check(&engine, "user:2", "viewer", "document:1"));
+        assert!(!check(&engine, "user:2", "editor", "document:1"));
+        assert!(!check(&engine, "user:3", "viewer", "document:1"));
+    }
+
+    #[test]
+    fn intersection() {
+        let engine = engine(&[
+            ("user:1", "approver", "document:1"),
+            ("user:1", "editor", "document:1"),
+            ("user:2", "approver", "document:1"),
+        ]);
+        assert!(check(&engine, "user:1", "approver", "document:1"));
+        assert!(!check(&engine, "user:2", "approver", "document:1"));
+    }
+
+    #[test]
+    fn exclusion() {
+        let engine = engine(&[
+            ("user:1", "viewer", "document:1"),
+            ("user:2", "viewer", "document:1"),
+            ("user:2", "blocked", "document:1"),
+        ]);
+        assert!(check(&engine, "user:1", "reader", "document:1"));
+        assert!(!check(&engine, "user:2", "reader", "document:1"));
+        assert!(check(&engine, "user:2", "viewer", "document:1"));
+    }
+
+    #[test]
+    fn wildcard() {
+        let engine = engine(&[("user:*", "member", "group:all")]);
+        assert!(check(&engine, "user:1", "member", "group:all"));
+        assert!(check(&engine, "user:*", "member", "group:all"));
+        assert!(!check(&engine, "group:1#member", "member", "group:all"));
+        // `user:*` is only assignable where the model allows it
+        assert!(engine
+            .write(vec![tuple("user:*", "owner", "document:1").into()])
+            .is_err());
+    }
+
+    #[test]
+    fn cycle() {
+        let engine = engine(&[
+            ("group:b#member", "member", "group:a"),
+            ("group:a#member", "member", "group:b"),
+            ("user:1", "member", "group:b"),
+        ]);
+        assert!(check(&engine, "user:1", "member", "group:a"));
+        assert!(!check(&engine, "user:2", "member", "group:a"));
+    }
+
+    #[test]
+    fn invalid_tuple() {
+        let engine = engine(&[]);
+        assert!(engine
+            .write(vec![tuple("folder:1", "owner", "document:1").into()])
+            .is_err());
+        assert!(engine
+            .write(vec![tuple("user:1", "unknown", "document:1").into()])
+            .is_err());
+        assert!(engine
+            .check(&tuple("user:1", "viewer", "unknown:1"), None, &[])
+            .is_err());
+    }
+
+    #[test]
+    fn condition() {
+        let engine = InMemoryEngine::from_dsl(MODEL)
+            .unwrap()
+            .with_condition("in_office", |context| {
+                context.get("ip") == Some(&json!("10.0.0.1"))
+            });
+        engine
+            .write(vec![ContextualTuple::new(
+                tuple("user:1", "viewer", "document:1"),
+                Some(Condition::new("in_office".to_string(), Default::default())),
+            )])
+            .unwrap();
+        let context = |ip: &str| ConditionContext::from([("ip".to_string(), json!(ip))]);
+        let viewer = tuple("user:1", "viewer", "document:1");
+        assert_eq!(
+            engine.check(&viewer, Some(&context("10.0.0.1")), &[]),
+            Ok(true)
+        );
+        assert_eq!(
+            engine.check(&viewer, Some(&context("10.0.0.2")), &[]),
+            Ok(false)
+        );
+        assert_eq!(engine.check(&viewer, None, &[]), Ok(false));
+
+        let unregistered = InMemoryEngine {
+            conditions: HashMap::new(),
+            ..engine.clone()
+        };
+        assert!(unregistered.check(&viewer, None, &[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn contextual_tuples_and_list_objects() {
+        let engine = engine(&[
+            ("user:1", "viewer", "document:1"),
+            ("folder:1", "parent", "document:2"),
+            ("group:1#member", "viewer", "folder:1"),
+        ]);
+        let member = vec![tuple("user:1", "member", "group:1").into()];
+        let checks = engine
+            .batch_check(
+                vec![
+                    tuple("user:1", "viewer", "document:2"),
+                    tuple("user:1", "viewer", "unknown:1"),
+                ],
+                None,
+                member.clone(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(checks[0], CheckResponse::Ok { allowed: true, .. }));
+        assert!(matches!(checks[1], CheckResponse::InvalidInput { .. }));
+        // contextual tuples are not written
+        assert!(!check(&engine, "user:1", "viewer", "document:2"));
+
+        let objects = |contextual_tuples| {
+            engine.list_objects("document", "viewer", "user:1", None, contextual_tuples)
+        };
+        assert_eq!(objects(Vec::new()).await.unwrap(), vec!["document:1"]);
+        assert_eq!(
+            objects(member).await.unwrap(),
+            vec!["document:1", "document:2"]
+        );
+    }
+}
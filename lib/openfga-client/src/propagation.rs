@@ -0,0 +1,18 @@
+use opentelemetry::propagation::Injector;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+/// injects trace context into headers of a reqwest request
+///
+/// `opentelemetry_http::HeaderInjector` of opentelemetry-http 0.11 takes http 0.2 headers, reqwest 0.12 is built on http 1
+pub(crate) struct HeaderInjector<'a>(pub &'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
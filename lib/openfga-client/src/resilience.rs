@@ -0,0 +1,393 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use reqwest::{Client, Method, Request, Response, StatusCode};
+use tracing::warn;
+
+use crate::{Error, OpenFGA};
+
+#[derive(Debug, Clone)]
+pub struct ResilienceConfig {
+    /// whole request including reading the response body
+    pub timeout: Duration,
+    /// whole `streamed_list_objects` stream, it outlives `timeout` when OpenFGA finds many objects
+    pub stream_timeout: Duration,
+    pub connect_timeout: Duration,
+    /// retries of a query failed with a timeout, a connection error, `429` or `5xx`, writes are never retried
+    pub max_retries: u32,
+    /// backoff before the first retry, doubled every retry up to `max_backoff`, the actual wait is a random part of it
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// consecutive failed requests opening the circuit, requests fail fast with `Error::CircuitOpen` while open
+    pub failure_threshold: u32,
+    /// time the circuit stays open, a single trial request decides whether it closes again
+    pub open_duration: Duration,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        ResilienceConfig {
+            timeout: Duration::from_secs(3),
+            stream_timeout: Duration::from_secs(60),
+            connect_timeout: Duration::from_millis(500),
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    /// the circuit is half open and a trial request is in flight
+    trial: bool,
+}
+
+/// circuit breaker shared by every clone of `OpenFGA`
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    state: Arc<Mutex<CircuitState>>,
+    failure_threshold: u32,
+    open_duration: Duration,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: &ResilienceConfig) -> Self {
+        CircuitBreaker {
+            state: Arc::new(Mutex::new(CircuitState::default())),
+            failure_threshold: config.failure_threshold.max(1),
+            open_duration: config.open_duration,
+        }
+    }
+
+    /// permit of a request, the circuit half opens once `open_duration` passed and lets a single trial through
+    fn acquire(&self) -> Result<CircuitPermit<'_>, Error> {
+        let mut state = self.state.lock().unwrap();
+        let trial = match state.open_until {
+            None => false,
+            Some(until) if Instant::now() < until => {
+                return Err(Error::CircuitOpen(until - Instant::now()))
+            }
+            Some(_) if state.trial => return Err(Error::CircuitOpen(Duration::ZERO)),
+            Some(_) => {
+                state.trial = true;
+                true
+            }
+        };
+        Ok(CircuitPermit {
+            circuit: self,
+            trial,
+            decided: false,
+        })
+    }
+
+    fn succeeded(&self) {
+        let mut state = self.state.lock().unwrap();
+        *state = CircuitState::default();
+    }
+
+    fn failed(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        let half_open = state.trial;
+        state.trial = false;
+        if half_open || state.consecutive_failures >= self.failure_threshold {
+            warn!(
+                "OpenFGA circuit opened for {:?} after {} consecutive failures",
+                self.open_duration, state.consecutive_failures
+            );
+            state.open_until = Some(Instant::now() + self.open_duration);
+        }
+    }
+}
+
+/// request let through by `CircuitBreaker::acquire`, its outcome closes or opens the circuit
+///
+/// a trial dropped before its outcome, such as a cancelled future, counts as failed, otherwise the circuit would stay half open forever
+struct CircuitPermit<'a> {
+    circuit: &'a CircuitBreaker,
+    trial: bool,
+    decided: bool,
+}
+
+impl CircuitPermit<'_> {
+    fn succeeded(mut self) {
+        self.decided = true;
+        self.circuit.succeeded();
+    }
+
+    fn failed(mut self) {
+        self.decided = true;
+        self.circuit.failed();
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if self.trial && !self.decided {
+            self.circuit.failed();
+        }
+    }
+}
+
+pub(crate) fn resilient_client(config: &ResilienceConfig) -> Client {
+    Client::builder()
+        .timeout(config.timeout)
+        .connect_timeout(config.connect_timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+/// `429` and `5xx` are worth retrying, OpenFGA answers every other status the same way again
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+impl OpenFGA {
+    /// timeouts, retries and circuit breaker of every request, `ResilienceConfig::default()` unless given
+    ///
+    /// the circuit breaker is replaced, clones made before no longer share it
+    pub fn with_resilience(mut self, config: ResilienceConfig) -> Self {
+        self.client = resilient_client(&config);
+        self.circuit = CircuitBreaker::new(&config);
+        self.resilience = config;
+        self
+    }
+
+    /// sends a query, retried when it failed transiently
+    pub(crate) async fn execute(&self, request: Request) -> Result<Response, Error> {
+        let mut attempt = 0;
+        loop {
+            // json bodies are cloneable, the last attempt sends `request` itself
+            let Some(retry) = request
+                .try_clone()
+                .filter(|_| attempt < self.resilience.max_retries)
+            else {
+                return self.execute_once(request).await;
+            };
+            let result = self.execute_once(retry).await;
+            let transient = match &result {
+                Ok(response) => is_transient(response.status()),
+                Err(Error::Reqwest(err)) => err.is_timeout() || err.is_connect(),
+                Err(_) => false,
+            };
+            if !transient {
+                return result;
+            }
+            let backoff = self.backoff(attempt);
+            warn!(
+                "OpenFGA {} {} failed transiently, retry {} in {:?}",
+                request.method(),
+                request.url().path(),
+                attempt + 1,
+                backoff
+            );
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// sends a request through the circuit breaker without retrying it, for writes which may be applied despite failing
+    pub(crate) async fn execute_once(&self, request: Request) -> Result<Response, Error> {
+        let permit = self.circuit.acquire()?;
+        let result = self.client.execute(request).await.map_err(Error::from);
+        match &result {
+            Ok(response) if is_transient(response.status()) => permit.failed(),
+            Ok(_) => permit.succeeded(),
+            Err(_) => permit.failed(),
+        }
+        result
+    }
+
+    /// management requests other than `GET` create or change stores and models, they are sent once
+    pub(crate) async fn execute_method(&self, request: Request) -> Result<Response, Error> {
+        match *request.method() {
+            Method::GET => self.execute(request).await,
+            _ => self.execute_once(request).await,
+        }
+    }
+
+    /// full jitter of the exponential backoff of `attempt`
+    fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .resilience
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.resilience.max_backoff);
+        ceiling.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const OPEN: Duration = Duration::from_millis(50);
+
+    fn config() -> ResilienceConfig {
+        ResilienceConfig {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            failure_threshold: 2,
+            open_duration: OPEN,
+            ..ResilienceConfig::default()
+        }
+    }
+
+    fn is_open(circuit: &CircuitBreaker) -> bool {
+        matches!(circuit.acquire(), Err(Error::CircuitOpen(_)))
+    }
+
+    #[tokio::test]
+    async fn circuit_transitions() {
+        let circuit = CircuitBreaker::new(&config());
+        circuit.acquire().unwrap().failed();
+        assert!(!is_open(&circuit));
+        // a success resets the consecutive failures
+        circuit.acquire().unwrap().succeeded();
+        circuit.acquire().unwrap().failed();
+        assert!(!is_open(&circuit));
+        circuit.acquire().unwrap().failed();
+        assert!(is_open(&circuit));
+
+        // half open lets a single trial through, a failed trial opens the circuit again
+        tokio::time::sleep(OPEN).await;
+        let trial = circuit.acquire().unwrap();
+        assert!(matches!(
+            circuit.acquire(),
+            Err(Error::CircuitOpen(Duration::ZERO))
+        ));
+        trial.failed();
+        assert!(is_open(&circuit));
+
+        // a succeeded trial closes it
+        tokio::time::sleep(OPEN).await;
+        circuit.acquire().unwrap().succeeded();
+        let permits = (0..3).map(|_| circuit.acquire()).collect::<Vec<_>>();
+        assert!(permits.iter().all(Result::is_ok));
+    }
+
+    #[tokio::test]
+    async fn dropped_trial_opens_circuit() {
+        let circuit = CircuitBreaker::new(&config());
+        circuit.acquire().unwrap().failed();
+        circuit.acquire().unwrap().failed();
+        tokio::time::sleep(OPEN).await;
+        drop(circuit.acquire().unwrap());
+        assert!(is_open(&circuit));
+
+        // requests dropped while closed decide nothing
+        tokio::time::sleep(OPEN).await;
+        circuit.acquire().unwrap().succeeded();
+        circuit.acquire().unwrap().failed();
+        drop(circuit.acquire().unwrap());
+        assert!(!is_open(&circuit));
+    }
+
+    #[test]
+    fn backoff() {
+        let openfga = OpenFGA::new("http://localhost", String::new(), String::new())
+            .with_resilience(config());
+        for (attempt, ceiling) in [(0, 1), (1, 2), (2, 4), (3, 4), (40, 4)] {
+            for _ in 0..20 {
+                assert!(openfga.backoff(attempt) <= Duration::from_millis(ceiling));
+            }
+        }
+    }
+
+    /// answers every request with the next of `statuses`, the last one repeated, and counts requests
+    async fn serve(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let count = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = Vec::new();
+                let mut read = [0; 1024];
+                while !buffer.ends_with(b"\r\n\r\n") {
+                    let n = socket.read(&mut read).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    buffer.extend_from_slice(&read[..n]);
+                }
+                let n = count.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[n.min(statuses.len() - 1)];
+                let response = format!(
+                    "HTTP/1.1 {} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    status
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    async fn execute(statuses: Vec<u16>, method: Method) -> (Result<u16, Error>, usize) {
+        let (url, requests) = serve(statuses).await;
+        let openfga = OpenFGA::new(url.clone(), String::new(), String::new()).with_resilience(
+            ResilienceConfig {
+                failure_threshold: 5,
+                ..config()
+            },
+        );
+        let request = openfga.client.request(method, url).build().unwrap();
+        let result = openfga
+            .execute_method(request)
+            .await
+            .map(|x| x.status().as_u16());
+        (result, requests.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn retry_transient() {
+        let (result, requests) = execute(vec![503, 429, 200], Method::GET).await;
+        assert_eq!((result.unwrap(), requests), (200, 3));
+
+        // the last attempt is returned as is
+        let (result, requests) = execute(vec![503], Method::GET).await;
+        assert_eq!((result.unwrap(), requests), (503, 3));
+
+        let (result, requests) = execute(vec![400, 200], Method::GET).await;
+        assert_eq!((result.unwrap(), requests), (400, 1));
+
+        // writes are sent once
+        let (result, requests) = execute(vec![503, 200], Method::POST).await;
+        assert_eq!((result.unwrap(), requests), (503, 1));
+    }
+
+    #[tokio::test]
+    async fn retry_opens_circuit() {
+        let (url, requests) = serve(vec![503]).await;
+        let openfga =
+            OpenFGA::new(url.clone(), String::new(), String::new()).with_resilience(config());
+        let request = || openfga.client.get(&url).build().unwrap();
+        // the circuit opens after `failure_threshold` attempts and fails the rest fast
+        let result = openfga.execute(request()).await;
+        assert!(matches!(result, Err(Error::CircuitOpen(_))));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        let result = openfga.execute(request()).await;
+        assert!(matches!(result, Err(Error::CircuitOpen(_))));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        // the trial request is retried like any other
+        tokio::time::sleep(OPEN).await;
+        let result = openfga.execute(request()).await;
+        assert!(matches!(result, Err(Error::CircuitOpen(_))));
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+}
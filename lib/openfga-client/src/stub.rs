@@ -0,0 +1,94 @@
+//! OpenFGA http api stub for tests, answers requests with canned responses and records them
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Request {
+    pub method: String,
+    pub path: String,
+    /// json body, `Value::Null` without body
+    pub body: Value,
+}
+
+pub(crate) struct Stub {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl Stub {
+    /// answers every request with the next of `responses`, the last one repeated
+    pub async fn serve(responses: Vec<(u16, &str)>) -> Self {
+        let responses = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.to_string()))
+            .collect::<Vec<_>>();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let request = read(&mut socket).await;
+                let n = {
+                    let mut recorded = recorded.lock().unwrap();
+                    recorded.push(request);
+                    recorded.len() - 1
+                };
+                let (status, body) = &responses[n.min(responses.len() - 1)];
+                let response = format!(
+                    "HTTP/1.1 {} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        Stub { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read(socket: &mut TcpStream) -> Request {
+    let mut buffer = Vec::new();
+    let mut read = [0; 1024];
+    let end = loop {
+        if let Some(end) = buffer.windows(4).position(|x| x == b"\r\n\r\n") {
+            break end;
+        }
+        let n = socket.read(&mut read).await.unwrap();
+        if n == 0 {
+            break buffer.len();
+        }
+        buffer.extend_from_slice(&read[..n]);
+    };
+    let head = String::from_utf8_lossy(&buffer[..end]).to_string();
+    let length = head
+        .lines()
+        .filter_map(|x| x.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or_default();
+    let mut body = buffer[(end + 4).min(buffer.len())..].to_vec();
+    while body.len() < length {
+        let n = socket.read(&mut read).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&read[..n]);
+    }
+    let mut line = head.lines().next().unwrap_or_default().split(' ');
+    Request {
+        method: line.next().unwrap_or_default().to_string(),
+        path: line.next().unwrap_or_default().to_string(),
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    }
+}
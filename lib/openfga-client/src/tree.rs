@@ -0,0 +1,361 @@
+use serde::{Deserialize, Serialize};
+
+/// relation tree of `expand`, `Display` pretty-prints it
+///
+/// ```text
+/// doc:1#viewer (union)
+/// ├── doc:1#viewer = user:1, user:2
+/// ├── doc:1#editor = doc:1#editor
+/// └── doc:1#viewer = folder:1#viewer from doc:1#parent
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsersetTree {
+    pub root: UsersetNode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsersetNode {
+    /// `type:id#relation`
+    pub name: String,
+    pub kind: UsersetNodeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsersetNodeKind {
+    Leaf(UsersetLeaf),
+    Union(Vec<UsersetNode>),
+    Intersection(Vec<UsersetNode>),
+    Difference {
+        base: Box<UsersetNode>,
+        subtract: Box<UsersetNode>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsersetLeaf {
+    /// users directly related, `user:1`, `user:*` or `group:1#member`
+    Users(Vec<String>),
+    /// rewritten from another relation of the same object
+    Computed(String),
+    /// rewritten from a relation of objects related by `tupleset`
+    TupleToUserset {
+        tupleset: String,
+        computed: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for UsersetTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.root.fmt_tree(f, "", None)
+    }
+}
+
+impl std::fmt::Display for UsersetNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_tree(f, "", None)
+    }
+}
+
+impl UsersetNode {
+    /// `last` is `None` for the root, otherwise whether the node is the last child of its parent
+    fn fmt_tree(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        prefix: &str,
+        last: Option<bool>,
+    ) -> std::fmt::Result {
+        let (branch, indent) = match last {
+            None => ("", ""),
+            Some(false) => ("├── ", "│   "),
+            Some(true) => ("└── ", "    "),
+        };
+        write!(f, "{}{}{}", prefix, branch, self.name)?;
+        let children = match &self.kind {
+            UsersetNodeKind::Leaf(UsersetLeaf::Users(users)) if users.is_empty() => {
+                return writeln!(f, " = (none)")
+            }
+            UsersetNodeKind::Leaf(UsersetLeaf::Users(users)) => {
+                return writeln!(f, " = {}", users.join(", "))
+            }
+            UsersetNodeKind::Leaf(UsersetLeaf::Computed(userset)) => {
+                return writeln!(f, " = {}", userset)
+            }
+            UsersetNodeKind::Leaf(UsersetLeaf::TupleToUserset { tupleset, computed }) => {
+                return writeln!(f, " = {} from {}", computed.join(", "), tupleset)
+            }
+            UsersetNodeKind::Union(nodes) => {
+                writeln!(f, " (union)")?;
+                nodes.iter().collect::<Vec<_>>()
+            }
+            UsersetNodeKind::Intersection(nodes) => {
+                writeln!(f, " (intersection)")?;
+                nodes.iter().collect::<Vec<_>>()
+            }
+            UsersetNodeKind::Difference { base, subtract } => {
+                writeln!(f, " (but not)")?;
+                vec![base.as_ref(), subtract.as_ref()]
+            }
+        };
+        let prefix = format!("{}{}", prefix, indent);
+        for (index, child) in children.iter().enumerate() {
+            child.fmt_tree(f, &prefix, Some(index + 1 == children.len()))?;
+        }
+        Ok(())
+    }
+}
+
+/// wire format of `expand`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ExpandResponseOk {
+    tree: WireTree,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireTree {
+    root: WireNode,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct WireNode {
+    name: String,
+    leaf: Option<WireLeaf>,
+    difference: Option<WireDifference>,
+    union: Option<WireNodes>,
+    intersection: Option<WireNodes>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct WireLeaf {
+    users: Option<WireUsers>,
+    computed: Option<WireComputed>,
+    tuple_to_userset: Option<WireTupleToUserset>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireUsers {
+    #[serde(default)]
+    users: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireComputed {
+    userset: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireTupleToUserset {
+    tupleset: String,
+    #[serde(default)]
+    computed: Vec<WireComputed>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireDifference {
+    base: Box<WireNode>,
+    subtract: Box<WireNode>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireNodes {
+    #[serde(default)]
+    nodes: Vec<WireNode>,
+}
+
+impl From<ExpandResponseOk> for UsersetTree {
+    fn from(value: ExpandResponseOk) -> Self {
+        UsersetTree {
+            root: value.tree.root.into(),
+        }
+    }
+}
+
+impl From<WireNode> for UsersetNode {
+    fn from(value: WireNode) -> Self {
+        let kind = match value {
+            WireNode {
+                union: Some(union), ..
+            } => UsersetNodeKind::Union(union.nodes.into_iter().map(Into::into).collect()),
+            WireNode {
+                intersection: Some(intersection),
+                ..
+            } => UsersetNodeKind::Intersection(
+                intersection.nodes.into_iter().map(Into::into).collect(),
+            ),
+            WireNode {
+                difference: Some(difference),
+                ..
+            } => UsersetNodeKind::Difference {
+                base: Box::new((*difference.base).into()),
+                subtract: Box::new((*difference.subtract).into()),
+            },
+            WireNode {
+                leaf:
+                    Some(WireLeaf {
+                        computed: Some(computed),
+                        ..
+                    }),
+                ..
+            } => UsersetNodeKind::Leaf(UsersetLeaf::Computed(computed.userset)),
+            WireNode {
+                leaf:
+                    Some(WireLeaf {
+                        tuple_to_userset: Some(ttu),
+                        ..
+                    }),
+                ..
+            } => UsersetNodeKind::Leaf(UsersetLeaf::TupleToUserset {
+                tupleset: ttu.tupleset,
+                computed: ttu.computed.into_iter().map(|x| x.userset).collect(),
+            }),
+            WireNode {
+                leaf: Some(WireLeaf {
+                    users: Some(users), ..
+                }),
+                ..
+            } => UsersetNodeKind::Leaf(UsersetLeaf::Users(users.users)),
+            // a leaf without users is sent as an empty object
+            WireNode { .. } => UsersetNodeKind::Leaf(UsersetLeaf::Users(Vec::new())),
+        };
+        UsersetNode {
+            name: value.name,
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// `expand` of `document:roadmap#viewer` as returned by OpenFGA
+    const EXPAND: &str = r##"{
+      "tree": {
+        "root": {
+          "name": "document:roadmap#viewer",
+          "union": {
+            "nodes": [
+              {
+                "name": "document:roadmap#viewer",
+                "leaf": { "users": { "users": ["user:anne", "team:product#member"] } }
+              },
+              {
+                "name": "document:roadmap#viewer",
+                "leaf": { "computed": { "userset": "document:roadmap#editor" } }
+              },
+              {
+                "name": "document:roadmap#viewer",
+                "leaf": {
+                  "tupleToUserset": {
+                    "tupleset": "document:roadmap#parent",
+                    "computed": [{ "userset": "folder:planning#viewer" }]
+                  }
+                }
+              },
+              {
+                "name": "document:roadmap#viewer",
+                "intersection": {
+                  "nodes": [
+                    { "name": "document:roadmap#member", "leaf": { "users": {} } },
+                    {
+                      "name": "document:roadmap#viewer",
+                      "difference": {
+                        "base": {
+                          "name": "document:roadmap#viewer",
+                          "leaf": { "users": { "users": ["user:*"] } }
+                        },
+                        "subtract": {
+                          "name": "document:roadmap#viewer",
+                          "leaf": { "computed": { "userset": "document:roadmap#blocked" } }
+                        }
+                      }
+                    }
+                  ]
+                }
+              }
+            ]
+          }
+        }
+      }
+    }"##;
+
+    fn node(name: &str, kind: UsersetNodeKind) -> UsersetNode {
+        UsersetNode {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    fn viewer(kind: UsersetNodeKind) -> UsersetNode {
+        node("document:roadmap#viewer", kind)
+    }
+
+    fn tree() -> UsersetTree {
+        serde_json::from_str::<ExpandResponseOk>(EXPAND)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn parse() {
+        let users = |users: &[&str]| {
+            UsersetNodeKind::Leaf(UsersetLeaf::Users(
+                users.iter().map(ToString::to_string).collect(),
+            ))
+        };
+        let computed =
+            |userset: &str| UsersetNodeKind::Leaf(UsersetLeaf::Computed(userset.to_string()));
+        assert_eq!(
+            tree(),
+            UsersetTree {
+                root: viewer(UsersetNodeKind::Union(vec![
+                    viewer(users(&["user:anne", "team:product#member"])),
+                    viewer(computed("document:roadmap#editor")),
+                    viewer(UsersetNodeKind::Leaf(UsersetLeaf::TupleToUserset {
+                        tupleset: "document:roadmap#parent".to_string(),
+                        computed: vec!["folder:planning#viewer".to_string()],
+                    })),
+                    viewer(UsersetNodeKind::Intersection(vec![
+                        node("document:roadmap#member", users(&[])),
+                        viewer(UsersetNodeKind::Difference {
+                            base: Box::new(viewer(users(&["user:*"]))),
+                            subtract: Box::new(viewer(computed("document:roadmap#blocked"))),
+                        }),
+                    ])),
+                ])),
+            }
+        );
+    }
+
+    #[test]
+    fn pretty_print() {
+        assert_eq!(
+            tree().to_string(),
+            "document:roadmap#viewer (union)
+├── document:roadmap#viewer = user:anne, team:product#member
+├── document:roadmap#viewer = document:roadmap#editor
+├── document:roadmap#viewer = folder:planning#viewer from document:roadmap#parent
+└── document:roadmap#viewer (intersection)
+    ├── document:roadmap#member = (none)
+    └── document:roadmap#viewer (but not)
+        ├── document:roadmap#viewer = user:*
+        └── document:roadmap#viewer = document:roadmap#blocked
+"
+        );
+    }
+
+    #[test]
+    fn leaf_root() {
+        let tree = serde_json::from_str::<ExpandResponseOk>(
+            r#"{"tree":{"root":{"name":"document:roadmap#owner","leaf":{"users":{"users":["user:anne"]}}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            UsersetTree::from(tree).to_string(),
+            "document:roadmap#owner = user:anne\n"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+/// 1-based position in the DSL source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// syntax or validation error, positioned when the model is parsed from the DSL
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{}{}", .position.map(|x| format!("{}: ", x)).unwrap_or_default(), .message)]
+pub struct Error {
+    pub position: Option<Position>,
+    pub message: String,
+}
+
+impl Error {
+    pub(crate) fn new<M: Into<String>>(position: Option<Position>, message: M) -> Self {
+        Self { position, message: message.into() }
+    }
+}
+
+/// errors joined one per line, for reporting them as a single message
+pub fn join_errors(errors: &[Error]) -> String {
+    errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ConditionDefinition, ConditionParameter, Error, Model, ParameterType, RelationDefinition, RestrictionKind, Rewrite, TypeDefinition, TypeRestriction};
+
+/// json format of the OpenFGA API, relations and conditions are maps and come back sorted by name
+#[derive(Serialize, Deserialize, Debug)]
+struct WireModel {
+    schema_version: String,
+    #[serde(default)]
+    type_definitions: Vec<WireType>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    conditions: BTreeMap<String, WireCondition>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireType {
+    #[serde(rename = "type")]
+    otype: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    relations: BTreeMap<String, WireUserset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metadata: Option<WireMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct WireMetadata {
+    #[serde(default)]
+    relations: BTreeMap<String, WireRelationMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct WireRelationMetadata {
+    #[serde(default)]
+    directly_related_user_types: Vec<WireRelationReference>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireRelationReference {
+    #[serde(rename = "type")]
+    otype: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    relation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wildcard: Option<WireEmpty>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    condition: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireEmpty {}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct WireUserset {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    this: Option<WireEmpty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    computed_userset: Option<WireObjectRelation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tuple_to_userset: Option<WireTupleToUserset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    union: Option<WireUsersets>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    intersection: Option<WireUsersets>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    difference: Option<WireDifference>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireObjectRelation {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    object: String,
+    #[serde(default)]
+    relation: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct WireTupleToUserset {
+    tupleset: WireObjectRelation,
+    computed_userset: WireObjectRelation,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireUsersets {
+    #[serde(default)]
+    child: Vec<WireUserset>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireDifference {
+    base: Box<WireUserset>,
+    subtract: Box<WireUserset>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireCondition {
+    name: String,
+    expression: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    parameters: BTreeMap<String, WireParameterType>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WireParameterType {
+    type_name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    generic_types: Vec<WireParameterType>,
+}
+
+impl Model {
+    /// json of the authorization model as written to OpenFGA
+    pub fn to_json(&self) -> Value {
+        let model = WireModel {
+            schema_version: self.schema_version.clone(),
+            type_definitions: self.types.iter().map(WireType::from).collect(),
+            conditions: self.conditions.iter().map(|x| (x.name.clone(), WireCondition::from(x))).collect(),
+        };
+        serde_json::to_value(model).unwrap_or_default()
+    }
+
+    /// reads the json of an authorization model without validating it
+    pub fn from_json(value: &Value) -> Result<Model, Error> {
+        let model = WireModel::deserialize(value).map_err(|e| Error::new(None, format!("invalid authorization model: {}", e)))?;
+        Ok(Model {
+            schema_version: model.schema_version,
+            types: model.type_definitions.into_iter().map(TypeDefinition::try_from).collect::<Result<_, _>>()?,
+            conditions: model.conditions.into_iter().map(|(name, condition)| condition.into_definition(name)).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TypeDefinition {
+    /// json of a single type definition, one item of `type_definitions`
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(WireType::from(self)).unwrap_or_default()
+    }
+
+    pub fn from_json(value: &Value) -> Result<TypeDefinition, Error> {
+        WireType::deserialize(value).map_err(|e| Error::new(None, format!("invalid type definition: {}", e)))?.try_into()
+    }
+}
+
+impl From<&TypeDefinition> for WireType {
+    fn from(value: &TypeDefinition) -> Self {
+        let relations = value.relations.iter().map(|x| (x.name.clone(), WireUserset::from(&x.rewrite))).collect::<BTreeMap<_, _>>();
+        let metadata = WireMetadata {
+            relations: value
+                .relations
+                .iter()
+                .map(|x| {
+                    let directly_related_user_types = x.rewrite.direct().into_iter().map(WireRelationReference::from).collect();
+                    (x.name.clone(), WireRelationMetadata { directly_related_user_types })
+                })
+                .collect(),
+        };
+        WireType {
+            otype: value.name.clone(),
+            metadata: (!relations.is_empty()).then_some(metadata),
+            relations,
+        }
+    }
+}
+
+impl From<&TypeRestriction> for WireRelationReference {
+    fn from(value: &TypeRestriction) -> Self {
+        WireRelationReference {
+            otype: value.otype.clone(),
+            relation: match &value.kind {
+                | RestrictionKind::Userset(relation) => Some(relation.clone()),
+                | _ => None,
+            },
+            wildcard: (value.kind == RestrictionKind::Wildcard).then_some(WireEmpty {}),
+            condition: value.condition.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&Rewrite> for WireUserset {
+    fn from(value: &Rewrite) -> Self {
+        let mut userset = WireUserset {
+            this: None,
+            computed_userset: None,
+            tuple_to_userset: None,
+            union: None,
+            intersection: None,
+            difference: None,
+        };
+        match value {
+            | Rewrite::Direct(_) => userset.this = Some(WireEmpty {}),
+            | Rewrite::Computed { relation, .. } => {
+                userset.computed_userset = Some(WireObjectRelation {
+                    object: String::new(),
+                    relation: relation.clone(),
+                })
+            }
+            | Rewrite::TupleToUserset { computed, tupleset, .. } => {
+                userset.tuple_to_userset = Some(WireTupleToUserset {
+                    tupleset: WireObjectRelation {
+                        object: String::new(),
+                        relation: tupleset.clone(),
+                    },
+                    computed_userset: WireObjectRelation {
+                        object: String::new(),
+                        relation: computed.clone(),
+                    },
+                })
+            }
+            | Rewrite::Union(children) => {
+                userset.union = Some(WireUsersets {
+                    child: children.iter().map(Into::into).collect(),
+                })
+            }
+            | Rewrite::Intersection(children) => {
+                userset.intersection = Some(WireUsersets {
+                    child: children.iter().map(Into::into).collect(),
+                })
+            }
+            | Rewrite::Difference { base, subtract } => {
+                userset.difference = Some(WireDifference {
+                    base: Box::new(base.as_ref().into()),
+                    subtract: Box::new(subtract.as_ref().into()),
+                })
+            }
+        }
+        userset
+    }
+}
+
+impl From<&ConditionDefinition> for WireCondition {
+    fn from(value: &ConditionDefinition) -> Self {
+        WireCondition {
+            name: value.name.clone(),
+            expression: value.expression.clone(),
+            parameters: value.parameters.iter().map(|x| (x.name.clone(), WireParameterType::from(&x.ptype))).collect(),
+        }
+    }
+}
+
+impl From<&ParameterType> for WireParameterType {
+    fn from(value: &ParameterType) -> Self {
+        WireParameterType {
+            type_name: value.type_name().to_string(),
+            generic_types: match value {
+                | ParameterType::List(generic) | ParameterType::Map(generic) => vec![generic.as_ref().into()],
+                | _ => Vec::new(),
+            },
+        }
+    }
+}
+
+impl TryFrom<WireType> for TypeDefinition {
+    type Error = Error;
+
+    fn try_from(value: WireType) -> Result<Self, Self::Error> {
+        let mut metadata = value.metadata.unwrap_or_default();
+        let relations = value
+            .relations
+            .into_iter()
+            .map(|(name, userset)| {
+                let direct = metadata.relations.remove(&name).unwrap_or_default().directly_related_user_types;
+                let direct = direct.into_iter().map(TypeRestriction::from).collect::<Vec<_>>();
+                Ok(RelationDefinition {
+                    rewrite: userset.into_rewrite(&direct).map_err(|e| Error::new(None, format!("relation {}#{}: {}", value.otype, name, e.message)))?,
+                    name,
+                    position: None,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(TypeDefinition { name: value.otype, relations, position: None })
+    }
+}
+
+impl From<WireRelationReference> for TypeRestriction {
+    fn from(value: WireRelationReference) -> Self {
+        TypeRestriction {
+            otype: value.otype,
+            kind: match (value.relation, value.wildcard) {
+                | (Some(relation), _) if !relation.is_empty() => RestrictionKind::Userset(relation),
+                | (_, Some(_)) => RestrictionKind::Wildcard,
+                | _ => RestrictionKind::Type,
+            },
+            condition: (!value.condition.is_empty()).then_some(value.condition),
+            position: None,
+        }
+    }
+}
+
+impl WireUserset {
+    /// `direct` are the directly related types of the relation, shared by every `this` of its rewrite
+    fn into_rewrite(self, direct: &[TypeRestriction]) -> Result<Rewrite, Error> {
+        Ok(match self {
+            | WireUserset { this: Some(_), .. } => Rewrite::Direct(direct.to_vec()),
+            | WireUserset { computed_userset: Some(computed), .. } => Rewrite::Computed { relation: computed.relation, position: None },
+            | WireUserset { tuple_to_userset: Some(ttu), .. } => Rewrite::TupleToUserset {
+                computed: ttu.computed_userset.relation,
+                tupleset: ttu.tupleset.relation,
+                position: None,
+            },
+            | WireUserset { union: Some(union), .. } => Rewrite::Union(union.child.into_iter().map(|x| x.into_rewrite(direct)).collect::<Result<_, _>>()?),
+            | WireUserset { intersection: Some(intersection), .. } => Rewrite::Intersection(intersection.child.into_iter().map(|x| x.into_rewrite(direct)).collect::<Result<_, _>>()?),
+            | WireUserset { difference: Some(difference), .. } => Rewrite::Difference {
+                base: Box::new(difference.base.into_rewrite(direct)?),
+                subtract: Box::new(difference.subtract.into_rewrite(direct)?),
+            },
+            | WireUserset { .. } => return Err(Error::new(None, "empty userset rewrite")),
+        })
+    }
+}
+
+impl WireCondition {
+    fn into_definition(self, name: String) -> Result<ConditionDefinition, Error> {
+        if self.name != name {
+            return Err(Error::new(None, format!("condition {} is named {}", name, self.name)));
+        }
+        Ok(ConditionDefinition {
+            parameters: self
+                .parameters
+                .into_iter()
+                .map(|(parameter, ptype)| {
+                    Ok(ConditionParameter {
+                        ptype: ptype.try_into().map_err(|e: Error| Error::new(None, format!("parameter {} of condition {}: {}", parameter, name, e.message)))?,
+                        name: parameter,
+                        position: None,
+                    })
+                })
+                .collect::<Result<_, Error>>()?,
+            name,
+            expression: self.expression,
+            position: None,
+        })
+    }
+}
+
+impl TryFrom<WireParameterType> for ParameterType {
+    type Error = Error;
+
+    fn try_from(value: WireParameterType) -> Result<Self, Self::Error> {
+        let generic = || -> Result<Box<ParameterType>, Error> {
+            match <[WireParameterType; 1]>::try_from(value.generic_types) {
+                | Ok([generic]) => Ok(Box::new(generic.try_into()?)),
+                | Err(_) => Err(Error::new(None, format!("{} takes exactly one generic type", value.type_name))),
+            }
+        };
+        Ok(match value.type_name.as_str() {
+            | "TYPE_NAME_LIST" => ParameterType::List(generic()?),
+            | "TYPE_NAME_MAP" => ParameterType::Map(generic()?),
+            | type_name => type_name
+                .strip_prefix("TYPE_NAME_")
+                .and_then(|x| ParameterType::from_dsl(&x.to_lowercase()))
+                .ok_or_else(|| Error::new(None, format!("unknown parameter type {}", type_name)))?,
+        })
+    }
+}
@@ -0,0 +1,17 @@
+//! OpenFGA DSL, parsed into `Model`, validated and converted from and into the json format of the OpenFGA API
+//!
+//! ```ignore
+//! let model = openfga_dsl::Model::from_dsl(source)?;
+//! let json = model.to_json();
+//! println!("{}", openfga_dsl::Model::from_json(&json)?);
+//! ```
+
+pub use error::*;
+pub use model::*;
+pub use parser::*;
+
+mod error;
+mod json;
+mod model;
+mod parser;
+mod validate;
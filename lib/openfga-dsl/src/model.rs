@@ -0,0 +1,260 @@
+use crate::Position;
+
+/// authorization model, `Display` prints it as DSL
+///
+/// positions are set when the model is parsed from the DSL and `None` when it is read from json
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Model {
+    pub schema_version: String,
+    pub types: Vec<TypeDefinition>,
+    pub conditions: Vec<ConditionDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDefinition {
+    pub name: String,
+    pub relations: Vec<RelationDefinition>,
+    pub position: Option<Position>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationDefinition {
+    pub name: String,
+    pub rewrite: Rewrite,
+    pub position: Option<Position>,
+}
+
+/// userset rewrite of a relation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rewrite {
+    /// `[user, user:*, group#member, user with condition]`
+    Direct(Vec<TypeRestriction>),
+    /// `editor`
+    Computed { relation: String, position: Option<Position> },
+    /// `viewer from parent`
+    TupleToUserset { computed: String, tupleset: String, position: Option<Position> },
+    /// `a or b`
+    Union(Vec<Rewrite>),
+    /// `a and b`
+    Intersection(Vec<Rewrite>),
+    /// `a but not b`
+    Difference { base: Box<Rewrite>, subtract: Box<Rewrite> },
+}
+
+/// type directly related to a relation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeRestriction {
+    pub otype: String,
+    pub kind: RestrictionKind,
+    pub condition: Option<String>,
+    pub position: Option<Position>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestrictionKind {
+    /// `user`
+    Type,
+    /// `user:*`
+    Wildcard,
+    /// `group#member`
+    Userset(String),
+}
+
+/// `condition name(param: type, ..) { expression }`, the CEL expression is kept as written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionDefinition {
+    pub name: String,
+    pub parameters: Vec<ConditionParameter>,
+    pub expression: String,
+    pub position: Option<Position>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionParameter {
+    pub name: String,
+    pub ptype: ParameterType,
+    pub position: Option<Position>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParameterType {
+    Any,
+    Bool,
+    String,
+    Int,
+    Uint,
+    Double,
+    Duration,
+    Timestamp,
+    Ipaddress,
+    List(Box<ParameterType>),
+    Map(Box<ParameterType>),
+}
+
+impl ParameterType {
+    /// type without generic of the DSL, `None` for unknown or generic types
+    pub(crate) fn from_dsl(name: &str) -> Option<Self> {
+        Some(match name {
+            | "any" => ParameterType::Any,
+            | "bool" => ParameterType::Bool,
+            | "string" => ParameterType::String,
+            | "int" => ParameterType::Int,
+            | "uint" => ParameterType::Uint,
+            | "double" => ParameterType::Double,
+            | "duration" => ParameterType::Duration,
+            | "timestamp" => ParameterType::Timestamp,
+            | "ipaddress" => ParameterType::Ipaddress,
+            | _ => return None,
+        })
+    }
+
+    /// `type_name` of the json model
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            | ParameterType::Any => "TYPE_NAME_ANY",
+            | ParameterType::Bool => "TYPE_NAME_BOOL",
+            | ParameterType::String => "TYPE_NAME_STRING",
+            | ParameterType::Int => "TYPE_NAME_INT",
+            | ParameterType::Uint => "TYPE_NAME_UINT",
+            | ParameterType::Double => "TYPE_NAME_DOUBLE",
+            | ParameterType::Duration => "TYPE_NAME_DURATION",
+            | ParameterType::Timestamp => "TYPE_NAME_TIMESTAMP",
+            | ParameterType::Ipaddress => "TYPE_NAME_IPADDRESS",
+            | ParameterType::List(_) => "TYPE_NAME_LIST",
+            | ParameterType::Map(_) => "TYPE_NAME_MAP",
+        }
+    }
+}
+
+impl Model {
+    pub fn type_definition(&self, name: &str) -> Option<&TypeDefinition> {
+        self.types.iter().find(|x| x.name == name)
+    }
+
+    pub fn condition(&self, name: &str) -> Option<&ConditionDefinition> {
+        self.conditions.iter().find(|x| x.name == name)
+    }
+}
+
+impl TypeDefinition {
+    pub fn relation(&self, name: &str) -> Option<&RelationDefinition> {
+        self.relations.iter().find(|x| x.name == name)
+    }
+}
+
+impl Rewrite {
+    /// type restrictions of every direct assignment in the rewrite
+    pub fn direct(&self) -> Vec<&TypeRestriction> {
+        match self {
+            | Rewrite::Direct(restrictions) => restrictions.iter().collect(),
+            | Rewrite::Computed { .. } | Rewrite::TupleToUserset { .. } => Vec::new(),
+            | Rewrite::Union(children) | Rewrite::Intersection(children) => children.iter().flat_map(Rewrite::direct).collect(),
+            | Rewrite::Difference { base, subtract } => base.direct().into_iter().chain(subtract.direct()).collect(),
+        }
+    }
+
+    fn fmt_nested(&self, f: &mut std::fmt::Formatter<'_>, nested: bool) -> std::fmt::Result {
+        let (children, operator) = match self {
+            | Rewrite::Direct(restrictions) => {
+                let restrictions = restrictions.iter().map(ToString::to_string).collect::<Vec<_>>();
+                return write!(f, "[{}]", restrictions.join(", "));
+            }
+            | Rewrite::Computed { relation, .. } => return write!(f, "{}", relation),
+            | Rewrite::TupleToUserset { computed, tupleset, .. } => return write!(f, "{} from {}", computed, tupleset),
+            | Rewrite::Union(children) => (children.iter().collect::<Vec<_>>(), " or "),
+            | Rewrite::Intersection(children) => (children.iter().collect::<Vec<_>>(), " and "),
+            | Rewrite::Difference { base, subtract } => (vec![base.as_ref(), subtract.as_ref()], " but not "),
+        };
+        if nested {
+            write!(f, "(")?;
+        }
+        for (index, child) in children.into_iter().enumerate() {
+            if index > 0 {
+                write!(f, "{}", operator)?;
+            }
+            child.fmt_nested(f, true)?;
+        }
+        if nested {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "model")?;
+        writeln!(f, "  schema {}", self.schema_version)?;
+        for otype in &self.types {
+            writeln!(f)?;
+            write!(f, "{}", otype)?;
+        }
+        for condition in &self.conditions {
+            writeln!(f)?;
+            write!(f, "{}", condition)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for TypeDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "type {}", self.name)?;
+        if !self.relations.is_empty() {
+            writeln!(f, "  relations")?;
+        }
+        for relation in &self.relations {
+            writeln!(f, "    define {}: {}", relation.name, relation.rewrite)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Rewrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_nested(f, false)
+    }
+}
+
+impl std::fmt::Display for TypeRestriction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            | RestrictionKind::Type => write!(f, "{}", self.otype)?,
+            | RestrictionKind::Wildcard => write!(f, "{}:*", self.otype)?,
+            | RestrictionKind::Userset(relation) => write!(f, "{}#{}", self.otype, relation)?,
+        }
+        match &self.condition {
+            | Some(condition) => write!(f, " with {}", condition),
+            | None => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for ConditionDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parameters = self.parameters.iter().map(|x| format!("{}: {}", x.name, x.ptype)).collect::<Vec<_>>();
+        writeln!(f, "condition {}({}) {{", self.name, parameters.join(", "))?;
+        for line in self.expression.lines() {
+            writeln!(f, "  {}", line.trim())?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+impl std::fmt::Display for ParameterType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            | ParameterType::Any => write!(f, "any"),
+            | ParameterType::Bool => write!(f, "bool"),
+            | ParameterType::String => write!(f, "string"),
+            | ParameterType::Int => write!(f, "int"),
+            | ParameterType::Uint => write!(f, "uint"),
+            | ParameterType::Double => write!(f, "double"),
+            | ParameterType::Duration => write!(f, "duration"),
+            | ParameterType::Timestamp => write!(f, "timestamp"),
+            | ParameterType::Ipaddress => write!(f, "ipaddress"),
+            | ParameterType::List(generic) => write!(f, "list<{}>", generic),
+            | ParameterType::Map(generic) => write!(f, "map<{}>", generic),
+        }
+    }
+}
@@ -0,0 +1,486 @@
+use crate::{ConditionDefinition, ConditionParameter, Error, Model, ParameterType, Position, RelationDefinition, RestrictionKind, Rewrite, TypeDefinition, TypeRestriction};
+
+/// parses the DSL without validating the model, stops at the first syntax error
+///
+/// the `model` header is optional for fragments of a model, `schema` defaults to `1.1` then
+pub fn parse(source: &str) -> Result<Model, Error> {
+    Parser::new(source).model()
+}
+
+impl Model {
+    /// parses and validates the DSL
+    pub fn from_dsl(source: &str) -> Result<Model, Vec<Error>> {
+        let model = parse(source).map_err(|e| vec![e])?;
+        model.validate()?;
+        Ok(model)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Or,
+    And,
+    ButNot,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+impl Parser {
+    fn new(source: &str) -> Self {
+        Parser {
+            chars: source.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn position(&self) -> Position {
+        Position { line: self.line, column: self.column }
+    }
+
+    fn error<M: Into<String>>(&self, message: M) -> Error {
+        Error::new(Some(self.position()), message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// skips whitespace and comments, `#` starts a comment only at the start of a line or after whitespace,
+    /// otherwise it separates the relation of `group#member`
+    fn skip_trivia(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '#' && (self.index == 0 || self.chars[self.index - 1].is_whitespace()) {
+                while !matches!(self.peek(), None | Some('\n')) {
+                    self.bump();
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn peek_word(&self) -> Option<String> {
+        let word = self.chars[self.index..].iter().take_while(|c| is_word_char(**c)).collect::<String>();
+        (!word.is_empty()).then_some(word)
+    }
+
+    fn word(&mut self, what: &str) -> Result<(String, Position), Error> {
+        let position = self.position();
+        let word = self.peek_word().ok_or_else(|| self.error(format!("expected {}", what)))?;
+        for _ in word.chars() {
+            self.bump();
+        }
+        Ok((word, position))
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<Position, Error> {
+        match self.peek_word() {
+            | Some(word) if word == keyword => Ok(self.word(keyword)?.1),
+            | _ => Err(self.error(format!("expected `{}`", keyword))),
+        }
+    }
+
+    fn symbol(&mut self, symbol: char) -> Result<(), Error> {
+        match self.peek() {
+            | Some(c) if c == symbol => {
+                self.bump();
+                Ok(())
+            }
+            | _ => Err(self.error(format!("expected `{}`", symbol))),
+        }
+    }
+
+    fn model(&mut self) -> Result<Model, Error> {
+        self.skip_trivia();
+        let schema_version = match self.peek_word().as_deref() {
+            | Some("model") => {
+                self.keyword("model")?;
+                self.skip_trivia();
+                self.keyword("schema")?;
+                self.skip_trivia();
+                self.word("schema version")?.0
+            }
+            | _ => "1.1".to_string(),
+        };
+        let mut model = Model {
+            schema_version,
+            types: Vec::new(),
+            conditions: Vec::new(),
+        };
+        loop {
+            self.skip_trivia();
+            match self.peek_word().as_deref() {
+                | None if self.peek().is_none() => return Ok(model),
+                | Some("type") => model.types.push(self.type_definition()?),
+                | Some("condition") => model.conditions.push(self.condition()?),
+                | Some(word @ ("module" | "extend")) => return Err(self.error(format!("`{}` of modular models is not supported", word))),
+                | _ => return Err(self.error("expected `type` or `condition`")),
+            }
+        }
+    }
+
+    fn type_definition(&mut self) -> Result<TypeDefinition, Error> {
+        self.keyword("type")?;
+        self.skip_trivia();
+        let (name, position) = self.word("type name")?;
+        let mut relations = Vec::new();
+        self.skip_trivia();
+        if self.peek_word().as_deref() == Some("relations") {
+            self.keyword("relations")?;
+            self.skip_trivia();
+            if self.peek_word().as_deref() != Some("define") {
+                return Err(self.error("expected `define`"));
+            }
+            while self.peek_word().as_deref() == Some("define") {
+                relations.push(self.relation()?);
+                self.skip_trivia();
+            }
+        }
+        Ok(TypeDefinition { name, relations, position: Some(position) })
+    }
+
+    fn relation(&mut self) -> Result<RelationDefinition, Error> {
+        self.keyword("define")?;
+        self.skip_trivia();
+        let (name, position) = self.word("relation name")?;
+        self.skip_trivia();
+        self.symbol(':')?;
+        let rewrite = self.rewrite()?;
+        Ok(RelationDefinition { name, rewrite, position: Some(position) })
+    }
+
+    /// operators can't be mixed on one level, `a or b and c` needs parentheses
+    fn rewrite(&mut self) -> Result<Rewrite, Error> {
+        let mut children = vec![self.term()?];
+        let mut operator = None;
+        loop {
+            self.skip_trivia();
+            let position = self.position();
+            let next = match self.peek_word().as_deref() {
+                | Some("or") => Operator::Or,
+                | Some("and") => Operator::And,
+                | Some("but") => Operator::ButNot,
+                | _ => break,
+            };
+            if operator == Some(Operator::ButNot) {
+                return Err(Error::new(Some(position), "`but not` can't be followed by another operator without parentheses"));
+            }
+            if operator.is_some_and(|x| x != next) {
+                return Err(Error::new(Some(position), "operators `or`, `and` and `but not` can't be mixed without parentheses"));
+            }
+            operator = Some(next);
+            self.word("operator")?;
+            if next == Operator::ButNot {
+                self.skip_trivia();
+                self.keyword("not")?;
+            }
+            children.push(self.term()?);
+        }
+        Ok(match operator {
+            | None => children.remove(0),
+            | Some(Operator::Or) => Rewrite::Union(children),
+            | Some(Operator::And) => Rewrite::Intersection(children),
+            | Some(Operator::ButNot) => {
+                let subtract = children.remove(1);
+                Rewrite::Difference {
+                    base: Box::new(children.remove(0)),
+                    subtract: Box::new(subtract),
+                }
+            }
+        })
+    }
+
+    fn term(&mut self) -> Result<Rewrite, Error> {
+        self.skip_trivia();
+        match self.peek() {
+            | Some('[') => self.direct(),
+            | Some('(') => {
+                self.bump();
+                let rewrite = self.rewrite()?;
+                self.skip_trivia();
+                self.symbol(')')?;
+                Ok(rewrite)
+            }
+            | Some(c) if is_word_char(c) => {
+                let (relation, position) = self.word("relation")?;
+                self.skip_trivia();
+                if self.peek_word().as_deref() != Some("from") {
+                    return Ok(Rewrite::Computed { relation, position: Some(position) });
+                }
+                self.keyword("from")?;
+                self.skip_trivia();
+                let (tupleset, _) = self.word("tupleset relation")?;
+                Ok(Rewrite::TupleToUserset {
+                    computed: relation,
+                    tupleset,
+                    position: Some(position),
+                })
+            }
+            | _ => Err(self.error("expected `[`, `(` or a relation")),
+        }
+    }
+
+    fn direct(&mut self) -> Result<Rewrite, Error> {
+        self.symbol('[')?;
+        let mut restrictions = Vec::new();
+        loop {
+            self.skip_trivia();
+            let (otype, position) = self.word("type")?;
+            let kind = match self.peek() {
+                | Some(':') => {
+                    self.bump();
+                    self.symbol('*')?;
+                    RestrictionKind::Wildcard
+                }
+                | Some('#') => {
+                    self.bump();
+                    RestrictionKind::Userset(self.word("relation")?.0)
+                }
+                | _ => RestrictionKind::Type,
+            };
+            self.skip_trivia();
+            let condition = match self.peek_word().as_deref() {
+                | Some("with") => {
+                    self.keyword("with")?;
+                    self.skip_trivia();
+                    let condition = self.word("condition name")?.0;
+                    self.skip_trivia();
+                    Some(condition)
+                }
+                | _ => None,
+            };
+            restrictions.push(TypeRestriction {
+                otype,
+                kind,
+                condition,
+                position: Some(position),
+            });
+            match self.peek() {
+                | Some(',') => {
+                    self.bump();
+                }
+                | Some(']') => {
+                    self.bump();
+                    return Ok(Rewrite::Direct(restrictions));
+                }
+                | _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn condition(&mut self) -> Result<ConditionDefinition, Error> {
+        self.keyword("condition")?;
+        self.skip_trivia();
+        let (name, position) = self.word("condition name")?;
+        self.skip_trivia();
+        self.symbol('(')?;
+        let mut parameters = Vec::new();
+        loop {
+            self.skip_trivia();
+            if self.peek() == Some(')') && parameters.is_empty() {
+                self.bump();
+                break;
+            }
+            let (parameter, position) = self.word("parameter name")?;
+            self.skip_trivia();
+            self.symbol(':')?;
+            self.skip_trivia();
+            parameters.push(ConditionParameter {
+                name: parameter,
+                ptype: self.parameter_type()?,
+                position: Some(position),
+            });
+            self.skip_trivia();
+            match self.peek() {
+                | Some(',') => {
+                    self.bump();
+                }
+                | Some(')') => {
+                    self.bump();
+                    break;
+                }
+                | _ => return Err(self.error("expected `,` or `)`")),
+            }
+        }
+        self.skip_trivia();
+        Ok(ConditionDefinition {
+            name,
+            parameters,
+            expression: self.expression()?,
+            position: Some(position),
+        })
+    }
+
+    fn parameter_type(&mut self) -> Result<ParameterType, Error> {
+        let position = self.position();
+        let (name, _) = self.word("parameter type")?;
+        let generic = match name.as_str() {
+            | "list" | "map" => {
+                self.skip_trivia();
+                self.symbol('<')?;
+                self.skip_trivia();
+                let generic = self.parameter_type()?;
+                self.skip_trivia();
+                self.symbol('>')?;
+                Box::new(generic)
+            }
+            | _ => return ParameterType::from_dsl(&name).ok_or_else(|| Error::new(Some(position), format!("unknown parameter type `{}`", name))),
+        };
+        Ok(match name.as_str() {
+            | "list" => ParameterType::List(generic),
+            | _ => ParameterType::Map(generic),
+        })
+    }
+
+    /// CEL expression between braces, braces in string literals are not counted
+    fn expression(&mut self) -> Result<String, Error> {
+        let start = self.position();
+        self.symbol('{')?;
+        let mut expression = String::new();
+        let mut depth = 1;
+        let mut quote = None;
+        loop {
+            let c = self.bump().ok_or_else(|| Error::new(Some(start), "unclosed `{` of condition expression"))?;
+            match (quote, c) {
+                | (Some(_), '\\') => {
+                    expression.push(c);
+                    if let Some(escaped) = self.bump() {
+                        expression.push(escaped);
+                    }
+                    continue;
+                }
+                | (Some(q), c) if q == c => quote = None,
+                | (Some(_), _) => {}
+                | (None, '"' | '\'') => quote = Some(c),
+                | (None, '{') => depth += 1,
+                | (None, '}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                | (None, _) => {}
+            }
+            expression.push(c);
+        }
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return Err(Error::new(Some(start), "condition expression is empty"));
+        }
+        Ok(expression.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const MODEL: &str = r#"model
+  schema 1.1
+
+type user
+
+type group
+  relations
+    define member: [user, user:*, group#member]
+
+type folder
+  relations
+    define owner: [user]
+    define viewer: [user with in_office] or owner
+
+type document
+  relations
+    define parent: [folder]
+    define blocked: [user]
+    define editor: [user, group#member] and viewer from parent
+    define viewer: (editor or viewer from parent) but not blocked
+
+condition in_office(ip: ipaddress, cidrs: list<string>) {
+  ip.in_cidr(cidrs[0])
+}
+"#;
+
+    #[test]
+    fn dsl_to_json() {
+        let model = Model::from_dsl(MODEL).unwrap();
+        let json = model.to_json();
+        assert_eq!(
+            json["type_definitions"][3]["relations"]["viewer"],
+            json!({"difference": {
+                "base": {"union": {"child": [
+                    {"computedUserset": {"relation": "editor"}},
+                    {"tupleToUserset": {"tupleset": {"relation": "parent"}, "computedUserset": {"relation": "viewer"}}}
+                ]}},
+                "subtract": {"computedUserset": {"relation": "blocked"}}
+            }})
+        );
+        assert_eq!(
+            json["type_definitions"][1]["metadata"]["relations"]["member"]["directly_related_user_types"],
+            json!([{"type": "user"}, {"type": "user", "wildcard": {}}, {"type": "group", "relation": "member"}])
+        );
+        assert_eq!(json["type_definitions"][2]["metadata"]["relations"]["viewer"]["directly_related_user_types"], json!([{"type": "user", "condition": "in_office"}]));
+        assert_eq!(
+            json["conditions"]["in_office"],
+            json!({"name": "in_office", "expression": "ip.in_cidr(cidrs[0])", "parameters": {
+                "ip": {"type_name": "TYPE_NAME_IPADDRESS"},
+                "cidrs": {"type_name": "TYPE_NAME_LIST", "generic_types": [{"type_name": "TYPE_NAME_STRING"}]}
+            }})
+        );
+    }
+
+    #[test]
+    fn json_to_dsl() {
+        let json = Model::from_dsl(MODEL).unwrap().to_json();
+        let model = Model::from_json(&json).unwrap();
+        assert_eq!(model.to_json(), json);
+        assert_eq!(Model::from_dsl(&model.to_string()).unwrap().to_json(), json);
+        assert_eq!(model.to_string().lines().nth(18), Some("    define parent: [folder]"));
+    }
+
+    #[test]
+    fn fragment_and_comments() {
+        let model = parse("# folders\ntype folder # no relations\n\ntype doc\n  relations\n    define parent: [folder]\n").unwrap();
+        assert_eq!(model.schema_version, "1.1");
+        assert_eq!(model.to_string(), "model\n  schema 1.1\n\ntype folder\n\ntype doc\n  relations\n    define parent: [folder]\n");
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let error = |source: &str| parse(source).unwrap_err().to_string();
+        assert_eq!(error("type doc\n  relations\n    define viewer: a or b and c"), "line 3, column 27: operators `or`, `and` and `but not` can't be mixed without parentheses");
+        assert_eq!(error("type doc\n  relations\n    define viewer [user]"), "line 3, column 19: expected `:`");
+        assert_eq!(error("type doc\n  relations\n    define viewer: [user,]"), "line 3, column 26: expected type");
+        assert_eq!(error("condition c(x: text) { x }"), "line 1, column 16: unknown parameter type `text`");
+        assert_eq!(error("condition c(x: int) { x > 1"), "line 1, column 21: unclosed `{` of condition expression");
+        assert_eq!(error("model\n  schema 1.1\nmodule doc"), "line 3, column 1: `module` of modular models is not supported");
+    }
+}
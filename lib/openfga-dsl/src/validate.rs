@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+
+use crate::{Error, Model, RelationDefinition, RestrictionKind, Rewrite, TypeDefinition};
+
+impl Model {
+    /// checks references between types, relations and conditions, every error is returned
+    ///
+    /// relations without entrypoint, like `define a: b` and `define b: a`, are only reported when there is no other error
+    pub fn validate(&self) -> Result<(), Vec<Error>> {
+        into_result(self.errors(None))
+    }
+
+    /// same as `validate` for type `name` only, other types and conditions are the context it refers to
+    pub fn validate_type(&self, name: &str) -> Result<(), Vec<Error>> {
+        into_result(self.errors(Some(name)))
+    }
+
+    fn errors(&self, only: Option<&str>) -> Vec<Error> {
+        let mut errors = Vec::new();
+        if only.is_none() && self.schema_version != "1.1" {
+            errors.push(Error::new(None, format!("schema {} is not supported, expected 1.1", self.schema_version)));
+        }
+        let mut types = HashSet::new();
+        for otype in &self.types {
+            let duplicated = !types.insert(otype.name.as_str());
+            if only.is_some_and(|x| x != otype.name) {
+                continue;
+            }
+            if duplicated {
+                errors.push(Error::new(otype.position, format!("type {} is defined more than once", otype.name)));
+            }
+            let mut relations = HashSet::new();
+            for relation in &otype.relations {
+                if !relations.insert(relation.name.as_str()) {
+                    errors.push(Error::new(relation.position, format!("relation {}#{} is defined more than once", otype.name, relation.name)));
+                }
+                self.validate_relation(otype, relation, &mut errors);
+            }
+        }
+        let mut conditions = HashSet::new();
+        for condition in self.conditions.iter().filter(|_| only.is_none()) {
+            if !conditions.insert(condition.name.as_str()) {
+                errors.push(Error::new(condition.position, format!("condition {} is defined more than once", condition.name)));
+            }
+            if condition.expression.trim().is_empty() {
+                errors.push(Error::new(condition.position, format!("condition {} has an empty expression", condition.name)));
+            }
+            let mut parameters = HashSet::new();
+            for parameter in &condition.parameters {
+                if !parameters.insert(parameter.name.as_str()) {
+                    errors.push(Error::new(parameter.position, format!("parameter {} of condition {} is defined more than once", parameter.name, condition.name)));
+                }
+            }
+        }
+        if errors.is_empty() {
+            errors = self.validate_entrypoints(only);
+        }
+        errors.sort_by_key(|x| x.position);
+        errors
+    }
+
+    fn validate_relation(&self, otype: &TypeDefinition, relation: &RelationDefinition, errors: &mut Vec<Error>) {
+        let name = format!("{}#{}", otype.name, relation.name);
+        if count_direct(&relation.rewrite) > 1 {
+            errors.push(Error::new(relation.position, format!("relation {} can have only one direct assignment", name)));
+        }
+        for restriction in relation.rewrite.direct() {
+            match self.type_definition(&restriction.otype) {
+                | None => errors.push(Error::new(restriction.position, format!("relation {}: unknown type {}", name, restriction.otype))),
+                | Some(related) => {
+                    if let RestrictionKind::Userset(userset) = &restriction.kind {
+                        if related.relation(userset).is_none() {
+                            errors.push(Error::new(restriction.position, format!("relation {}: unknown relation {}#{}", name, restriction.otype, userset)));
+                        }
+                    }
+                }
+            }
+            if let Some(condition) = &restriction.condition {
+                if self.condition(condition).is_none() {
+                    errors.push(Error::new(restriction.position, format!("relation {}: unknown condition {}", name, condition)));
+                }
+            }
+        }
+        self.validate_rewrite(otype, &name, &relation.rewrite, errors);
+    }
+
+    fn validate_rewrite(&self, otype: &TypeDefinition, name: &str, rewrite: &Rewrite, errors: &mut Vec<Error>) {
+        match rewrite {
+            | Rewrite::Direct(_) => {}
+            | Rewrite::Computed { relation, position } => {
+                if otype.relation(relation).is_none() {
+                    errors.push(Error::new(*position, format!("relation {}: unknown relation {}#{}", name, otype.name, relation)));
+                }
+            }
+            | Rewrite::TupleToUserset { computed, tupleset, position } => {
+                let Some(tupleset_relation) = otype.relation(tupleset) else {
+                    errors.push(Error::new(*position, format!("relation {}: unknown tupleset relation {}#{}", name, otype.name, tupleset)));
+                    return;
+                };
+                let Rewrite::Direct(restrictions) = &tupleset_relation.rewrite else {
+                    errors.push(Error::new(*position, format!("relation {}: tupleset relation {}#{} must be a direct assignment only", name, otype.name, tupleset)));
+                    return;
+                };
+                if restrictions.iter().any(|x| x.kind != RestrictionKind::Type) {
+                    errors.push(Error::new(*position, format!("relation {}: tupleset relation {}#{} can't be assigned usersets or wildcards", name, otype.name, tupleset)));
+                }
+                let related = restrictions.iter().filter_map(|x| self.type_definition(&x.otype));
+                if !related.clone().any(|x| x.relation(computed).is_some()) && related.count() > 0 {
+                    errors.push(Error::new(*position, format!("relation {}: no type of {}#{} defines relation {}", name, otype.name, tupleset, computed)));
+                }
+            }
+            | Rewrite::Union(children) | Rewrite::Intersection(children) => children.iter().for_each(|x| self.validate_rewrite(otype, name, x, errors)),
+            | Rewrite::Difference { base, subtract } => {
+                self.validate_rewrite(otype, name, base, errors);
+                self.validate_rewrite(otype, name, subtract, errors);
+            }
+        }
+    }
+
+    /// a relation has an entrypoint when a tuple can make it true, found by iterating to a fixpoint
+    fn validate_entrypoints(&self, only: Option<&str>) -> Vec<Error> {
+        let mut entrypoints = HashSet::new();
+        loop {
+            let before = entrypoints.len();
+            for otype in &self.types {
+                for relation in &otype.relations {
+                    if self.has_entrypoint(otype, &relation.rewrite, &entrypoints) {
+                        entrypoints.insert((otype.name.as_str(), relation.name.as_str()));
+                    }
+                }
+            }
+            if entrypoints.len() == before {
+                break;
+            }
+        }
+        self.types
+            .iter()
+            .filter(|otype| only.is_none_or(|x| x == otype.name))
+            .flat_map(|otype| otype.relations.iter().map(move |relation| (otype, relation)))
+            .filter(|(otype, relation)| !entrypoints.contains(&(otype.name.as_str(), relation.name.as_str())))
+            .map(|(otype, relation)| Error::new(relation.position, format!("relation {}#{} has no entrypoint, it is not assignable or defined by a cycle only", otype.name, relation.name)))
+            .collect()
+    }
+
+    fn has_entrypoint(&self, otype: &TypeDefinition, rewrite: &Rewrite, entrypoints: &HashSet<(&str, &str)>) -> bool {
+        match rewrite {
+            | Rewrite::Direct(restrictions) => restrictions.iter().any(|x| match &x.kind {
+                | RestrictionKind::Type | RestrictionKind::Wildcard => true,
+                | RestrictionKind::Userset(relation) => entrypoints.contains(&(x.otype.as_str(), relation.as_str())),
+            }),
+            | Rewrite::Computed { relation, .. } => entrypoints.contains(&(otype.name.as_str(), relation.as_str())),
+            | Rewrite::TupleToUserset { computed, tupleset, .. } => otype.relation(tupleset).map(|x| x.rewrite.direct()).unwrap_or_default().iter().any(|x| entrypoints.contains(&(x.otype.as_str(), computed.as_str()))),
+            | Rewrite::Union(children) => children.iter().any(|x| self.has_entrypoint(otype, x, entrypoints)),
+            | Rewrite::Intersection(children) => children.iter().all(|x| self.has_entrypoint(otype, x, entrypoints)),
+            | Rewrite::Difference { base, .. } => self.has_entrypoint(otype, base, entrypoints),
+        }
+    }
+}
+
+fn into_result(errors: Vec<Error>) -> Result<(), Vec<Error>> {
+    match errors.is_empty() {
+        | true => Ok(()),
+        | false => Err(errors),
+    }
+}
+
+fn count_direct(rewrite: &Rewrite) -> usize {
+    match rewrite {
+        | Rewrite::Direct(_) => 1,
+        | Rewrite::Computed { .. } | Rewrite::TupleToUserset { .. } => 0,
+        | Rewrite::Union(children) | Rewrite::Intersection(children) => children.iter().map(count_direct).sum(),
+        | Rewrite::Difference { base, subtract } => count_direct(base) + count_direct(subtract),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{join_errors, Model};
+
+    fn validate(source: &str) -> String {
+        Model::from_dsl(source).map(|_| String::new()).unwrap_or_else(|e| join_errors(&e))
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(validate("type user\ntype folder\n  relations\n    define viewer: [user] or viewer from parent\n    define parent: [folder]\n"), "");
+    }
+
+    #[test]
+    fn unknown_references() {
+        let errors = validate(
+            r#"type user
+type doc
+  relations
+    define owner: [user, team, user#friend, user with in_office]
+    define viewer: editor or owner
+    define parent: [doc]
+    define reader: viewer from parent or owner from viewer or admin from parent
+"#,
+        );
+        assert_eq!(
+            errors,
+            [
+                "line 4, column 26: relation doc#owner: unknown type team",
+                "line 4, column 32: relation doc#owner: unknown relation user#friend",
+                "line 4, column 45: relation doc#owner: unknown condition in_office",
+                "line 5, column 20: relation doc#viewer: unknown relation doc#editor",
+                "line 7, column 42: relation doc#reader: tupleset relation doc#viewer must be a direct assignment only",
+                "line 7, column 63: relation doc#reader: no type of doc#parent defines relation admin",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        let errors = validate("type user\ntype user\ntype doc\n  relations\n    define a: [user]\n    define a: [user] or [user]\n");
+        assert_eq!(
+            errors,
+            [
+                "line 2, column 6: type user is defined more than once",
+                "line 6, column 12: relation doc#a is defined more than once",
+                "line 6, column 12: relation doc#a can have only one direct assignment",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn tupleset_usersets() {
+        let errors = validate("type user\ntype group\n  relations\n    define member: [user]\n    define parent: [group#member]\n    define viewer: member from parent\n");
+        assert_eq!(errors, "line 6, column 20: relation group#viewer: tupleset relation group#parent can't be assigned usersets or wildcards");
+    }
+
+    #[test]
+    fn entrypoints() {
+        let errors = validate("type user\ntype doc\n  relations\n    define a: b\n    define b: a or c\n    define c: [user] and a\n    define d: [user] but not a\n");
+        assert_eq!(
+            errors,
+            [
+                "line 4, column 12: relation doc#a has no entrypoint, it is not assignable or defined by a cycle only",
+                "line 5, column 12: relation doc#b has no entrypoint, it is not assignable or defined by a cycle only",
+                "line 6, column 12: relation doc#c has no entrypoint, it is not assignable or defined by a cycle only",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn single_type() {
+        let model = Model::from_json(&serde_json::json!({"schema_version": "1.1", "type_definitions": [
+            {"type": "user"},
+            {"type": "team", "relations": {"member": {"computedUserset": {"relation": "lead"}}}},
+            {"type": "doc", "relations": {"owner": {"this": {}}}, "metadata": {"relations": {"owner": {"directly_related_user_types": [{"type": "user"}, {"type": "team", "relation": "member"}]}}}}
+        ]}))
+        .unwrap();
+        assert_eq!(model.validate_type("team").map_err(|e| join_errors(&e)), Err("relation team#member: unknown relation team#lead".to_string()));
+        assert_eq!(model.validate_type("doc").map_err(|e| join_errors(&e)), Ok(()));
+    }
+
+    #[test]
+    fn json_model() {
+        let model = Model::from_json(&serde_json::json!({"schema_version": "1.0", "type_definitions": [{"type": "doc", "relations": {"viewer": {"this": {}}}}]})).unwrap();
+        assert_eq!(model.validate().map_err(|e| join_errors(&e)), Err("schema 1.0 is not supported, expected 1.1".to_string()));
+    }
+}
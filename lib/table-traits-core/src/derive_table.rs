@@ -6,13 +6,15 @@ use syn::spanned::Spanned;
 use syn::Path;
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(table), supports(struct_named))]
+#[darling(attributes(table), supports(struct_named), and_then = Table::validation)]
 pub struct Table {
     pub(crate) ident: syn::Ident,
 
     pub(crate) schema: Option<String>,
     pub(crate) table: Option<String>,
     pub(crate) history: Option<TableHistory>,
+    pub(crate) check: Option<TableCheck>,
+    pub(crate) rebac: Option<TableRebac>,
 
     #[darling(default)]
     pub(crate) naming: TableNaming,
@@ -20,6 +22,15 @@ pub struct Table {
     pub(crate) data: ast::Data<(), Column>,
 }
 
+impl Table {
+    fn validation(self) -> darling::Result<Self> {
+        if self.rebac.is_some() && self.id_columns().len() != 1 {
+            return Err(darling::Error::custom("rebac requires exactly one id column").with_span(&self.ident));
+        }
+        Ok(self)
+    }
+}
+
 #[derive(Debug, FromMeta)]
 pub enum Case {
     Pascal,
@@ -27,6 +38,35 @@ pub enum Case {
     Snake,
 }
 
+/// opt-in compile time check against database schema, paths are relative to `CARGO_MANIFEST_DIR`
+///
+/// - `check(migrations = "../../deploy/migration/files/postgres-migration")` : apply all `*.sql` in version order
+/// - `check(snapshot = "schema.sql")` : single ddl file, ex) `pg_dump --schema-only`
+#[derive(Debug, FromMeta)]
+#[darling(and_then = TableCheck::validation)]
+pub struct TableCheck {
+    pub(crate) migrations: Option<String>,
+    pub(crate) snapshot: Option<String>,
+}
+
+impl TableCheck {
+    fn validation(self) -> darling::Result<Self> {
+        match (&self.migrations, &self.snapshot) {
+            (Some(_), None) | (None, Some(_)) => Ok(self),
+            _ => Err(darling::Error::custom("check requires exactly one of `migrations` or `snapshot`")),
+        }
+    }
+}
+
+/// row level authorization, rows are visible only when the principal has `rel` on `otype:<id>`
+///
+/// - `rebac(otype = "otype", rel = "viewer")`
+#[derive(Debug, FromMeta)]
+pub struct TableRebac {
+    pub(crate) otype: String,
+    pub(crate) rel: String,
+}
+
 #[derive(Debug, FromMeta)]
 pub struct TableHistory {
     pub(crate) schema: Option<String>,
@@ -37,6 +77,7 @@ pub struct TableHistory {
 
 #[derive(Debug, FromMeta, Derivative)]
 #[derivative(Default)]
+#[darling(default)]
 pub struct TableNaming {
     #[derivative(Default(value = r#"String::from("${table}Field")"#))]
     pub(crate) field: String,
@@ -114,6 +155,17 @@ impl Column {
             .as_ref()
             .map_or_else(|| self.ty.span(), |ident| ident.span())
     }
+    /// `Option<T>` columns are nullable, everything else is treated as NOT NULL
+    pub(crate) fn is_nullable(&self) -> bool {
+        match &self.ty {
+            syn::Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map_or(false, |segment| segment.ident == "Option"),
+            _ => false,
+        }
+    }
     pub fn validation(mut self) -> darling::Result<Self> {
         match (self.id, &self.filter) {
             (false, _)
@@ -175,6 +227,10 @@ pub struct ColumnFilterWith {
     #[darling(default)]
     pub(crate) nlike: bool,
     #[darling(default)]
+    pub(crate) ilike: bool,
+    #[darling(default)]
+    pub(crate) nilike: bool,
+    #[darling(default)]
     pub(crate) null: bool,
     #[darling(default)]
     pub(crate) not_null: bool,
@@ -689,4 +745,56 @@ mod test {
 }"#;
         pretty_assertions::assert_eq!(actual, expect);
     }
+
+    #[test]
+    fn column_nullable() {
+        let table = quote! {
+            #[derive(Table)]
+            #[table(schema = "public", table = "test")]
+            pub struct Test {
+                #[column(id)]
+                pub id: Uuid,
+                pub description: Option<String>,
+                pub qualified: ::core::option::Option<String>,
+            }
+        };
+        let source = syn::parse2(table).unwrap();
+        let actual = Table::from_derive_input(&source).unwrap();
+        let actual = actual.columns().into_iter().map(|column| column.is_nullable()).collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(actual, vec![false, true, true]);
+    }
+
+    #[test]
+    fn table_rebac() {
+        let table = quote! {
+            #[derive(Table)]
+            #[table(schema = "public", table = "otype", rebac(otype = "otype", rel = "viewer"))]
+            pub struct Otype {
+                #[column(id)]
+                pub otype_id: Uuid,
+                pub name: String,
+            }
+        };
+        let source = syn::parse2(table).unwrap();
+        let actual = Table::from_derive_input(&source).unwrap();
+        let actual = actual.rebac.map(|rebac| (rebac.otype, rebac.rel));
+        pretty_assertions::assert_eq!(actual, Some((String::from("otype"), String::from("viewer"))));
+    }
+
+    #[test]
+    fn table_rebac_composite_id() {
+        let table = quote! {
+            #[derive(Table)]
+            #[table(rebac(otype = "value", rel = "viewer"))]
+            pub struct Value {
+                #[column(id)]
+                pub otype_id: Uuid,
+                #[column(id)]
+                pub value_id: Uuid,
+            }
+        };
+        let source = syn::parse2(table).unwrap();
+        let actual = Table::from_derive_input(&source).map_err(|e| e.to_string());
+        pretty_assertions::assert_eq!(actual.err(), Some(String::from("rebac requires exactly one id column")));
+    }
 }
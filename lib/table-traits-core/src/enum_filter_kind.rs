@@ -12,6 +12,8 @@ pub enum FilterKind {
     NotIn,
     Like,
     NLike,
+    ILike,
+    NILike,
     Null,
     NotNull,
     Between,
@@ -38,6 +40,8 @@ impl FilterKind {
             FilterKind::NotIn,
             FilterKind::Like,
             FilterKind::NLike,
+            FilterKind::ILike,
+            FilterKind::NILike,
             FilterKind::Null,
             FilterKind::NotNull,
             FilterKind::Between,
@@ -63,6 +67,8 @@ impl FilterKind {
             FilterKind::NotIn => Ident::new("not_in", span),
             FilterKind::Like => Ident::new("like", span),
             FilterKind::NLike => Ident::new("nlike", span),
+            FilterKind::ILike => Ident::new("ilike", span),
+            FilterKind::NILike => Ident::new("nilike", span),
             FilterKind::Null => Ident::new("null", span),
             FilterKind::NotNull => Ident::new("not_null", span),
             FilterKind::Between => Ident::new("between", span),
@@ -88,6 +94,8 @@ impl FilterKind {
             FilterKind::NotIn => Ident::new("NotIn", span),
             FilterKind::Like => Ident::new("Like", span),
             FilterKind::NLike => Ident::new("NLike", span),
+            FilterKind::ILike => Ident::new("ILike", span),
+            FilterKind::NILike => Ident::new("NILike", span),
             FilterKind::Null => Ident::new("Null", span),
             FilterKind::NotNull => Ident::new("NotNull", span),
             FilterKind::Between => Ident::new("Between", span),
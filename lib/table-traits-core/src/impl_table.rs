@@ -1,7 +1,7 @@
 use darling::ast::Data;
 use darling::util::Override;
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
 
 use crate::derive_table::{Column, ColumnFilter, ColumnSorter, Table};
 use crate::token_cursor::TokenCursor;
@@ -65,6 +65,26 @@ impl Table {
             }
         }
     }
+    pub(crate) fn filter_type(&self, column: &Column, filter: Override<&ColumnFilter>, crate_location: CrateLocation) -> TokenStream {
+        let field_ty = &column.ty;
+        match filter {
+            Override::Inherit | Override::Explicit(ColumnFilter::Default) => quote! { <#field_ty as #crate_location::FilterImpl>::DefaultFilter },
+            Override::Explicit(ColumnFilter::With(_)) => self.naming.to_filter_type_ident(&self.ident, column.ident.as_ref().unwrap()).to_token_stream(),
+            Override::Explicit(ColumnFilter::By(by)) => by.to_token_stream(),
+        }
+    }
+    pub(crate) fn sorter_type(&self, column: &Column, sorter: Override<&ColumnSorter>, crate_location: CrateLocation) -> TokenStream {
+        let field_ty = &column.ty;
+        match sorter {
+            Override::Inherit | Override::Explicit(ColumnSorter::Default) => quote! { <#field_ty as #crate_location::SorterImpl>::DefaultSorter },
+            Override::Explicit(ColumnSorter::With(_)) => {
+                let field_ident = column.ident.as_ref().unwrap();
+                let field_ty_name = self.naming.to_locality_sorter_type_name(self.ident.to_string().as_str(), field_ident.to_string().as_str());
+                Ident::new(field_ty_name.as_str(), field_ident.span()).to_token_stream()
+            }
+            Override::Explicit(ColumnSorter::By(by)) => by.to_token_stream(),
+        }
+    }
     pub(crate) fn field(&self) -> TokenField {
         TokenField { table: self }
     }
@@ -179,6 +199,8 @@ impl Table {
         let cursor_type = self.cursor().ident();
         let sorter_type = self.table_sorter().ident();
         let id_type = self.id_type();
+        let impl_descriptor_rs = self.impl_descriptor(crate_location);
+        let impl_rebac_rs = self.impl_rebac(crate_location);
 
         let schema_lit = self.naming.to_schema_name(&self.schema, "public");
         let table_lit = self.naming.to_schema_name(&self.table, &table_lit);
@@ -194,6 +216,21 @@ impl Table {
                 #field_type::#enum_value
             }
         });
+        let id_values = self.id_columns().into_iter().map(|column| {
+            let field_name = self.naming.to_field_ident(&column.ident.as_ref().unwrap());
+            quote! { self.#field_name.clone() }
+        });
+        let id_rs = if self.id_columns().len() == 1 {
+            quote! { #(#id_values)* }
+        } else {
+            quote! { (#(#id_values),*) }
+        };
+        let id_values_rs = if self.id_columns().len() == 1 {
+            quote! { vec![::sea_query::Value::from(id.clone())] }
+        } else {
+            let indexes = (0..self.id_columns().len()).map(syn::Index::from);
+            quote! { vec![#(::sea_query::Value::from(id.#indexes.clone())),*] }
+        };
 
         quote! {
             impl #crate_location::Table for #table_ident{
@@ -212,12 +249,98 @@ impl Table {
                     vec![#(#id_fields_rs,)*]
                 }
 
+                fn id(&self) -> Self::Id{
+                    #id_rs
+                }
+
+                fn id_values(id: &Self::Id) -> Vec<::sea_query::Value>{
+                    #id_values_rs
+                }
+
                 fn to_cursor(&self) -> Self::Cursor{
                     Self::Cursor{
                         #(#cursor_fields,)*
                         __cursor_chunk: None,
                     }
                 }
+
+                #impl_descriptor_rs
+
+                #impl_rebac_rs
+            }
+        }
+    }
+
+    fn impl_rebac(&self, crate_location: CrateLocation) -> TokenStream {
+        let Some(rebac) = &self.rebac else {
+            return TokenStream::new();
+        };
+        let otype_lit = &rebac.otype;
+        let rel_lit = &rebac.rel;
+        quote! {
+            fn rebac() -> ::core::option::Option<#crate_location::TableRebac>{
+                ::core::option::Option::Some(#crate_location::TableRebac{ otype: #otype_lit, rel: #rel_lit })
+            }
+
+            fn rebac_oid(id: &Self::Id) -> ::core::option::Option<String>{
+                ::core::option::Option::Some(id.to_string())
+            }
+
+            fn rebac_id(oid: &str) -> ::core::option::Option<Self::Id>{
+                oid.parse().ok()
+            }
+        }
+    }
+
+    fn impl_descriptor(&self, crate_location: CrateLocation) -> TokenStream {
+        let name_lit = self.ident.to_string();
+        let schema_lit = self.naming.to_schema_name(&self.schema, "public");
+        let table_lit = self.naming.to_schema_name(&self.table, &name_lit);
+        let id_columns_lit = self.id_columns().into_iter().map(|column| self.naming.to_db_case(&column.ident.as_ref().unwrap().to_string()));
+        let filters = self.filter_columns();
+        let sorters = self.sorter_columns();
+        let columns_rs = self.columns().into_iter().map(|column| {
+            let ident = column.ident.as_ref().unwrap();
+            let field_lit = ident.to_string();
+            let column_lit = self.naming.to_db_case(&field_lit);
+            let rust_type_lit = column.ty.to_token_stream().to_string().replace(' ', "");
+            let nullable = column.is_nullable();
+            let id = column.id;
+            let filters_rs = match filters.iter().find(|(x, _)| x.ident == column.ident) {
+                Some((column, filter)) => {
+                    let filter_ty = self.filter_type(column, filter.clone(), crate_location);
+                    quote! { <#filter_ty as #crate_location::Filter>::implemented_filters() }
+                }
+                None => quote! { ::std::vec::Vec::new() },
+            };
+            let sorters_rs = match sorters.iter().find(|(x, _)| x.ident == column.ident) {
+                Some((column, sorter)) => {
+                    let sorter_ty = self.sorter_type(column, sorter.clone(), crate_location);
+                    quote! { <#sorter_ty as #crate_location::Sorter>::implemented_sorters() }
+                }
+                None => quote! { ::std::vec::Vec::new() },
+            };
+            quote! {
+                #crate_location::ColumnDescriptor {
+                    field: ::std::string::String::from(#field_lit),
+                    column: ::std::string::String::from(#column_lit),
+                    rust_type: ::std::string::String::from(#rust_type_lit),
+                    nullable: #nullable,
+                    id: #id,
+                    filters: #filters_rs,
+                    sorters: #sorters_rs,
+                }
+            }
+        });
+        quote! {
+            fn descriptor() -> #crate_location::TableDescriptor{
+                #crate_location::TableDescriptor {
+                    name: ::std::string::String::from(#name_lit),
+                    schema: ::std::string::String::from(#schema_lit),
+                    table: ::std::string::String::from(#table_lit),
+                    id_columns: ::std::vec![#(::std::string::String::from(#id_columns_lit),)*],
+                    columns: ::std::vec![#(#columns_rs,)*],
+                }
             }
         }
     }
@@ -267,3 +390,127 @@ impl ToTokenWrapperSupport for Table {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use darling::FromDeriveInput;
+    use quote::quote;
+
+    use crate::derive_table::Table;
+    use crate::CrateLocation;
+
+    fn descriptor(input: proc_macro2::TokenStream) -> String {
+        let source = syn::parse2(input).unwrap();
+        let table = Table::from_derive_input(&source).unwrap();
+        table.impl_descriptor(CrateLocation::Outside).to_string()
+    }
+
+    #[test]
+    fn descriptor_default_naming() {
+        let actual = descriptor(quote! {
+            #[derive(Table)]
+            pub struct ProjectOtype {
+                #[column(id, filter, sorter)]
+                pub project_id: Uuid,
+                pub note: Option<String>,
+                #[column(id, filter)]
+                pub otype_id: Uuid,
+            }
+        });
+        let expect = quote! {
+            fn descriptor() -> ::table_traits::TableDescriptor {
+                ::table_traits::TableDescriptor {
+                    name: ::std::string::String::from("ProjectOtype"),
+                    schema: ::std::string::String::from("public"),
+                    table: ::std::string::String::from("project_otype"),
+                    id_columns: ::std::vec![::std::string::String::from("project_id"), ::std::string::String::from("otype_id"),],
+                    columns: ::std::vec![
+                        ::table_traits::ColumnDescriptor {
+                            field: ::std::string::String::from("project_id"),
+                            column: ::std::string::String::from("project_id"),
+                            rust_type: ::std::string::String::from("Uuid"),
+                            nullable: false,
+                            id: true,
+                            filters: < <Uuid as ::table_traits::FilterImpl>::DefaultFilter as ::table_traits::Filter>::implemented_filters(),
+                            sorters: < <Uuid as ::table_traits::SorterImpl>::DefaultSorter as ::table_traits::Sorter>::implemented_sorters(),
+                        },
+                        ::table_traits::ColumnDescriptor {
+                            field: ::std::string::String::from("note"),
+                            column: ::std::string::String::from("note"),
+                            rust_type: ::std::string::String::from("Option<String>"),
+                            nullable: true,
+                            id: false,
+                            filters: ::std::vec::Vec::new(),
+                            sorters: ::std::vec::Vec::new(),
+                        },
+                        ::table_traits::ColumnDescriptor {
+                            field: ::std::string::String::from("otype_id"),
+                            column: ::std::string::String::from("otype_id"),
+                            rust_type: ::std::string::String::from("Uuid"),
+                            nullable: false,
+                            id: true,
+                            filters: < <Uuid as ::table_traits::FilterImpl>::DefaultFilter as ::table_traits::Filter>::implemented_filters(),
+                            sorters: ::std::vec::Vec::new(),
+                        },
+                    ],
+                }
+            }
+        };
+        pretty_assertions::assert_eq!(actual, expect.to_string());
+    }
+
+    #[test]
+    fn descriptor_explicit_naming() {
+        let actual = descriptor(quote! {
+            #[derive(Table)]
+            #[table(schema = "audit", table = "otype_log")]
+            pub struct OtypeLog {
+                #[column(id, filter, sorter)]
+                pub log_id: Uuid,
+                #[column(filter(by = NameFilter))]
+                pub name: String,
+                pub definition: ::core::option::Option<serde_json::Value>,
+            }
+        });
+        let expect = quote! {
+            fn descriptor() -> ::table_traits::TableDescriptor {
+                ::table_traits::TableDescriptor {
+                    name: ::std::string::String::from("OtypeLog"),
+                    schema: ::std::string::String::from("audit"),
+                    table: ::std::string::String::from("otype_log"),
+                    id_columns: ::std::vec![::std::string::String::from("log_id"),],
+                    columns: ::std::vec![
+                        ::table_traits::ColumnDescriptor {
+                            field: ::std::string::String::from("log_id"),
+                            column: ::std::string::String::from("log_id"),
+                            rust_type: ::std::string::String::from("Uuid"),
+                            nullable: false,
+                            id: true,
+                            filters: < <Uuid as ::table_traits::FilterImpl>::DefaultFilter as ::table_traits::Filter>::implemented_filters(),
+                            sorters: < <Uuid as ::table_traits::SorterImpl>::DefaultSorter as ::table_traits::Sorter>::implemented_sorters(),
+                        },
+                        ::table_traits::ColumnDescriptor {
+                            field: ::std::string::String::from("name"),
+                            column: ::std::string::String::from("name"),
+                            rust_type: ::std::string::String::from("String"),
+                            nullable: false,
+                            id: false,
+                            filters: <NameFilter as ::table_traits::Filter>::implemented_filters(),
+                            sorters: ::std::vec::Vec::new(),
+                        },
+                        ::table_traits::ColumnDescriptor {
+                            field: ::std::string::String::from("definition"),
+                            column: ::std::string::String::from("definition"),
+                            rust_type: ::std::string::String::from("::core::option::Option<serde_json::Value>"),
+                            nullable: true,
+                            id: false,
+                            filters: ::std::vec::Vec::new(),
+                            sorters: ::std::vec::Vec::new(),
+                        },
+                    ],
+                }
+            }
+        };
+        pretty_assertions::assert_eq!(actual, expect.to_string());
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{GenericArgument, PathArguments, Type};
+
+use crate::derive_table::{Column, Table};
+use crate::schema_snapshot::SchemaSnapshot;
+
+impl Table {
+    /// validate table, columns, types and nullability against `#[table(check(...))]`
+    ///
+    /// returned tokens make the derive depend on the read files, so changed migrations re-run this check
+    pub(crate) fn check_schema(&self) -> darling::Result<TokenStream> {
+        let Some(check) = &self.check else {
+            return Ok(TokenStream::new());
+        };
+        let root = std::env::var("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap_or_default();
+        let snapshot = match (&check.migrations, &check.snapshot) {
+            (Some(migrations), _) => SchemaSnapshot::from_migrations(&root.join(migrations)),
+            (_, Some(snapshot)) => SchemaSnapshot::from_snapshot(&root.join(snapshot)),
+            (None, None) => unreachable!(),
+        }
+        .map_err(|e| darling::Error::custom(e).with_span(&self.ident))?;
+
+        let name_lit = self.ident.to_string();
+        let schema_lit = self.naming.to_schema_name(&self.schema, "public");
+        let table_lit = self.naming.to_schema_name(&self.table, &name_lit);
+
+        let mut errors = darling::Error::accumulator();
+        match snapshot.table(&schema_lit, &table_lit) {
+            None => {
+                errors.push(darling::Error::custom(format!("table `{}.{}` does not exist in database schema", schema_lit, table_lit)).with_span(&self.ident));
+            }
+            Some(table) => {
+                for column in self.columns() {
+                    let ident = column.ident.as_ref().unwrap();
+                    let column_name = self.naming.to_db_case(&ident.to_string());
+                    let Some(db_column) = table.column(&column_name) else {
+                        errors.push(darling::Error::custom(format!("column `{}` does not exist in `{}.{}`", column_name, schema_lit, table_lit)).with_span(ident));
+                        continue;
+                    };
+                    match (column.is_nullable(), db_column.not_null) {
+                        (true, true) => {
+                            errors.push(darling::Error::custom(format!("column `{}` is NOT NULL, field must not be `Option<_>`", column_name)).with_span(&column.ty));
+                        }
+                        (false, false) => {
+                            errors.push(darling::Error::custom(format!("column `{}` is nullable, field must be `Option<_>`", column_name)).with_span(&column.ty));
+                        }
+                        _ => {}
+                    }
+                    if let Some(expected) = expected_db_types(column) {
+                        if !expected.iter().any(|x| x == &db_column.ty) {
+                            errors.push(darling::Error::custom(format!("column `{}` is `{}` in database, field type expects one of {:?}", column_name, db_column.ty, expected)).with_span(&column.ty));
+                        }
+                    }
+                }
+            }
+        }
+        errors.finish()?;
+
+        let sources = snapshot.sources.iter().map(|x| x.to_string_lossy().to_string());
+        Ok(quote! {
+            const _: () = {
+                #(const _: &[u8] = ::core::include_bytes!(#sources);)*
+            };
+        })
+    }
+}
+
+/// normalized postgres types a rust type can be decoded from, `None` when unknown (custom `sqlx::Type`)
+fn expected_db_types(column: &Column) -> Option<Vec<String>> {
+    let ty = if column.is_nullable() { generic_argument(&column.ty)? } else { &column.ty };
+    rust_to_db_types(ty)
+}
+
+fn rust_to_db_types(ty: &Type) -> Option<Vec<String>> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    let name = segment.ident.to_string();
+    let types: &[&str] = match name.as_str() {
+        "String" => &["text"],
+        "Uuid" => &["uuid"],
+        "bool" => &["bool"],
+        "i8" => &["\"char\""],
+        "i16" => &["int2"],
+        "i32" => &["int4"],
+        "i64" => &["int8"],
+        "f32" => &["float4"],
+        "f64" => &["float8"],
+        "Decimal" | "BigDecimal" => &["numeric"],
+        "Value" | "JsonValue" | "Json" => &["json", "jsonb"],
+        "OffsetDateTime" => &["timestamptz"],
+        "PrimitiveDateTime" | "NaiveDateTime" => &["timestamp"],
+        "Date" | "NaiveDate" => &["date"],
+        "Time" | "NaiveTime" => &["time"],
+        "Vec" => {
+            let inner = generic_argument(ty)?;
+            if let Type::Path(inner_path) = inner {
+                if inner_path.path.is_ident("u8") {
+                    return Some(vec!["bytea".to_string()]);
+                }
+            }
+            return Some(rust_to_db_types(inner)?.into_iter().map(|x| format!("{}[]", x)).collect());
+        }
+        _ => return None,
+    };
+    Some(types.iter().map(|x| x.to_string()).collect())
+}
+
+fn generic_argument(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else { return None };
+    match &path.path.segments.last()?.arguments {
+        PathArguments::AngleBracketed(arguments) => arguments.args.iter().find_map(|x| match x {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use darling::FromDeriveInput;
+    use quote::quote;
+
+    use crate::derive_table::Table;
+
+    fn check(input: proc_macro2::TokenStream) -> Result<(), String> {
+        let source = syn::parse2(input).unwrap();
+        let table = Table::from_derive_input(&source).unwrap();
+        table.check_schema().map(|_| ()).map_err(|e| e.into_iter().map(|x| x.to_string()).collect::<Vec<_>>().join("\n"))
+    }
+
+    #[test]
+    fn check_migrations_ok() {
+        let result = check(quote! {
+            #[derive(Table)]
+            #[table(schema = "public", table = "otype", check(migrations = "../../deploy/migration/files/postgres-migration"))]
+            pub struct Otype {
+                #[column(id)]
+                pub otype_id: Uuid,
+                pub name: String,
+                pub description: Option<String>,
+                pub definition: serde_json::Value,
+            }
+        });
+        pretty_assertions::assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn check_migrations_mismatch() {
+        let result = check(quote! {
+            #[derive(Table)]
+            #[table(schema = "public", table = "otype", check(migrations = "../../deploy/migration/files/postgres-migration"))]
+            pub struct Otype {
+                #[column(id)]
+                pub otype_id: Uuid,
+                pub name: Option<String>,
+                pub description: String,
+                pub definition: i32,
+                pub missing: String,
+            }
+        });
+        pretty_assertions::assert_eq!(
+            result,
+            Err([
+                "column `name` is NOT NULL, field must not be `Option<_>`",
+                "column `description` is nullable, field must be `Option<_>`",
+                "column `definition` is `jsonb` in database, field type expects one of [\"int4\"]",
+                "column `missing` does not exist in `public.otype`",
+            ]
+            .join("\n"))
+        );
+    }
+
+    #[test]
+    fn check_missing_table() {
+        let result = check(quote! {
+            #[derive(Table)]
+            #[table(schema = "public", table = "unknown", check(migrations = "../../deploy/migration/files/postgres-migration"))]
+            pub struct Unknown {
+                #[column(id)]
+                pub id: Uuid,
+            }
+        });
+        pretty_assertions::assert_eq!(result, Err("table `public.unknown` does not exist in database schema".to_string()));
+    }
+}
@@ -10,9 +10,11 @@ mod derive_table;
 mod enum_filter_kind;
 mod enum_sorter_kind;
 mod impl_table;
+mod impl_table_check;
 mod impl_table_naming;
 mod macro_sorter;
 mod macro_table;
+mod schema_snapshot;
 mod syntax;
 mod token_cursor;
 mod token_field;
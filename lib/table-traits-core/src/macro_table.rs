@@ -9,7 +9,13 @@ use crate::utils::ToTokenWrapperSupport;
 pub fn table(crate_location: CrateLocation, input: TokenStream) -> TokenStream {
     let source = syn::parse2(input).unwrap();
     let actual = Table::from_derive_input(&source).unwrap();
-    actual.to_token_stream(crate_location)
+    let mut tokens = actual.to_token_stream(crate_location);
+    // derived items are emitted with the errors, so uses of the type don't fail as well
+    match actual.check_schema() {
+        Ok(check) => tokens.extend(check),
+        Err(errors) => tokens.extend(errors.write_errors()),
+    }
+    tokens
 }
 #[cfg(test)]
 mod test {
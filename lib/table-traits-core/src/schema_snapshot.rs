@@ -0,0 +1,637 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// database schema rebuilt from sql ddl (migrations or `pg_dump --schema-only` snapshot)
+///
+/// only the subset of ddl needed to know columns, types and nullability is understood,
+/// every other statement (extension, index, function, grant ...) is ignored
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SchemaSnapshot {
+    pub(crate) tables: BTreeMap<(String, String), SnapshotTable>,
+    /// every file read to build this snapshot, used to re-run the check when migrations are changed
+    pub(crate) sources: Vec<PathBuf>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SnapshotTable {
+    pub(crate) columns: Vec<SnapshotColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotColumn {
+    pub(crate) name: String,
+    /// normalized type name, see [`normalize_type`]
+    pub(crate) ty: String,
+    pub(crate) not_null: bool,
+}
+
+impl SnapshotTable {
+    pub(crate) fn column(&self, name: &str) -> Option<&SnapshotColumn> {
+        self.columns.iter().find(|x| x.name == name)
+    }
+    fn column_mut(&mut self, name: &str) -> Option<&mut SnapshotColumn> {
+        self.columns.iter_mut().find(|x| x.name == name)
+    }
+}
+
+impl SchemaSnapshot {
+    /// apply every `*.sql` file of the directory, ordered by version (`v0.0.2_x.sql` < `v0.0.10_y.sql`)
+    pub fn from_migrations(dir: &Path) -> Result<Self, String> {
+        let entries = std::fs::read_dir(dir).map_err(|e| format!("can't read migrations directory {}: {}", dir.display(), e))?;
+        let mut files = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().map_or(false, |ext| ext == "sql"))
+            .collect::<Vec<_>>();
+        files.sort_by_key(|path| natural_key(&path.file_name().unwrap_or_default().to_string_lossy()));
+
+        let mut snapshot = Self::default();
+        for file in files {
+            let sql = std::fs::read_to_string(&file).map_err(|e| format!("can't read migration {}: {}", file.display(), e))?;
+            snapshot.apply(&sql);
+            snapshot.sources.push(file);
+        }
+        Ok(snapshot)
+    }
+
+    /// single sql file, typically generated by `pg_dump --schema-only` against migrated database
+    pub fn from_snapshot(file: &Path) -> Result<Self, String> {
+        let sql = std::fs::read_to_string(file).map_err(|e| format!("can't read schema snapshot {}: {}", file.display(), e))?;
+        let mut snapshot = Self::default();
+        snapshot.apply(&sql);
+        snapshot.sources.push(file.to_path_buf());
+        Ok(snapshot)
+    }
+
+    pub fn table(&self, schema: &str, table: &str) -> Option<&SnapshotTable> {
+        self.tables.get(&(schema.to_string(), table.to_string()))
+    }
+
+    pub fn apply(&mut self, sql: &str) {
+        for statement in split_statements(sql) {
+            let tokens = tokenize(&statement);
+            let mut cursor = TokenCursor { tokens: &tokens, index: 0 };
+            if cursor.eat_keywords(&["create", "table"]) || cursor.eat_keywords(&["create", "unlogged", "table"]) {
+                cursor.eat_keywords(&["if", "not", "exists"]);
+                self.apply_create_table(&mut cursor);
+            } else if cursor.eat_keywords(&["alter", "table"]) {
+                cursor.eat_keywords(&["if", "exists"]);
+                cursor.eat_keywords(&["only"]);
+                self.apply_alter_table(&mut cursor);
+            } else if cursor.eat_keywords(&["drop", "table"]) {
+                cursor.eat_keywords(&["if", "exists"]);
+                loop {
+                    let Some(name) = cursor.qualified_name() else { break };
+                    self.tables.remove(&name);
+                    if !cursor.eat(&Token::Comma) {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply_create_table(&mut self, cursor: &mut TokenCursor) {
+        let Some(name) = cursor.qualified_name() else { return };
+        let Some(Token::Group(body)) = cursor.next() else { return };
+        let mut table = SnapshotTable::default();
+        let mut primary_keys = Vec::new();
+        for definition in split_by_comma(body) {
+            let mut cursor = TokenCursor { tokens: &definition, index: 0 };
+            if cursor.eat_keywords(&["constraint"]) {
+                cursor.next();
+            }
+            if cursor.eat_keywords(&["primary", "key"]) {
+                primary_keys.extend(cursor.name_list());
+            } else if cursor.peek_keyword_any(&["foreign", "unique", "check", "exclude", "like"]) {
+                // table constraints, nothing to do with columns
+            } else if let Some(column) = parse_column_definition(&mut cursor) {
+                table.columns.push(column);
+            }
+        }
+        for key in primary_keys {
+            if let Some(column) = table.column_mut(&key) {
+                column.not_null = true;
+            }
+        }
+        self.tables.insert(name, table);
+    }
+
+    fn apply_alter_table(&mut self, cursor: &mut TokenCursor) {
+        let Some(name) = cursor.qualified_name() else { return };
+        if cursor.eat_keywords(&["rename", "to"]) {
+            if let (Some(new_name), Some(table)) = (cursor.ident(), self.tables.remove(&name)) {
+                self.tables.insert((name.0, new_name), table);
+            }
+            return;
+        }
+        let Some(table) = self.tables.get_mut(&name) else { return };
+        for action in split_by_comma(&cursor.tokens[cursor.index..]) {
+            let mut cursor = TokenCursor { tokens: &action, index: 0 };
+            if cursor.eat_keywords(&["add", "constraint"]) {
+                cursor.next();
+                if cursor.eat_keywords(&["primary", "key"]) {
+                    set_not_null(table, cursor.name_list());
+                }
+            } else if cursor.eat_keywords(&["add", "primary", "key"]) {
+                set_not_null(table, cursor.name_list());
+            } else if cursor.eat_keywords(&["add"]) {
+                if cursor.peek_keyword_any(&["foreign", "unique", "check", "exclude"]) {
+                    continue;
+                }
+                cursor.eat_keywords(&["column"]);
+                cursor.eat_keywords(&["if", "not", "exists"]);
+                if let Some(column) = parse_column_definition(&mut cursor) {
+                    if table.column(&column.name).is_none() {
+                        table.columns.push(column);
+                    }
+                }
+            } else if cursor.eat_keywords(&["drop", "constraint"]) {
+                // dropping a primary key keeps columns as they are
+            } else if cursor.eat_keywords(&["drop"]) {
+                cursor.eat_keywords(&["column"]);
+                cursor.eat_keywords(&["if", "exists"]);
+                if let Some(column) = cursor.ident() {
+                    table.columns.retain(|x| x.name != column);
+                }
+            } else if cursor.eat_keywords(&["rename", "constraint"]) {
+                // constraint names are not tracked
+            } else if cursor.eat_keywords(&["rename"]) {
+                cursor.eat_keywords(&["column"]);
+                if let (Some(from), true, Some(to)) = (cursor.ident(), cursor.eat_keywords(&["to"]), cursor.ident()) {
+                    if let Some(column) = table.column_mut(&from) {
+                        column.name = to;
+                    }
+                }
+            } else if cursor.eat_keywords(&["alter"]) {
+                cursor.eat_keywords(&["column"]);
+                let Some(column) = cursor.ident().and_then(|name| table.column_mut(&name)) else { continue };
+                if cursor.eat_keywords(&["set", "not", "null"]) {
+                    column.not_null = true;
+                } else if cursor.eat_keywords(&["drop", "not", "null"]) {
+                    column.not_null = false;
+                } else if cursor.eat_keywords(&["set", "data", "type"]) || cursor.eat_keywords(&["type"]) {
+                    if let Some(ty) = parse_type(&mut cursor) {
+                        column.ty = ty;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn set_not_null(table: &mut SnapshotTable, names: Vec<String>) {
+    for name in names {
+        if let Some(column) = table.column_mut(&name) {
+            column.not_null = true;
+        }
+    }
+}
+
+fn parse_column_definition(cursor: &mut TokenCursor) -> Option<SnapshotColumn> {
+    let name = cursor.ident()?;
+    let ty = parse_type(cursor)?;
+    let mut not_null = false;
+    while let Some(token) = cursor.next() {
+        match token {
+            Token::Word(word) if word.eq_ignore_ascii_case("not") => {
+                if cursor.eat_keywords(&["null"]) {
+                    not_null = true;
+                }
+            }
+            Token::Word(word) if word.eq_ignore_ascii_case("null") => {
+                not_null = false;
+            }
+            Token::Word(word) if word.eq_ignore_ascii_case("default") => {
+                // default expression can be `null`
+                cursor.next();
+            }
+            Token::Word(word) if word.eq_ignore_ascii_case("primary") => {
+                if cursor.eat_keywords(&["key"]) {
+                    not_null = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(SnapshotColumn { name, ty, not_null })
+}
+
+/// read type, skipping modifiers such as `(1024)`, and normalize it
+fn parse_type(cursor: &mut TokenCursor) -> Option<String> {
+    const MULTI_WORD: &[&[&str]] = &[
+        &["character", "varying"],
+        &["double", "precision"],
+        &["bit", "varying"],
+        &["timestamp", "with", "time", "zone"],
+        &["timestamp", "without", "time", "zone"],
+        &["time", "with", "time", "zone"],
+        &["time", "without", "time", "zone"],
+    ];
+    let mut name = None;
+    for words in MULTI_WORD {
+        if cursor.eat_keywords(words) {
+            name = Some(words.join(" "));
+            break;
+        }
+    }
+    let mut name = match name {
+        Some(name) => name,
+        None => {
+            let mut quoted = matches!(cursor.peek(), Some(Token::Quoted(_)));
+            let mut name = cursor.ident()?;
+            // schema qualified type, ex) `public.citext`
+            while cursor.eat(&Token::Dot) {
+                quoted = matches!(cursor.peek(), Some(Token::Quoted(_)));
+                name = cursor.ident()?;
+            }
+            // quoted `"char"` is the single byte type, unquoted `char` is `character(1)`
+            if quoted && name == "char" {
+                name = "\"char\"".to_string();
+            }
+            name
+        }
+    };
+    if let Some(Token::Group(_)) = cursor.peek() {
+        cursor.next();
+    }
+    // `timestamp(3) with time zone`
+    for suffix in [["with", "time", "zone"], ["without", "time", "zone"]] {
+        if cursor.eat_keywords(&suffix) {
+            name = format!("{} {}", name, suffix.join(" "));
+        }
+    }
+    let mut array = 0;
+    while let Some(Token::Bracket) = cursor.peek() {
+        cursor.next();
+        array += 1;
+    }
+    if cursor.eat_keywords(&["array"]) {
+        array += 1;
+    }
+    Some(format!("{}{}", normalize_type(&name), "[]".repeat(array)))
+}
+
+/// collapse postgres type aliases into single canonical name
+pub(crate) fn normalize_type(name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    let normalized = match name.as_str() {
+        "varchar" | "character varying" | "text" | "char" | "character" | "bpchar" | "name" | "citext" => "text",
+        "\"char\"" => "\"char\"",
+        "int2" | "smallint" | "smallserial" | "serial2" => "int2",
+        "int" | "int4" | "integer" | "serial" | "serial4" => "int4",
+        "int8" | "bigint" | "bigserial" | "serial8" => "int8",
+        "float4" | "real" => "float4",
+        "float8" | "double precision" | "float" => "float8",
+        "numeric" | "decimal" => "numeric",
+        "bool" | "boolean" => "bool",
+        "timestamp" | "timestamp without time zone" => "timestamp",
+        "timestamptz" | "timestamp with time zone" => "timestamptz",
+        "time" | "time without time zone" => "time",
+        "timetz" | "time with time zone" => "timetz",
+        other => other,
+    };
+    normalized.to_string()
+}
+
+/// `v0.0.10_a.sql` must be applied after `v0.0.9_b.sql`
+fn natural_key(name: &str) -> Vec<(u64, String)> {
+    let mut result = Vec::new();
+    let mut chars = name.chars().peekable();
+    while chars.peek().is_some() {
+        let mut text = String::new();
+        while let Some(c) = chars.peek().filter(|c| !c.is_ascii_digit()) {
+            text.push(*c);
+            chars.next();
+        }
+        let mut number = String::new();
+        while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+            number.push(*c);
+            chars.next();
+        }
+        result.push((number.parse().unwrap_or(0), text));
+    }
+    result
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Literal,
+    Group(Vec<Token>),
+    Bracket,
+    Comma,
+    Dot,
+    Other,
+}
+
+struct TokenCursor<'a> {
+    tokens: &'a [Token],
+    index: usize,
+}
+
+impl<'a> TokenCursor<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.index)
+    }
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.index);
+        self.index += 1;
+        token
+    }
+    fn eat(&mut self, expected: &Token) -> bool {
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+    /// consume all keywords or nothing
+    fn eat_keywords(&mut self, keywords: &[&str]) -> bool {
+        let matched = keywords.iter().enumerate().all(|(i, keyword)| match self.tokens.get(self.index + i) {
+            Some(Token::Word(word)) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        });
+        if matched {
+            self.index += keywords.len();
+        }
+        matched
+    }
+    fn peek_keyword_any(&self, keywords: &[&str]) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) => keywords.iter().any(|keyword| word.eq_ignore_ascii_case(keyword)),
+            _ => false,
+        }
+    }
+    /// unquoted identifiers are folded to lower case as postgres does
+    fn ident(&mut self) -> Option<String> {
+        match self.peek()? {
+            Token::Word(word) => {
+                self.index += 1;
+                Some(word.to_lowercase())
+            }
+            Token::Quoted(quoted) => {
+                self.index += 1;
+                Some(quoted.clone())
+            }
+            _ => None,
+        }
+    }
+    /// `schema.table` or `table`(public)
+    fn qualified_name(&mut self) -> Option<(String, String)> {
+        let first = self.ident()?;
+        if self.eat(&Token::Dot) {
+            Some((first, self.ident()?))
+        } else {
+            Some(("public".to_string(), first))
+        }
+    }
+    fn name_list(&mut self) -> Vec<String> {
+        match self.next() {
+            Some(Token::Group(group)) => split_by_comma(group)
+                .into_iter()
+                .filter_map(|names| TokenCursor { tokens: &names, index: 0 }.ident())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn split_by_comma(tokens: &[Token]) -> Vec<Vec<Token>> {
+    tokens
+        .split(|token| token == &Token::Comma)
+        .filter(|x| !x.is_empty())
+        .map(|x| x.to_vec())
+        .collect()
+}
+
+/// split on `;` outside of quotes, comments and dollar quoted bodies
+fn split_statements(sql: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let chars = sql.chars().collect::<Vec<_>>();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                current.push('\n');
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                current.push(' ');
+                continue;
+            }
+            '\'' | '"' => {
+                current.push(c);
+                i += 1;
+                while i < chars.len() {
+                    current.push(chars[i]);
+                    if chars[i] == c {
+                        if chars.get(i + 1) == Some(&c) {
+                            current.push(c);
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+            '$' => {
+                // $tag$ ... $tag$
+                let tag_end = chars[i + 1..].iter().position(|x| !(x.is_alphanumeric() || *x == '_')).map(|x| x + i + 1);
+                match tag_end {
+                    Some(tag_end) if chars[tag_end] == '$' => {
+                        let tag = chars[i..=tag_end].iter().collect::<String>();
+                        let rest = chars[tag_end + 1..].iter().collect::<String>();
+                        let body_len = rest.find(&tag).map(|x| rest[..x].chars().count()).unwrap_or(rest.chars().count());
+                        current.push_str("''");
+                        i = tag_end + 1 + body_len + tag.chars().count();
+                        continue;
+                    }
+                    _ => current.push(c),
+                }
+            }
+            ';' => {
+                result.push(std::mem::take(&mut current));
+            }
+            _ => current.push(c),
+        }
+        i += 1;
+    }
+    result.push(current);
+    result.into_iter().filter(|x| !x.trim().is_empty()).collect()
+}
+
+fn tokenize(statement: &str) -> Vec<Token> {
+    fn group(chars: &[char], i: &mut usize, close: Option<char>) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        while *i < chars.len() {
+            let c = chars[*i];
+            if Some(c) == close {
+                *i += 1;
+                return tokens;
+            }
+            match c {
+                c if c.is_whitespace() => *i += 1,
+                '(' => {
+                    *i += 1;
+                    tokens.push(Token::Group(group(chars, i, Some(')'))));
+                }
+                '[' => {
+                    while *i < chars.len() && chars[*i] != ']' {
+                        *i += 1;
+                    }
+                    *i += 1;
+                    tokens.push(Token::Bracket);
+                }
+                ',' => {
+                    *i += 1;
+                    tokens.push(Token::Comma);
+                }
+                '.' => {
+                    *i += 1;
+                    tokens.push(Token::Dot);
+                }
+                '"' | '\'' => {
+                    *i += 1;
+                    let mut text = String::new();
+                    while *i < chars.len() {
+                        if chars[*i] == c {
+                            if chars.get(*i + 1) == Some(&c) {
+                                text.push(c);
+                                *i += 2;
+                                continue;
+                            }
+                            break;
+                        }
+                        text.push(chars[*i]);
+                        *i += 1;
+                    }
+                    *i += 1;
+                    tokens.push(if c == '"' { Token::Quoted(text) } else { Token::Literal });
+                }
+                c if c.is_alphanumeric() || c == '_' => {
+                    let mut word = String::new();
+                    while *i < chars.len() && (chars[*i].is_alphanumeric() || chars[*i] == '_' || chars[*i] == '$') {
+                        word.push(chars[*i]);
+                        *i += 1;
+                    }
+                    tokens.push(Token::Word(word));
+                }
+                _ => {
+                    *i += 1;
+                    tokens.push(Token::Other);
+                }
+            }
+        }
+        tokens
+    }
+    let chars = statement.chars().collect::<Vec<_>>();
+    group(&chars, &mut 0, None)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn column(name: &str, ty: &str, not_null: bool) -> SnapshotColumn {
+        SnapshotColumn { name: name.to_string(), ty: ty.to_string(), not_null }
+    }
+
+    #[test]
+    fn create_table() {
+        let mut snapshot = SchemaSnapshot::default();
+        snapshot.apply(
+            r#"
+            create extension if not exists "uuid-ossp";
+            create table if not exists otype
+            (
+                otype_id    uuid          not null default uuid_generate_v4(),
+                name        varchar(1024) not null,
+                description varchar       null, -- optional
+                definition  jsonb         not null,
+                primary key (otype_id)
+            );
+            CREATE TABLE "Audit"."Log" (
+                "Id" BIGSERIAL PRIMARY KEY,
+                created_at TIMESTAMP(3) WITH TIME ZONE DEFAULT now(),
+                tags TEXT[] NOT NULL DEFAULT '{}',
+                CONSTRAINT log_tags CHECK (array_length(tags, 1) > 0)
+            );
+            "#,
+        );
+        assert_eq!(
+            snapshot.table("public", "otype").unwrap().columns,
+            vec![
+                column("otype_id", "uuid", true),
+                column("name", "text", true),
+                column("description", "text", false),
+                column("definition", "jsonb", true),
+            ]
+        );
+        assert_eq!(
+            snapshot.table("Audit", "Log").unwrap().columns,
+            vec![
+                column("Id", "int8", true),
+                column("created_at", "timestamptz", false),
+                column("tags", "text[]", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn alter_table() {
+        let mut snapshot = SchemaSnapshot::default();
+        snapshot.apply(
+            r#"
+            create table project (project_id uuid, name varchar not null, memo text);
+            alter table project add primary key (project_id);
+            alter table if exists public.project
+                add column if not exists code int,
+                drop column memo,
+                alter column name drop not null,
+                alter column code set not null,
+                alter column code type bigint;
+            alter table project rename column name to title;
+            create function noop() returns trigger as $$ begin; return new; end; $$ language plpgsql;
+            create table dropped (id int);
+            drop table if exists dropped;
+            "#,
+        );
+        assert_eq!(
+            snapshot.table("public", "project").unwrap().columns,
+            vec![column("project_id", "uuid", true), column("title", "text", false), column("code", "int8", true)]
+        );
+        assert!(snapshot.table("public", "dropped").is_none());
+    }
+
+    #[test]
+    fn char_types() {
+        let mut snapshot = SchemaSnapshot::default();
+        snapshot.apply(r#"create table flag (kind "char" not null, code char(2), other pg_catalog."char");"#);
+        assert_eq!(
+            snapshot.table("public", "flag").unwrap().columns,
+            vec![column("kind", "\"char\"", true), column("code", "text", false), column("other", "\"char\"", false)]
+        );
+    }
+
+    #[test]
+    fn migration_order() {
+        let mut names = vec!["v0.0.10_c.sql", "v0.0.2_b.sql", "v0.0.1_init.sql", "v0.0.0_setup.sql"];
+        names.sort_by_key(|x| natural_key(x));
+        assert_eq!(names, vec!["v0.0.0_setup.sql", "v0.0.1_init.sql", "v0.0.2_b.sql", "v0.0.10_c.sql"]);
+    }
+}
@@ -135,145 +135,182 @@ impl SyntaxFilter {
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_eq(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_eq(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Ne => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_ne(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_ne(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Gt => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_gt(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_gt(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Lt => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_lt(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_lt(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Gte => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_gte(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_gte(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Lte => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_lte(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_lte(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::In => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_in(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_in(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::NotIn => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_not_in(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_not_in(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Like => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_like(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_like(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::NLike => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nlike(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nlike(x, target_column.clone(), backend))
+                        .transpose()?
+                        .flatten()
+                },
+                FilterKind::ILike => quote!{
+                    #crate_location::FilterKind::#enum_value => self.#field_ident
+                        .as_ref()
+                        .cloned()
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_ilike(x, target_column.clone(), backend))
+                        .transpose()?
+                        .flatten()
+                },
+                FilterKind::NILike => quote!{
+                    #crate_location::FilterKind::#enum_value => self.#field_ident
+                        .as_ref()
+                        .cloned()
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nilike(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Null => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .filter(|x|*x)
-                        .map(|_| <Self::Target as #crate_location::private::FilterType>::expr_null(target_column.clone()))
+                        .map(|_| <Self::Target as #crate_location::private::FilterType>::expr_null(target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::NotNull => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .filter(|x|*x)
-                        .map(|_| <Self::Target as #crate_location::private::FilterType>::expr_not_null(target_column.clone()))
+                        .map(|_| <Self::Target as #crate_location::private::FilterType>::expr_not_null(target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Between => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_between(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_between(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::NBetween => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nbetween(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nbetween(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Prefix => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_prefix(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_prefix(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::NPrefix => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nprefix(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nprefix(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Suffix => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_suffix(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_suffix(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::NSuffix => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nsuffix(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_nsuffix(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Contain => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_contain(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_contain(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::NContain => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_ncontain(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_ncontain(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
                 FilterKind::Regex => quote!{
                     #crate_location::FilterKind::#enum_value => self.#field_ident
                         .as_ref()
                         .cloned()
-                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_regex(x, target_column.clone()))
+                        .map(|x| <Self::Target as #crate_location::private::FilterType>::expr_regex(x, target_column.clone(), backend))
+                        .transpose()?
                         .flatten()
                 },
             }
@@ -295,12 +332,12 @@ impl SyntaxFilter {
                     #(#impl_filters;)*
                     result
                 }
-                fn build_condition(&self, filter_kind: #crate_location::FilterKind, target_column: impl ::sea_query::IntoColumnRef + ::core::clone::Clone) -> ::core::option::Option<::sea_query::Condition>{
-                    match filter_kind{
+                fn build_condition(&self, filter_kind: #crate_location::FilterKind, target_column: impl ::sea_query::IntoColumnRef + ::core::clone::Clone, backend: &dyn #crate_location::SqlBackend) -> ::core::result::Result<::core::option::Option<::sea_query::Condition>, #crate_location::types::FilterError>{
+                    let expr = match filter_kind{
                         #(#match_arms_build_condition,)*
                         #(#match_arms_not_implemented,)*
-                    }
-                        .map(|x|x.into_condition())
+                    };
+                    ::core::result::Result::Ok(expr.map(|x|x.into_condition()))
                 }
             }
         }
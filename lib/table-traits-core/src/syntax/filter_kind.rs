@@ -17,6 +17,8 @@ mod kw {
     syn::custom_keyword!(not_in);
     syn::custom_keyword!(like);
     syn::custom_keyword!(nlike);
+    syn::custom_keyword!(ilike);
+    syn::custom_keyword!(nilike);
     syn::custom_keyword!(null);
     syn::custom_keyword!(not_null);
     syn::custom_keyword!(between);
@@ -98,6 +100,18 @@ impl Parse for SyntaxFilterKind {
                 inner: FilterKind::NLike,
                 span: input.span(),
             })
+        } else if lookahead.peek(kw::ilike) {
+            input.parse::<kw::ilike>()?;
+            Ok(Self {
+                inner: FilterKind::ILike,
+                span: input.span(),
+            })
+        } else if lookahead.peek(kw::nilike) {
+            input.parse::<kw::nilike>()?;
+            Ok(Self {
+                inner: FilterKind::NILike,
+                span: input.span(),
+            })
         } else if lookahead.peek(kw::null) {
             input.parse::<kw::null>()?;
             Ok(Self {
@@ -212,6 +226,14 @@ impl SyntaxFilterKind {
                 quote! {::core::option::Option<#target_ty>}
             }
 
+            FilterKind::ILike => {
+                quote! {::core::option::Option<#target_ty>}
+            }
+
+            FilterKind::NILike => {
+                quote! {::core::option::Option<#target_ty>}
+            }
+
             FilterKind::Null => {
                 quote! {::core::option::Option<bool>}
             }
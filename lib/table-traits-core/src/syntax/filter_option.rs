@@ -48,6 +48,12 @@ impl SyntaxFilterOption {
         if self.inner.nlike {
             kinds.push(FilterKind::NLike);
         }
+        if self.inner.ilike {
+            kinds.push(FilterKind::ILike);
+        }
+        if self.inner.nilike {
+            kinds.push(FilterKind::NILike);
+        }
         if self.inner.null {
             kinds.push(FilterKind::Null);
         }
@@ -112,6 +118,8 @@ impl Parse for SyntaxFilterOption {
                 FilterKind::NotIn => result.inner.not_in = true,
                 FilterKind::Like => result.inner.like = true,
                 FilterKind::NLike => result.inner.nlike = true,
+                FilterKind::ILike => result.inner.ilike = true,
+                FilterKind::NILike => result.inner.nilike = true,
                 FilterKind::Null => result.inner.null = true,
                 FilterKind::NotNull => result.inner.not_null = true,
                 FilterKind::Between => result.inner.between = true,
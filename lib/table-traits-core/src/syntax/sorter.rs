@@ -93,6 +93,19 @@ impl ToTokenWrapperSupport for SyntaxSorterStatements {
     }
 }
 impl SyntaxSorter {
+    fn impl_sorters(&self, crate_location: CrateLocation) -> Vec<TokenStream> {
+        let mut result = Vec::new();
+        if self.option.inner.asc {
+            result.push(quote! { result.push(#crate_location::SorterKind::Asc) });
+        }
+        if self.option.inner.desc {
+            result.push(quote! { result.push(#crate_location::SorterKind::Desc) });
+        }
+        if self.option.inner.values {
+            result.push(quote! { result.push(#crate_location::SorterKind::Values) });
+        }
+        result
+    }
     fn impl_filter(&self, crate_location: CrateLocation) -> TokenStream {
         if self.option.inner.is_simple_order() {
             self.impl_filter_simple(crate_location)
@@ -103,6 +116,7 @@ impl SyntaxSorter {
     fn impl_filter_simple(&self, crate_location: CrateLocation) -> TokenStream {
         let ident = &self.ident;
         let target_ty = &self.option.target_ty;
+        let impl_sorters = self.impl_sorters(crate_location);
 
         let mut order_rs = TokenStream::new();
         if self.option.inner.asc {
@@ -153,6 +167,12 @@ impl SyntaxSorter {
 
                 type Target = #target_ty;
 
+                fn implemented_sorters() -> ::std::vec::Vec<#crate_location::SorterKind> {
+                    let mut result = ::std::vec::Vec::new();
+                    #(#impl_sorters;)*
+                    result
+                }
+
                 fn build_order(&self, target_column: impl ::sea_query::IntoColumnRef + ::core::clone::Clone)
                     -> (::sea_query::SimpleExpr, ::sea_query::Order, ::core::option::Option<::sea_query::NullOrdering>){
                     match self{
@@ -193,6 +213,7 @@ impl SyntaxSorter {
     fn impl_filter_complex(&self, crate_location: CrateLocation) -> TokenStream {
         let ident = &self.ident;
         let target_ty = &self.option.target_ty;
+        let impl_sorters = self.impl_sorters(crate_location);
 
         let complex_order_rs = self.option.complex_order_rs(crate_location).unwrap();
 
@@ -205,6 +226,12 @@ impl SyntaxSorter {
 
                 type Target = #target_ty;
 
+                fn implemented_sorters() -> ::std::vec::Vec<#crate_location::SorterKind> {
+                    let mut result = ::std::vec::Vec::new();
+                    #(#impl_sorters;)*
+                    result
+                }
+
                 fn build_order(&self, target_column: impl ::sea_query::IntoColumnRef + ::core::clone::Clone)
                     -> (::sea_query::SimpleExpr, ::sea_query::Order, ::core::option::Option<::sea_query::NullOrdering>){
                     match self{
@@ -54,7 +54,7 @@ impl<'a> TokenTableFilter<'a> {
                 let field_ident = self.table.naming.to_field_ident(&column.ident.as_ref().unwrap());
                 quote! {
                     if let ::core::option::Option::Some(value) = &self.#field_ident{
-                        result = result.add(#crate_location::Filter::build_all_condition(value, context.column_ref(#field_type::#enum_value.column_ident())));
+                        result = result.add(#crate_location::Filter::build_all_condition(value, context.column_ref(#field_type::#enum_value.column_ident()), context.backend())?);
                     }
                 }
             })
@@ -75,6 +75,7 @@ impl<'a> TokenTableFilter<'a> {
                 .into_iter()
                 .enumerate()
                 .map(|(i, column)| {
+                    let i = syn::Index::from(i);
                     let field_ident = self
                         .table
                         .naming
@@ -86,6 +87,15 @@ impl<'a> TokenTableFilter<'a> {
                 .collect_vec()
         };
 
+        // rows outside of `RowAuthorizer::list_objects` are filtered out, see `Context::rebac_condition`
+        let rebac_condition = self.table.rebac.as_ref().map(|_| {
+            quote! {
+                if let ::core::option::Option::Some(rebac) = context.rebac_condition() {
+                    result = result.add(rebac);
+                }
+            }
+        });
+
         quote! {
             impl #crate_location::TableFilter for #filter_ident{
 
@@ -103,10 +113,11 @@ impl<'a> TokenTableFilter<'a> {
                         ..Default::default()
                     }
                 }
-                fn to_condition<'a, 'b>(&self, context: &'a mut #crate_location::types::Context<'b, Self::Table>) -> ::sea_query::Condition{
+                fn to_condition<'a, 'b>(&self, context: &'a mut #crate_location::types::Context<'b, Self::Table>) -> ::core::result::Result<::sea_query::Condition, #crate_location::types::FilterError>{
                     let mut result = ::sea_query::Condition::all();
                     #(#conditions)*
-                    result
+                    #rebac_condition
+                    ::core::result::Result::Ok(result)
                 }
             }
         }
@@ -284,7 +284,7 @@ impl<'a> TokenTableSorter<'a> {
                     }
                 }
 
-                fn to_value(&self) -> Value {
+                fn to_value(&self) -> ::async_graphql::Value {
                     ::async_graphql::Value::List(self.0.iter().map(::async_graphql::InputType::to_value).collect())
                 }
 
@@ -0,0 +1,220 @@
+use std::fmt::Debug;
+
+use sea_query::extension::postgres::{PgBinOper, PgExpr};
+use sea_query::{Alias, ColumnRef, Expr, Func, LikeExpr, QueryBuilder, SimpleExpr, TableRef};
+
+use crate::types::FilterError;
+
+/// dialect specific operators, every filter is built through the backend of `Config::backend`
+///
+/// operators without a portable sql form (regex, array, json) return [`FilterError::Unsupported`]
+/// by default, so a filter requested on a backend that can't express it fails instead of silently matching everything
+pub trait SqlBackend: Send + Sync + Debug {
+    fn name(&self) -> &'static str;
+
+    fn query_builder(&self) -> &'static dyn QueryBuilder;
+
+    /// rewrite table reference for dialects without schema support
+    fn table_ref(&self, table: TableRef) -> TableRef {
+        table
+    }
+
+    /// case insensitive `LIKE`, `\` is escape character
+    fn ilike(&self, col: ColumnRef, pattern: String) -> Result<SimpleExpr, FilterError> {
+        Ok(Expr::expr(Func::lower(Expr::col(col))).like(LikeExpr::new(pattern.to_lowercase()).escape('\\')))
+    }
+
+    fn regex(&self, _col: ColumnRef, _pattern: String) -> Result<SimpleExpr, FilterError> {
+        Err(FilterError::Unsupported { operator: "regex", backend: self.name() })
+    }
+
+    /// array column contains every value
+    fn array_contains(&self, _col: ColumnRef, _values: Vec<sea_query::Value>) -> Result<SimpleExpr, FilterError> {
+        Err(FilterError::Unsupported { operator: "array contains", backend: self.name() })
+    }
+
+    /// json column contains the json document
+    fn json_contains(&self, _col: ColumnRef, _value: serde_json::Value) -> Result<SimpleExpr, FilterError> {
+        Err(FilterError::Unsupported { operator: "json contains", backend: self.name() })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostgresBackend;
+
+impl SqlBackend for PostgresBackend {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn query_builder(&self) -> &'static dyn QueryBuilder {
+        &sea_query::PostgresQueryBuilder
+    }
+
+    fn ilike(&self, col: ColumnRef, pattern: String) -> Result<SimpleExpr, FilterError> {
+        Ok(Expr::col(col).ilike(LikeExpr::new(pattern).escape('\\')))
+    }
+
+    fn regex(&self, col: ColumnRef, pattern: String) -> Result<SimpleExpr, FilterError> {
+        Ok(Expr::col(col).binary(PgBinOper::Regex, Expr::value(pattern)))
+    }
+
+    /// `$1 = ANY(col) AND ..` instead of `col @> ARRAY[..]`, sea-query reads `[..]` of a custom expression as quoted identifier and would not bind the values
+    fn array_contains(&self, col: ColumnRef, values: Vec<sea_query::Value>) -> Result<SimpleExpr, FilterError> {
+        Ok(values.into_iter().map(|value| Expr::val(value).eq(Func::cust(Alias::new("ANY")).arg(Expr::col(col.clone())))).reduce(SimpleExpr::and).unwrap_or_else(|| Expr::value(true)))
+    }
+
+    fn json_contains(&self, col: ColumnRef, value: serde_json::Value) -> Result<SimpleExpr, FilterError> {
+        Ok(Expr::col(col).binary(PgBinOper::Contains, Expr::cust_with_exprs("$1::jsonb", [Expr::value(value.to_string())])))
+    }
+}
+
+#[cfg(feature = "backend-sqlite")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SqliteBackend;
+
+#[cfg(feature = "backend-sqlite")]
+impl SqlBackend for SqliteBackend {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn query_builder(&self) -> &'static dyn QueryBuilder {
+        &sea_query::SqliteQueryBuilder
+    }
+
+    /// sqlite has no schema, `schema.table` would be read as `attached_database.table`
+    fn table_ref(&self, table: TableRef) -> TableRef {
+        match table {
+            TableRef::SchemaTable(_, table) => TableRef::Table(table),
+            TableRef::SchemaTableAlias(_, table, alias) => TableRef::TableAlias(table, alias),
+            table => table,
+        }
+    }
+}
+
+#[cfg(feature = "backend-mysql")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MySqlBackend;
+
+#[cfg(feature = "backend-mysql")]
+impl SqlBackend for MySqlBackend {
+    fn name(&self) -> &'static str {
+        "mysql"
+    }
+
+    fn query_builder(&self) -> &'static dyn QueryBuilder {
+        &sea_query::MysqlQueryBuilder
+    }
+
+    fn regex(&self, col: ColumnRef, pattern: String) -> Result<SimpleExpr, FilterError> {
+        Ok(Expr::cust_with_exprs("? REGEXP ?", [Expr::col(col).into(), Expr::value(pattern)]))
+    }
+
+    fn json_contains(&self, col: ColumnRef, value: serde_json::Value) -> Result<SimpleExpr, FilterError> {
+        Ok(Expr::cust_with_exprs("JSON_CONTAINS(?, ?)", [Expr::col(col).into(), Expr::value(value.to_string())]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use sea_query::{IntoIden, Query, SelectStatement};
+
+    use super::*;
+    use crate::private::FilterType;
+
+    fn select(expr: SimpleExpr) -> SelectStatement {
+        Query::select().column(Alias::new("id")).from(Alias::new("otype")).and_where(expr).to_owned()
+    }
+
+    fn sql(backend: &dyn SqlBackend, expr: Result<Option<SimpleExpr>, FilterError>) -> (String, sea_query::Values) {
+        select(expr.unwrap().unwrap()).build_any(backend.query_builder())
+    }
+
+    fn col(name: &str) -> Alias {
+        Alias::new(name)
+    }
+
+    fn unsupported(expr: Result<Option<SimpleExpr>, FilterError>) -> (&'static str, &'static str) {
+        match expr {
+            | Err(FilterError::Unsupported { operator, backend }) => (operator, backend),
+            | Ok(_) => panic!("expected an unsupported filter"),
+        }
+    }
+
+    #[test]
+    fn postgres_operators() {
+        let backend = PostgresBackend;
+
+        let (query, values) = sql(&backend, String::expr_ilike("nam\\_e%".to_string(), col("name"), &backend));
+        assert_eq!(query, r#"SELECT "id" FROM "otype" WHERE "name" ILIKE ($1 ESCAPE E'\\')"#);
+        assert_eq!(values.0, vec![sea_query::Value::from("nam\\_e%")]);
+
+        let (query, _) = sql(&backend, String::expr_nilike("name".to_string(), col("name"), &backend));
+        assert_eq!(query, r#"SELECT "id" FROM "otype" WHERE NOT ("name" ILIKE ($1 ESCAPE E'\\'))"#);
+
+        let (query, values) = sql(&backend, String::expr_regex("^a.*".to_string(), col("name"), &backend));
+        assert_eq!(query, r#"SELECT "id" FROM "otype" WHERE "name" ~ $1"#);
+        assert_eq!(values.0, vec![sea_query::Value::from("^a.*")]);
+
+        let (query, values) = sql(&backend, Vec::<i32>::expr_contain(vec![1, 2], col("tags"), &backend));
+        assert_eq!(query, r#"SELECT "id" FROM "otype" WHERE $1 = ANY("tags") AND $2 = ANY("tags")"#);
+        assert_eq!(values.0, vec![sea_query::Value::from(1), sea_query::Value::from(2)]);
+
+        let (query, _) = sql(&backend, Vec::<i32>::expr_ncontain(vec![1, 2], col("tags"), &backend));
+        assert_eq!(query, r#"SELECT "id" FROM "otype" WHERE NOT ($1 = ANY("tags") AND $2 = ANY("tags"))"#);
+
+        let (query, values) = sql(&backend, serde_json::Value::expr_contain(serde_json::json!({ "a": 1 }), col("definition"), &backend));
+        assert_eq!(query, r#"SELECT "id" FROM "otype" WHERE "definition" @> ($1::jsonb)"#);
+        assert_eq!(values.0, vec![sea_query::Value::from(r#"{"a":1}"#)]);
+    }
+
+    #[test]
+    fn operators_not_implemented_for_a_type_are_unsupported() {
+        let backend = PostgresBackend;
+
+        assert_eq!(unsupported(Vec::<i32>::expr_eq(vec![1], col("tags"), &backend)), ("eq", "postgres"));
+        assert_eq!(unsupported(serde_json::Value::expr_regex(serde_json::Value::Null, col("definition"), &backend)), ("regex", "postgres"));
+    }
+
+    #[cfg(feature = "backend-sqlite")]
+    #[test]
+    fn sqlite_operators() {
+        let backend = SqliteBackend;
+
+        let (query, values) = sql(&backend, String::expr_ilike("Name%".to_string(), col("name"), &backend));
+        assert_eq!(query, r#"SELECT "id" FROM "otype" WHERE LOWER("name") LIKE ? ESCAPE '\'"#);
+        assert_eq!(values.0, vec![sea_query::Value::from("name%")]);
+
+        assert_eq!(unsupported(String::expr_regex("^a".to_string(), col("name"), &backend)), ("regex", "sqlite"));
+        assert_eq!(unsupported(Vec::<i32>::expr_contain(vec![1], col("tags"), &backend)), ("array contains", "sqlite"));
+        assert_eq!(unsupported(serde_json::Value::expr_contain(serde_json::Value::Null, col("definition"), &backend)), ("json contains", "sqlite"));
+    }
+
+    #[cfg(feature = "backend-sqlite")]
+    #[test]
+    fn sqlite_drops_the_schema() {
+        let table = TableRef::SchemaTable(Alias::new("public").into_iden(), Alias::new("otype").into_iden());
+        let (query, _) = Query::select().column(Alias::new("id")).from(SqliteBackend.table_ref(table)).build_any(SqliteBackend.query_builder());
+        assert_eq!(query, r#"SELECT "id" FROM "otype""#);
+    }
+
+    #[cfg(feature = "backend-mysql")]
+    #[test]
+    fn mysql_operators() {
+        let backend = MySqlBackend;
+
+        let (query, _) = sql(&backend, String::expr_ilike("name%".to_string(), col("name"), &backend));
+        assert_eq!(query, r#"SELECT `id` FROM `otype` WHERE LOWER(`name`) LIKE ? ESCAPE '\\'"#);
+
+        let (query, values) = sql(&backend, String::expr_regex("^a".to_string(), col("name"), &backend));
+        assert_eq!(query, "SELECT `id` FROM `otype` WHERE `name` REGEXP ?");
+        assert_eq!(values.0, vec![sea_query::Value::from("^a")]);
+
+        let (query, _) = sql(&backend, serde_json::Value::expr_contain(serde_json::json!([1]), col("definition"), &backend));
+        assert_eq!(query, "SELECT `id` FROM `otype` WHERE JSON_CONTAINS(`definition`, ?)");
+
+        assert_eq!(unsupported(Vec::<i32>::expr_contain(vec![1], col("tags"), &backend)), ("array contains", "mysql"));
+    }
+}
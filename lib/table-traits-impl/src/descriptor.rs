@@ -0,0 +1,70 @@
+use async_graphql::{Object, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{FilterKind, SorterKind, Table};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct TableDescriptor {
+    /// rust type name of derived table
+    pub name: String,
+    pub schema: String,
+    pub table: String,
+    /// db column names of id columns, ordered as `Table::Id`
+    pub id_columns: Vec<String>,
+    pub columns: Vec<ColumnDescriptor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct ColumnDescriptor {
+    /// rust field name
+    pub field: String,
+    /// db column name
+    pub column: String,
+    pub rust_type: String,
+    pub nullable: bool,
+    pub id: bool,
+    pub filters: Vec<FilterKind>,
+    pub sorters: Vec<SorterKind>,
+}
+
+impl TableDescriptor {
+    pub fn column(&self, column: &str) -> Option<&ColumnDescriptor> {
+        self.columns.iter().find(|x| x.column == column)
+    }
+
+    pub fn id_column_descriptors(&self) -> Vec<&ColumnDescriptor> {
+        self.columns.iter().filter(|x| x.id).collect()
+    }
+}
+
+/// opt-in `__tables` query, merge it into query root with `async_graphql::MergedObject`
+///
+/// ```ignore
+/// #[derive(MergedObject, Default)]
+/// pub struct Query(QueryRoot, TablesQuery);
+///
+/// let tables = TablesQuery::default().with::<Otype>().with::<Project>();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TablesQuery {
+    tables: Vec<TableDescriptor>,
+}
+
+impl TablesQuery {
+    pub fn with<T: Table>(mut self) -> Self {
+        self.tables.push(T::descriptor());
+        self
+    }
+
+    pub fn descriptors(&self) -> &[TableDescriptor] {
+        &self.tables
+    }
+}
+
+#[Object]
+impl TablesQuery {
+    #[graphql(name = "__tables")]
+    async fn tables(&self, name: Option<String>) -> Vec<&TableDescriptor> {
+        self.tables.iter().filter(|x| name.as_ref().map_or(true, |name| &x.name == name)).collect()
+    }
+}
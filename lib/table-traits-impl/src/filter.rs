@@ -1,8 +1,12 @@
+use async_graphql::Enum;
 use sea_query::{ColumnRef, Condition, IntoColumnRef};
+use serde::{Deserialize, Serialize};
 
 use crate::private::FilterType;
+use crate::types::FilterError;
+use crate::SqlBackend;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Enum)]
 pub enum FilterKind {
     // equality
     Eq,
@@ -18,6 +22,8 @@ pub enum FilterKind {
     // like
     Like,
     NLike,
+    ILike,
+    NILike,
     // null
     Null,
     NotNull,
@@ -44,15 +50,15 @@ pub trait Filter {
 
     fn implemented_filters() -> Vec<FilterKind>;
 
-    fn build_condition(&self, filter_kind: FilterKind, target_column: impl IntoColumnRef + Clone) -> Option<Condition>;
+    fn build_condition(&self, filter_kind: FilterKind, target_column: impl IntoColumnRef + Clone, backend: &dyn SqlBackend) -> Result<Option<Condition>, FilterError>;
 
-    fn build_all_condition(&self, target_column: impl IntoColumnRef + Clone) -> Condition {
+    fn build_all_condition(&self, target_column: impl IntoColumnRef + Clone, backend: &dyn SqlBackend) -> Result<Condition, FilterError> {
         let activated = Self::implemented_filters();
         let target_column = target_column.into_column_ref();
         let mut condition = Condition::all();
         for kind in activated {
-            condition = condition.add_option(self.build_condition(kind, target_column.clone()));
+            condition = condition.add_option(self.build_condition(kind, target_column.clone(), backend)?);
         }
-        condition
+        Ok(condition)
     }
 }
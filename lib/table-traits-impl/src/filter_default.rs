@@ -11,7 +11,7 @@ filter_crate!(
     pub struct UuidFilter for Uuid impl eq + in { }
 
     #[derive(Debug, Clone, Default, InputObject, Serialize, Deserialize)]
-    pub struct StringFilter for String impl eq + ne + in + not_in + prefix + nprefix + contains + ncontains + suffix + nsuffix + like + nlike + regex {}
+    pub struct StringFilter for String impl eq + ne + in + not_in + prefix + nprefix + contains + ncontains + suffix + nsuffix + like + nlike + ilike + nilike + regex {}
 
     #[derive(Debug, Clone, Default, InputObject, Serialize, Deserialize)]
     pub struct U8Filter for u8 impl eq + ne + gt + gte + lt + lte + between + nbetween { }
@@ -1,10 +1,13 @@
+pub use backend::*;
 pub use cursor::*;
+pub use descriptor::*;
 pub use field::*;
 pub use filter::*;
 pub use filter_default::*;
 pub use filter_impl::*;
 pub use page::*;
 pub use range::*;
+pub use rebac::*;
 pub use sorter::*;
 pub use sorter_default::*;
 pub use sorter_impl::*;
@@ -12,7 +15,9 @@ pub use table::*;
 pub use table_filter::*;
 pub use table_sorter::*;
 
+mod backend;
 mod cursor;
+mod descriptor;
 mod field;
 mod filter;
 mod filter_default;
@@ -21,6 +26,7 @@ mod page;
 pub mod prelude;
 pub mod private;
 mod range;
+mod rebac;
 mod sorter;
 mod sorter_default;
 mod sorter_impl;
@@ -84,6 +84,8 @@ impl<T: Cursor> InputType for PageByCursor<T> {
                         inaccessible: false,
                         tags: Vec::new(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Vec::new(),
                     },
                 );
                 fields.insert(
@@ -97,6 +99,8 @@ impl<T: Cursor> InputType for PageByCursor<T> {
                         inaccessible: false,
                         tags: Vec::new(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Vec::new(),
                     },
                 );
                 fields.insert(
@@ -110,6 +114,8 @@ impl<T: Cursor> InputType for PageByCursor<T> {
                         inaccessible: false,
                         tags: Vec::new(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Vec::new(),
                     },
                 );
                 fields.insert(
@@ -123,6 +129,8 @@ impl<T: Cursor> InputType for PageByCursor<T> {
                         inaccessible: false,
                         tags: Vec::new(),
                         is_secret: false,
+                        deprecation: Default::default(),
+                        directive_invocations: Vec::new(),
                     },
                 );
                 fields
@@ -132,6 +140,7 @@ impl<T: Cursor> InputType for PageByCursor<T> {
             tags: Vec::new(),
             rust_typename: Some(std::any::type_name::<Self>()),
             oneof: false,
+            directive_invocations: Vec::new(),
         })
     }
     fn parse(value: Option<async_graphql::Value>) -> InputValueResult<Self> {
@@ -1,40 +1,94 @@
-use sea_query::extension::postgres::PgBinOper;
-use sea_query::{IntoColumnRef, SimpleExpr};
+use sea_query::{IntoColumnRef, LikeExpr, SimpleExpr};
 
+use crate::types::FilterError;
+use crate::SqlBackend;
+
+/// sql expression of every filter operator on a column of this type
+///
+/// operators a type doesn't override return [`FilterError::Unsupported`], so a filter kind that isn't
+/// implemented for the type fails instead of silently matching every row
 pub trait FilterType: Sized + Clone {
     type Target;
     type TargetContainer;
     type TargetRange;
 
     // equality
-    fn expr_eq(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_ne(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_eq(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "eq", backend: backend.name() })
+    }
+    fn expr_ne(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "ne", backend: backend.name() })
+    }
     // comparison
-    fn expr_gt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_lt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_gte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_lte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_gt(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "gt", backend: backend.name() })
+    }
+    fn expr_lt(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "lt", backend: backend.name() })
+    }
+    fn expr_gte(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "gte", backend: backend.name() })
+    }
+    fn expr_lte(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "lte", backend: backend.name() })
+    }
     // set
-    fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_in(_value: Self::TargetContainer, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "in", backend: backend.name() })
+    }
+    fn expr_not_in(_value: Self::TargetContainer, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not in", backend: backend.name() })
+    }
     // like
-    fn expr_like(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_nlike(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_like(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "like", backend: backend.name() })
+    }
+    fn expr_nlike(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not like", backend: backend.name() })
+    }
+    fn expr_ilike(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "ilike", backend: backend.name() })
+    }
+    fn expr_nilike(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not ilike", backend: backend.name() })
+    }
     // null
-    fn expr_null(col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_not_null(col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_null(_col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "null", backend: backend.name() })
+    }
+    fn expr_not_null(_col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not null", backend: backend.name() })
+    }
     // between
-    fn expr_between(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_nbetween(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_between(_value: Self::TargetRange, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "between", backend: backend.name() })
+    }
+    fn expr_nbetween(_value: Self::TargetRange, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not between", backend: backend.name() })
+    }
     // string matches
-    fn expr_prefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_nprefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_suffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_nsuffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_contain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
-    fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_prefix(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "prefix", backend: backend.name() })
+    }
+    fn expr_nprefix(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not prefix", backend: backend.name() })
+    }
+    fn expr_suffix(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "suffix", backend: backend.name() })
+    }
+    fn expr_nsuffix(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not suffix", backend: backend.name() })
+    }
+    fn expr_contain(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "contains", backend: backend.name() })
+    }
+    fn expr_ncontain(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "not contains", backend: backend.name() })
+    }
     // regex
-    fn expr_regex(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr>;
+    fn expr_regex(_value: Self::Target, _col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Err(FilterError::Unsupported { operator: "regex", backend: backend.name() })
+    }
 }
 
 fn str_like_escape<S: Into<String>>(str: S) -> String {
@@ -93,74 +147,78 @@ macro_rules! impl_default_types {
         impl_default_types!(= $($tt)+);
     };
 
-    (impl eq) => { fn expr_eq(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).eq(::sea_query::Expr::value(value))) }};
-    (impl ! eq) => { fn expr_eq(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl ne) => { fn expr_ne(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).ne(::sea_query::Expr::value(value))) }};
-    (impl ! ne) => { fn expr_ne(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-
-    (impl gt) => { fn expr_gt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).gt(::sea_query::Expr::value(value))) }};
-    (impl ! gt) => { fn expr_gt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl lt) => { fn expr_lt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).lt(::sea_query::Expr::value(value))) }};
-    (impl ! lt) => { fn expr_lt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl gte) => { fn expr_gte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).gte(::sea_query::Expr::value(value))) }};
-    (impl ! gte) => { fn expr_gte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl lte) => { fn expr_lte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).lte(::sea_query::Expr::value(value))) }};
-    (impl ! lte) => { fn expr_lte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-
-    (impl in) => { fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).is_in(value)) }};
-    (impl in $map:expr) => { fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).is_in(value.map($map))) }};
-    (impl ! in_) => { fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl not_in) => { fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).is_not_in(value)) }};
-    (impl not_in $map:expr ) => { fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).is_not_in(value.map($map))) }};
-    (impl ! not_in) => { fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-
-    (impl like) => { fn expr_like(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).like(value)) }};
-    (impl ! like) => { fn expr_like(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl nlike) => { fn expr_nlike(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).not_like(value)) }};
-    (impl ! nlike) => { fn expr_nlike(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-
-
-    (impl null) => { fn expr_null(col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).is_null()) }};
-    (impl ! null) => { fn expr_null(col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl not_null) => { fn expr_not_null(col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).is_not_null()) }};
-    (impl ! not_null) => { fn expr_not_null(col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-
-    (impl between) => { fn expr_between(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).between(::sea_query::Expr::value(value.min), ::sea_query::Expr::value(value.max))) }};
-    (impl ! between) => { fn expr_between(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl not_between) => { fn expr_nbetween(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).not_between(::sea_query::Expr::value(value.min), ::sea_query::Expr::value(value.max))) }};
-    (impl ! not_between) => { fn expr_nbetween(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-
-    (impl prefix) => { fn expr_prefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).like(format!("{}%", str_like_escape(value)))) }};
-    (impl ! prefix) => { fn expr_prefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl nprefix) => { fn expr_nprefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).not_like(format!("{}%", str_like_escape(value)))) }};
-    (impl ! nprefix) => { fn expr_nprefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl suffix) => { fn expr_suffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).like(format!("%{}", str_like_escape(value)))) }};
-    (impl ! suffix) => { fn expr_suffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl nsuffix) => { fn expr_nsuffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).not_like(format!("%{}", str_like_escape(value)))) }};
-    (impl ! nsuffix) => { fn expr_nsuffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl contain) => { fn expr_contain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).like(format!("%{}%", str_like_escape(value)))) }};
-    (impl ! contain) => { fn expr_contain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-    (impl ncontain) => { fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).not_like(format!("%{}%", str_like_escape(value)))) }};
-    (impl ! ncontain) => { fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
-
-    (impl regex) => { fn expr_regex(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { Some(::sea_query::Expr::col(col).binary(PgBinOper::Regex, ::sea_query::Expr::value(value))) }};
-    (impl ! regex) => { fn expr_regex(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> { None }};
+    (impl eq) => { fn expr_eq(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).eq(::sea_query::Expr::value(value)))) }};
+    (impl ! eq) => { fn expr_eq(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl ne) => { fn expr_ne(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).ne(::sea_query::Expr::value(value)))) }};
+    (impl ! ne) => { fn expr_ne(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+
+    (impl gt) => { fn expr_gt(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).gt(::sea_query::Expr::value(value)))) }};
+    (impl ! gt) => { fn expr_gt(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl lt) => { fn expr_lt(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).lt(::sea_query::Expr::value(value)))) }};
+    (impl ! lt) => { fn expr_lt(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl gte) => { fn expr_gte(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).gte(::sea_query::Expr::value(value)))) }};
+    (impl ! gte) => { fn expr_gte(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl lte) => { fn expr_lte(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).lte(::sea_query::Expr::value(value)))) }};
+    (impl ! lte) => { fn expr_lte(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+
+    (impl in) => { fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).is_in(value))) }};
+    (impl in $map:expr) => { fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).is_in(value.map($map)))) }};
+    (impl ! in_) => { fn expr_in(_value: Self::TargetContainer, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl not_in) => { fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).is_not_in(value))) }};
+    (impl not_in $map:expr ) => { fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).is_not_in(value.map($map)))) }};
+    (impl ! not_in) => { fn expr_not_in(_value: Self::TargetContainer, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+
+    (impl like) => { fn expr_like(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).like(value))) }};
+    (impl ! like) => { fn expr_like(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl nlike) => { fn expr_nlike(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).not_like(value))) }};
+    (impl ! nlike) => { fn expr_nlike(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl ilike) => { fn expr_ilike(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { backend.ilike(col.into_column_ref(), value).map(Some) }};
+    (impl ! ilike) => { fn expr_ilike(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl nilike) => { fn expr_nilike(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { backend.ilike(col.into_column_ref(), value).map(|x| Some(x.not())) }};
+    (impl ! nilike) => { fn expr_nilike(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+
+
+    (impl null) => { fn expr_null(col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).is_null())) }};
+    (impl ! null) => { fn expr_null(_col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl not_null) => { fn expr_not_null(col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).is_not_null())) }};
+    (impl ! not_null) => { fn expr_not_null(_col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+
+    (impl between) => { fn expr_between(value: Self::TargetRange, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).between(::sea_query::Expr::value(value.min), ::sea_query::Expr::value(value.max)))) }};
+    (impl ! between) => { fn expr_between(_value: Self::TargetRange, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl not_between) => { fn expr_nbetween(value: Self::TargetRange, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).not_between(::sea_query::Expr::value(value.min), ::sea_query::Expr::value(value.max)))) }};
+    (impl ! not_between) => { fn expr_nbetween(_value: Self::TargetRange, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+
+    (impl prefix) => { fn expr_prefix(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).like(LikeExpr::new(format!("{}%", str_like_escape(value))).escape('\\')))) }};
+    (impl ! prefix) => { fn expr_prefix(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl nprefix) => { fn expr_nprefix(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).not_like(LikeExpr::new(format!("{}%", str_like_escape(value))).escape('\\')))) }};
+    (impl ! nprefix) => { fn expr_nprefix(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl suffix) => { fn expr_suffix(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).like(LikeExpr::new(format!("%{}", str_like_escape(value))).escape('\\')))) }};
+    (impl ! suffix) => { fn expr_suffix(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl nsuffix) => { fn expr_nsuffix(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).not_like(LikeExpr::new(format!("%{}", str_like_escape(value))).escape('\\')))) }};
+    (impl ! nsuffix) => { fn expr_nsuffix(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl contain) => { fn expr_contain(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).like(LikeExpr::new(format!("%{}%", str_like_escape(value))).escape('\\')))) }};
+    (impl ! contain) => { fn expr_contain(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+    (impl ncontain) => { fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(Some(::sea_query::Expr::col(col).not_like(LikeExpr::new(format!("%{}%", str_like_escape(value))).escape('\\')))) }};
+    (impl ! ncontain) => { fn expr_ncontain(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
+
+    (impl regex) => { fn expr_regex(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { backend.regex(col.into_column_ref(), value).map(Some) }};
+    (impl ! regex) => { fn expr_regex(_value: Self::Target, _col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> { Ok(None) }};
 }
 
-impl_default_types!(std::string::String = eq + ne + gt + gte + lt + lte + in + not_in + like + nlike + !null + !not_null + between + not_between + prefix + nprefix + suffix + nsuffix + contain + ncontain + regex);
-impl_default_types!(uuid::Uuid = eq + ne + !gt + !gte + !lt + !lte + in + not_in + !like + !nlike + !null + !not_null + !between + !not_between + !prefix + !nprefix + !suffix + !nsuffix + !contain + !ncontain + !regex);
+impl_default_types!(std::string::String = eq + ne + gt + gte + lt + lte + in + not_in + like + nlike + ilike + nilike + !null + !not_null + between + not_between + prefix + nprefix + suffix + nsuffix + contain + ncontain + regex);
+impl_default_types!(uuid::Uuid = eq + ne + !gt + !gte + !lt + !lte + in + not_in + !like + !nlike + !ilike + !nilike + !null + !not_null + !between + !not_between + !prefix + !nprefix + !suffix + !nsuffix + !contain + !ncontain + !regex);
 //
 impl_default_types!(
     u8,
     u16,
     u32,
-    u64 = eq + ne + gt + gte + lt + lte + !in_ + !not_in + !like + !nlike + !null + !not_null + between + not_between + !prefix + !nprefix + !suffix + !nsuffix + !contain + !ncontain + !regex
+    u64 = eq + ne + gt + gte + lt + lte + !in_ + !not_in + !like + !nlike + !ilike + !nilike + !null + !not_null + between + not_between + !prefix + !nprefix + !suffix + !nsuffix + !contain + !ncontain + !regex
 );
 impl_default_types!(
     i8,
     i16,
     i32,
-    i64 = eq + ne + gt + gte + lt + lte + !in_ + !not_in + !like + !nlike + !null + !not_null + between + not_between + !prefix + !nprefix + !suffix + !nsuffix + !contain + !ncontain + !regex
+    i64 = eq + ne + gt + gte + lt + lte + !in_ + !not_in + !like + !nlike + !ilike + !nilike + !null + !not_null + between + not_between + !prefix + !nprefix + !suffix + !nsuffix + !contain + !ncontain + !regex
 );
 
 impl<T: FilterType> FilterType for Option<T> {
@@ -168,144 +226,186 @@ impl<T: FilterType> FilterType for Option<T> {
     type TargetContainer = Option<T::TargetContainer>;
     type TargetRange = Option<T::TargetRange>;
 
-    fn expr_eq(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_eq(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_eq(value, col),
-            None => None,
+            Some(value) => T::expr_eq(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_ne(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_ne(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_ne(value, col),
-            None => None,
+            Some(value) => T::expr_ne(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_gt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_gt(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_gt(value, col),
-            None => None,
+            Some(value) => T::expr_gt(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_lt(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_lt(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_lt(value, col),
-            None => None,
+            Some(value) => T::expr_lt(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_gte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_gte(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_gte(value, col),
-            None => None,
+            Some(value) => T::expr_gte(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_lte(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_lte(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_lte(value, col),
-            None => None,
+            Some(value) => T::expr_lte(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_in(value: Self::TargetContainer, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_in(value, col),
-            None => None,
+            Some(value) => T::expr_in(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_not_in(value: Self::TargetContainer, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_not_in(value, col),
-            None => None,
+            Some(value) => T::expr_not_in(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_like(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_like(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_like(value, col),
-            None => None,
+            Some(value) => T::expr_like(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_nlike(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_nlike(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_nlike(value, col),
-            None => None,
+            Some(value) => T::expr_nlike(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_null(col: impl IntoColumnRef) -> Option<SimpleExpr> {
-        Some(sea_query::Expr::col(col).is_null())
+    fn expr_ilike(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        match value {
+            Some(value) => T::expr_ilike(value, col, backend),
+            None => Ok(None),
+        }
     }
 
-    fn expr_not_null(col: impl IntoColumnRef) -> Option<SimpleExpr> {
-        Some(sea_query::Expr::col(col).is_not_null())
+    fn expr_nilike(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        match value {
+            Some(value) => T::expr_nilike(value, col, backend),
+            None => Ok(None),
+        }
     }
 
-    fn expr_between(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_null(col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Ok(Some(sea_query::Expr::col(col).is_null()))
+    }
+
+    fn expr_not_null(col: impl IntoColumnRef, _backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        Ok(Some(sea_query::Expr::col(col).is_not_null()))
+    }
+
+    fn expr_between(value: Self::TargetRange, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_between(value, col),
-            _ => None,
+            Some(value) => T::expr_between(value, col, backend),
+            _ => Ok(None),
         }
     }
 
-    fn expr_nbetween(value: Self::TargetRange, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_nbetween(value: Self::TargetRange, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_nbetween(value, col),
-            _ => None,
+            Some(value) => T::expr_nbetween(value, col, backend),
+            _ => Ok(None),
         }
     }
 
-    fn expr_prefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_prefix(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_prefix(value, col),
-            None => None,
+            Some(value) => T::expr_prefix(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_nprefix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_nprefix(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_nprefix(value, col),
-            None => None,
+            Some(value) => T::expr_nprefix(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_suffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_suffix(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_suffix(value, col),
-            None => None,
+            Some(value) => T::expr_suffix(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_nsuffix(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_nsuffix(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_nsuffix(value, col),
-            None => None,
+            Some(value) => T::expr_nsuffix(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_contain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_contain(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_contain(value, col),
-            None => None,
+            Some(value) => T::expr_contain(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_ncontain(value, col),
-            None => None,
+            Some(value) => T::expr_ncontain(value, col, backend),
+            None => Ok(None),
         }
     }
 
-    fn expr_regex(value: Self::Target, col: impl IntoColumnRef) -> Option<SimpleExpr> {
+    fn expr_regex(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
         match value {
-            Some(value) => T::expr_regex(value, col),
-            None => None,
+            Some(value) => T::expr_regex(value, col, backend),
+            None => Ok(None),
         }
     }
 }
+
+impl<T: Clone + Into<sea_query::Value>> FilterType for Vec<T> {
+    type Target = Self;
+    type TargetContainer = Vec<Self>;
+    type TargetRange = crate::Range<Self>;
+
+    fn expr_contain(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        backend.array_contains(col.into_column_ref(), value.into_iter().map(Into::into).collect()).map(Some)
+    }
+
+    fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        backend.array_contains(col.into_column_ref(), value.into_iter().map(Into::into).collect()).map(|x| Some(x.not()))
+    }
+}
+
+impl FilterType for serde_json::Value {
+    type Target = Self;
+    type TargetContainer = Vec<Self>;
+    type TargetRange = crate::Range<Self>;
+
+    fn expr_contain(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        backend.json_contains(col.into_column_ref(), value).map(Some)
+    }
+
+    fn expr_ncontain(value: Self::Target, col: impl IntoColumnRef, backend: &dyn SqlBackend) -> Result<Option<SimpleExpr>, FilterError> {
+        backend.json_contains(col.into_column_ref(), value).map(|x| Some(x.not()))
+    }
+}
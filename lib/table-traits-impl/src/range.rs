@@ -32,6 +32,8 @@ impl<T: InputType> InputType for Range<T> {
                             inaccessible: false,
                             tags: Vec::new(),
                             is_secret: false,
+                            deprecation: Default::default(),
+                            directive_invocations: Vec::new(),
                         },
                     );
                     fields.insert(
@@ -45,6 +47,8 @@ impl<T: InputType> InputType for Range<T> {
                             inaccessible: false,
                             tags: Vec::new(),
                             is_secret: false,
+                            deprecation: Default::default(),
+                            directive_invocations: Vec::new(),
                         },
                     );
                     fields
@@ -54,6 +58,7 @@ impl<T: InputType> InputType for Range<T> {
                 inaccessible: false,
                 rust_typename: Some(::std::any::type_name::<Self>()),
                 oneof: false,
+                directive_invocations: Vec::new(),
             },
         )
     }
@@ -0,0 +1,45 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+
+use crate::types::RowAuthError;
+
+/// `#[table(rebac(otype = "otype", rel = "viewer"))]`, a row is visible only when the principal has `rel` on `otype:<id>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableRebac {
+    pub otype: &'static str,
+    pub rel: &'static str,
+}
+
+/// object ids visible to the principal of `RowAuthorizer`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowedObjects {
+    /// every visible object id, injected into the filter as `id IN (..)`
+    Ids(Vec<String>),
+    /// too many objects to inline into the query, fetched rows are checked one by one
+    TooMany,
+}
+
+/// resolves rebac of derived tables for the principal of current request
+///
+/// ```ignore
+/// let req = req.data(OpenFgaRowAuthorizer::new(openfga.clone(), user.clone()));
+/// SqlxExecutor::new(pool).with_authorizer(ctx.data_unchecked::<OpenFgaRowAuthorizer>()).connection(page, filter, sorter).await?
+/// ```
+#[async_trait]
+pub trait RowAuthorizer: Send + Sync {
+    async fn list_objects(&self, otype: &str, rel: &str) -> Result<AllowedObjects, RowAuthError>;
+
+    /// visible subset of `oids`, used when `list_objects` returns `AllowedObjects::TooMany`
+    async fn check_objects(&self, otype: &str, rel: &str, oids: Vec<String>) -> Result<HashSet<String>, RowAuthError>;
+}
+
+/// row access of `Context`, resolved by `Context::authorize`
+#[derive(Debug, Clone, Default)]
+pub enum RowAccess {
+    /// table without rebac, or authorization not resolved yet
+    #[default]
+    Unresolved,
+    Ids(Vec<sea_query::Value>),
+    CheckRows,
+}
@@ -1,10 +1,21 @@
+use async_graphql::Enum;
 use sea_query::{Condition, IntoColumnRef, NullOrdering, Order, SimpleExpr};
+use serde::{Deserialize, Serialize};
 
 use crate::FieldMetadata;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Enum)]
+pub enum SorterKind {
+    Asc,
+    Desc,
+    Values,
+}
+
 pub trait Sorter {
     type Target;
 
+    fn implemented_sorters() -> Vec<SorterKind>;
+
     fn build_order(
         &self,
         target_column: impl IntoColumnRef + Clone,
@@ -6,7 +6,7 @@ use sea_query::{Alias, ColumnRef, IntoIden, NullOrdering, SelectStatement, Table
 
 use crate::page::{Page, PageByCursor};
 use crate::types::{Config, Parameter, State, DEFAULT_CONFIG};
-use crate::{Cursor, Field, FieldGetter, FieldMetadata, TableFilter, TableSorter};
+use crate::{Cursor, Field, FieldGetter, FieldMetadata, TableDescriptor, TableFilter, TableRebac, TableSorter};
 
 #[async_trait]
 pub trait Table: Sized + FieldMetadata + FieldGetter {
@@ -22,5 +22,26 @@ pub trait Table: Sized + FieldMetadata + FieldGetter {
 
     fn id_fields() -> Vec<Self::Field>;
 
+    fn id(&self) -> Self::Id;
+
+    /// values of id columns, ordered as `id_fields`
+    fn id_values(id: &Self::Id) -> Vec<sea_query::Value>;
+
     fn to_cursor(&self) -> Self::Cursor;
+
+    fn descriptor() -> TableDescriptor;
+
+    /// row level authorization, `None` when every row is visible
+    fn rebac() -> Option<TableRebac> {
+        None
+    }
+
+    /// object id of row in rebac tuples, `otype:<oid>`
+    fn rebac_oid(_id: &Self::Id) -> Option<String> {
+        None
+    }
+
+    fn rebac_id(_oid: &str) -> Option<Self::Id> {
+        None
+    }
 }
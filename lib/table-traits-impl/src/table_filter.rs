@@ -3,7 +3,7 @@ use std::hash::Hash;
 use async_trait::async_trait;
 use sea_query::Condition;
 
-use crate::types::{Context, State};
+use crate::types::{Context, FilterError, State};
 use crate::{FieldMetadata, Table};
 
 #[async_trait]
@@ -14,5 +14,5 @@ pub trait TableFilter: Sized {
 
     fn by_id(id: <Self::Table as Table>::Id) -> Self;
 
-    fn to_condition<'a, 'b>(&self, state: &'a mut Context<'b, Self::Table>) -> Condition;
+    fn to_condition<'a, 'b>(&self, state: &'a mut Context<'b, Self::Table>) -> Result<Condition, FilterError>;
 }
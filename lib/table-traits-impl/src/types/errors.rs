@@ -18,9 +18,26 @@ pub enum PageCursorVariantError {
     BothFirstAndLast,
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum FilterError {
+    #[error("{operator} filter is not supported by {backend} backend")]
+    Unsupported { operator: &'static str, backend: &'static str },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RowAuthError {
+    #[error("table `{table}` has rebac, but no row authorizer is given")]
+    MissingAuthorizer { table: String },
+    #[error("row authorization failed, {0}")]
+    Authorizer(String),
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ConnectionError {
     #[error(transparent)]
     PageCursorVariantError(#[from] PageCursorVariantError),
-    
+    #[error(transparent)]
+    FilterError(#[from] FilterError),
+    #[error(transparent)]
+    RowAuthError(#[from] RowAuthError),
 }
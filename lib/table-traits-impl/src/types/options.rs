@@ -1,8 +1,11 @@
 use derivative::Derivative;
 
+use crate::SqlBackend;
+
 pub const DEFAULT_CONFIG: Config = Config {
     sql: OptionsSql { default_limit: 1000, max_limit: None },
     processing: OptionsProcessing { chunking: Some(Chunking::Auto) },
+    backend: Backend::Postgres,
 };
 #[derive(Debug, Clone, Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Default)]
@@ -10,6 +13,32 @@ pub struct Config {
     pub sql: OptionsSql,
 
     pub processing: OptionsProcessing,
+
+    #[serde(default)]
+    pub backend: Backend,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Default)]
+pub enum Backend {
+    #[derivative(Default)]
+    Postgres,
+    #[cfg(feature = "backend-sqlite")]
+    Sqlite,
+    #[cfg(feature = "backend-mysql")]
+    MySql,
+}
+
+impl Backend {
+    pub fn dialect(&self) -> &'static dyn SqlBackend {
+        match self {
+            Backend::Postgres => &crate::PostgresBackend,
+            #[cfg(feature = "backend-sqlite")]
+            Backend::Sqlite => &crate::SqliteBackend,
+            #[cfg(feature = "backend-mysql")]
+            Backend::MySql => &crate::MySqlBackend,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Derivative, serde::Serialize, serde::Deserialize)]
@@ -1,20 +1,28 @@
 use crate::page::Page;
-use crate::Table;
-use sea_query::{Alias, ColumnRef, IntoColumnRef, IntoIden, IntoTableRef, TableRef};
+use crate::{AllowedObjects, Field, RowAccess, RowAuthorizer, SqlBackend, Table};
+use sea_query::{Alias, ColumnRef, Expr, IntoColumnRef, IntoIden, IntoTableRef, SelectStatement, SimpleExpr, TableRef, Values};
 use std::marker::PhantomData;
 
-use crate::types::Config;
+use crate::types::{Config, RowAuthError};
 
 impl Config {
     pub fn context_as<T: Table, A: Into<Option<String>>>(&self, alias: A) -> Context<T> {
         let alias = alias.into().map(|alias| Alias::new(alias));
-        Context { config: self, alias, _phantom: PhantomData }
+        Context {
+            config: self,
+            alias,
+            authorizer: None,
+            access: RowAccess::Unresolved,
+            _phantom: PhantomData,
+        }
     }
     pub fn execute<T: Table>(&self, page: Page<T::Cursor>, filter: T::Filter, sorter: T::Sorter) -> State<T> {
         State {
             context: Context {
                 config: self,
                 alias: None,
+                authorizer: None,
+                access: RowAccess::Unresolved,
                 _phantom: PhantomData,
             },
             parameter: Parameter { page, filter, sorter },
@@ -30,12 +38,70 @@ pub struct State<'a, T: Table> {
 pub struct Context<'a, T: Table> {
     pub config: &'a Config,
     pub alias: Option<Alias>,
+    pub authorizer: Option<&'a dyn RowAuthorizer>,
+    pub access: RowAccess,
     _phantom: PhantomData<fn() -> T>,
 }
 
 impl<'a, T: Table> Context<'a, T> {
+    pub fn with_authorizer(self, authorizer: &'a dyn RowAuthorizer) -> Self {
+        Context { authorizer: Some(authorizer), ..self }
+    }
+    /// resolves `access` by `RowAuthorizer::list_objects`, tables without rebac are left unresolved
+    pub async fn authorize(&mut self) -> Result<(), RowAuthError> {
+        let Some(rebac) = T::rebac() else {
+            return Ok(());
+        };
+        let Some(authorizer) = self.authorizer else {
+            return Err(RowAuthError::MissingAuthorizer { table: T::descriptor().name });
+        };
+        self.access = match authorizer.list_objects(rebac.otype, rebac.rel).await? {
+            AllowedObjects::Ids(oids) => RowAccess::Ids(oids.iter().filter_map(|oid| T::rebac_id(oid)).flat_map(|id| T::id_values(&id)).collect()),
+            AllowedObjects::TooMany => RowAccess::CheckRows,
+        };
+        Ok(())
+    }
+    /// rows visible by `RowAuthorizer::check_objects` when `access` is `RowAccess::CheckRows`, otherwise rows are already filtered by `rebac_condition`
+    pub async fn check_rows(&self, rows: Vec<T>) -> Result<Vec<T>, RowAuthError> {
+        if !matches!(self.access, RowAccess::CheckRows) {
+            return Ok(rows);
+        }
+        self.check_loaded(rows).await
+    }
+    /// rows visible by `RowAuthorizer::check_objects` whatever `access` is, for rows loaded by id without `rebac_condition`
+    pub async fn check_loaded(&self, rows: Vec<T>) -> Result<Vec<T>, RowAuthError> {
+        let Some(rebac) = T::rebac() else {
+            return Ok(rows);
+        };
+        let Some(authorizer) = self.authorizer else {
+            return Err(RowAuthError::MissingAuthorizer { table: T::descriptor().name });
+        };
+        if rows.is_empty() {
+            return Ok(rows);
+        }
+        let oids = rows.iter().filter_map(|x| T::rebac_oid(&x.id())).collect::<Vec<_>>();
+        let allowed = authorizer.check_objects(rebac.otype, rebac.rel, oids).await?;
+        Ok(rows.into_iter().filter(|x| T::rebac_oid(&x.id()).is_some_and(|oid| allowed.contains(&oid))).collect())
+    }
+    /// `id IN (..)` of visible rows, fails closed when rebac of table is not authorized yet
+    pub fn rebac_condition(&self) -> Option<SimpleExpr> {
+        T::rebac()?;
+        let id = T::id_fields().into_iter().next()?;
+        let id = Expr::col(self.column_ref(id.column_ident()));
+        match &self.access {
+            RowAccess::Unresolved => Some(id.is_in(Vec::<sea_query::Value>::new())),
+            RowAccess::Ids(ids) => Some(id.is_in(ids.clone())),
+            RowAccess::CheckRows => None,
+        }
+    }
+    pub fn backend(&self) -> &'static dyn SqlBackend {
+        self.config.backend.dialect()
+    }
+    pub fn build(&self, query: &SelectStatement) -> (String, Values) {
+        query.build_any(self.backend().query_builder())
+    }
     pub fn table_ref(&self, table: impl IntoTableRef) -> TableRef {
-        let table = table.into_table_ref();
+        let table = self.backend().table_ref(table.into_table_ref());
         match self.alias.clone() {
             Some(alias) => table.alias(alias),
             None => table,
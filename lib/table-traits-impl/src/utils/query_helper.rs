@@ -7,7 +7,7 @@ use sea_query::{ColumnRef, NullOrdering, SelectStatement, TableRef};
 
 use crate::page::Page;
 use crate::types::{ConnectionError, Context};
-use crate::{CursorWrap, Field, PageByCursor, PageByCursorVariant, Table, TableFilter, TableSorter};
+use crate::{CursorWrap, Field, PageByCursor, PageByCursorVariant, RowAccess, Table, TableFilter, TableSorter};
 
 //
 impl<'a, T: Table + OutputType> Context<'a, T> {
@@ -31,14 +31,15 @@ impl<'a, T: Table + OutputType> Context<'a, T> {
     //     }));
     //     result
     // }
-    pub async fn connection<CR0, C0, FR0, F0, FR1, F1>(&mut self, page: Option<Page<T::Cursor>>, filter: T::Filter, sorter: T::Sorter, c0: C0, f0: F0, f1: F1) -> Result<Connection<CursorWrap<T::Cursor>, T>, ConnectionError>
+    pub async fn connection<E, CR0, C0, FR0, F0, FR1, F1>(&mut self, page: Option<Page<T::Cursor>>, filter: T::Filter, sorter: T::Sorter, c0: C0, f0: F0, f1: F1) -> Result<Connection<CursorWrap<T::Cursor>, T>, E>
     where
-        CR0: Future<Output = Option<Vec<T::Id>>>,
+        E: From<ConnectionError>,
+        CR0: Future<Output = Result<Option<Vec<T::Id>>, E>>,
         C0: FnOnce(&T::Cursor) -> CR0,
-        FR0: Future<Output = Vec<T::Id>>,
-        F0: FnOnce(SelectStatement) -> FR0,
-        FR1: Future<Output = HashMap<T::Id, T>>,
-        F1: FnOnce(Vec<T::Id>) -> FR1,
+        FR0: Future<Output = Result<Vec<T::Id>, E>>,
+        F0: FnMut(SelectStatement) -> FR0,
+        FR1: Future<Output = Result<HashMap<T::Id, T>, E>>,
+        F1: FnMut(Vec<T::Id>) -> FR1,
     {
         let page = page.unwrap_or_default();
         match page {
@@ -46,26 +47,54 @@ impl<'a, T: Table + OutputType> Context<'a, T> {
         }
     }
 
-    pub async fn connection_by_cursor<CR0, C0, FR0, F0, FR1, F1>(&mut self, page: PageByCursor<T::Cursor>, filter: T::Filter, sorter: T::Sorter, c0: C0, f0: F0, f1: F1) -> Result<Connection<CursorWrap<T::Cursor>, T>, ConnectionError>
+    pub async fn connection_by_cursor<E, CR0, C0, FR0, F0, FR1, F1>(&mut self, page: PageByCursor<T::Cursor>, filter: T::Filter, sorter: T::Sorter, c0: C0, mut f0: F0, mut f1: F1) -> Result<Connection<CursorWrap<T::Cursor>, T>, E>
     where
-        CR0: Future<Output = Option<Vec<T::Id>>>,
+        E: From<ConnectionError>,
+        CR0: Future<Output = Result<Option<Vec<T::Id>>, E>>,
         C0: FnOnce(&T::Cursor) -> CR0,
-        FR0: Future<Output = Vec<T::Id>>,
-        F0: FnOnce(SelectStatement) -> FR0,
-        FR1: Future<Output = HashMap<T::Id, T>>,
-        F1: FnOnce(Vec<T::Id>) -> FR1,
+        FR0: Future<Output = Result<Vec<T::Id>, E>>,
+        F0: FnMut(SelectStatement) -> FR0,
+        FR1: Future<Output = Result<HashMap<T::Id, T>, E>>,
+        F1: FnMut(Vec<T::Id>) -> FR1,
     {
-        let page_variant = page.into_variant()?;
+        let mut page_variant = page.into_variant().map_err(ConnectionError::from)?;
+        self.authorize().await.map_err(ConnectionError::from)?;
+        let check_rows = matches!(self.access, RowAccess::CheckRows);
+        let limit = match &page_variant {
+            PageByCursorVariant::After { limit, .. } | PageByCursorVariant::Before { limit, .. } | PageByCursorVariant::Between { limit, .. } | PageByCursorVariant::BetweenRev { limit, .. } => limit.map(|x| x as usize),
+        };
 
-        let result_query = self.prepare_query(self.prepare_all_column_refs(), filter, sorter, page_variant);
+        let mut nodes = Vec::new();
+        loop {
+            let result_query = self.prepare_query(self.prepare_id_column_refs(), &filter, &sorter, page_variant.clone())?;
 
-        let ids = f0(result_query).await;
-        let mut data = f1(ids.clone()).await;
-        let mut result = Connection::new(false, false);
-        for id in ids {
-            let node = data.remove(&id).unwrap();
-            result.edges.push(async_graphql::connection::Edge::new(CursorWrap::new(node.to_cursor()), node));
+            let ids = f0(result_query).await?;
+            let exhausted = limit.map_or(true, |limit| ids.len() < limit);
+            let mut data = f1(ids.clone()).await?;
+            // row deleted between id query and entity load
+            let mut rows = ids.into_iter().filter_map(|id| data.remove(&id)).collect::<Vec<_>>();
+            let Some(last) = rows.last().map(|x| x.to_cursor()) else {
+                break;
+            };
+            if check_rows {
+                rows = self.check_rows(rows).await.map_err(ConnectionError::from)?;
+            }
+            nodes.extend(rows);
+            // rows rejected by per row check are refilled from the next page, so `limit` stays honest
+            if !check_rows || exhausted || limit.is_some_and(|limit| nodes.len() >= limit) {
+                break;
+            }
+            page_variant = match page_variant {
+                PageByCursorVariant::After { limit, .. } => PageByCursorVariant::After { after: Some(CursorWrap::new(last)), limit },
+                _ => break,
+            };
         }
+        if let Some(limit) = limit {
+            nodes.truncate(limit);
+        }
+
+        let mut result = Connection::new(false, false);
+        result.edges.extend(nodes.into_iter().map(|node| async_graphql::connection::Edge::new(CursorWrap::new(node.to_cursor()), node)));
         Ok(result)
     }
 
@@ -75,13 +104,13 @@ impl<'a, T: Table + OutputType> Context<'a, T> {
     fn prepare_all_column_refs(&self) -> Vec<ColumnRef> {
         T::fields().into_iter().map(|x| self.column_ref(x.column_ident())).collect::<Vec<ColumnRef>>()
     }
-    fn prepare_query(&mut self, id_column_refs: Vec<ColumnRef>, filter: <T as Table>::Filter, sorter: <T as Table>::Sorter, page_variant: PageByCursorVariant<<T as Table>::Cursor>) -> SelectStatement {
+    fn prepare_query(&mut self, id_column_refs: Vec<ColumnRef>, filter: &<T as Table>::Filter, sorter: &<T as Table>::Sorter, page_variant: PageByCursorVariant<<T as Table>::Cursor>) -> Result<SelectStatement, ConnectionError> {
         let table_ref = self.table_ref(T::table());
 
         let mut query = SelectStatement::new();
         query.columns(id_column_refs);
         query.from(table_ref);
-        query.cond_where(filter.to_condition(self));
+        query.cond_where(filter.to_condition(self)?);
         match page_variant {
             PageByCursorVariant::After { after: None, limit } | PageByCursorVariant::Before { before: None, limit } => {
                 if let Some(limit) = limit {
@@ -107,6 +136,6 @@ impl<'a, T: Table + OutputType> Context<'a, T> {
         for (expr, order, nulls) in sorter.to_order(self) {
             query.order_by_expr_with_nulls(expr, order, nulls.unwrap_or(NullOrdering::First));
         }
-        query
+        Ok(query)
     }
 }
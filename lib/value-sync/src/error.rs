@@ -0,0 +1,41 @@
+use crate::Operation;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("malformed change event of value: {0}")]
+    Malformed(String),
+
+    #[error("{0:?} of value {1} has no old row, value needs `replica identity full`")]
+    MissingBefore(Operation, String),
+
+    #[error("truncate of value can not be synced, tuples have to be reconciled")]
+    Truncated,
+
+    #[error("OpenFGA: {0}")]
+    OpenFGA(#[from] openfga_client::Error),
+
+    #[error("event source: {0}")]
+    Source(String),
+
+    #[error("dead letter: {0}")]
+    DeadLetter(String),
+
+    #[cfg(feature = "kafka")]
+    #[error("Kafka: {0}")]
+    Kafka(#[from] rdkafka::error::KafkaError),
+}
+
+impl SyncError {
+    /// retried until it succeeds, events are dead-lettered on any other error
+    pub fn is_transient(&self) -> bool {
+        match self {
+            | SyncError::Malformed(_) | SyncError::MissingBefore(..) | SyncError::Truncated => false,
+            | SyncError::OpenFGA(openfga_client::Error::WriteFailed(status, ..)) => status.as_u16() == 429 || status.is_server_error(),
+            | SyncError::OpenFGA(openfga_client::Error::Reqwest(_) | openfga_client::Error::CircuitOpen(_)) => true,
+            | SyncError::OpenFGA(_) => false,
+            | SyncError::Source(_) | SyncError::DeadLetter(_) => true,
+            #[cfg(feature = "kafka")]
+            | SyncError::Kafka(_) => true,
+        }
+    }
+}
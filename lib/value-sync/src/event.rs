@@ -0,0 +1,189 @@
+use openfga_client::Tuple;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::SyncError;
+
+/// row of `value`, a relationship tuple `user_name relation object_name` of `source_id`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ValueRow {
+    pub value_id: String,
+    pub source_id: String,
+    pub user_name: String,
+    pub relation: String,
+    pub object_name: String,
+}
+
+impl ValueRow {
+    pub fn tuple(&self) -> Tuple {
+        Tuple::new(self.user_name.clone(), self.relation.clone(), self.object_name.clone())
+    }
+}
+
+/// `op` of debezium change event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Operation {
+    #[serde(rename = "c")]
+    Create,
+    /// row of the initial snapshot
+    #[serde(rename = "r")]
+    Read,
+    #[serde(rename = "u")]
+    Update,
+    #[serde(rename = "d")]
+    Delete,
+    #[serde(rename = "t")]
+    Truncate,
+}
+
+/// debezium change event of `value`
+///
+/// `before` is `None` unless the table has `replica identity full`, debezium sends only the primary key of the old row otherwise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueChange {
+    pub op: Operation,
+    /// `value_id` of the changed row, changes of the same key are applied in order
+    pub key: Option<String>,
+    pub before: Option<ValueRow>,
+    pub after: Option<ValueRow>,
+    /// log sequence number of the transaction, replays of an applied change are skipped by it
+    pub lsn: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    op: Operation,
+    before: Option<Value>,
+    after: Option<Value>,
+    source: Option<SourceInfo>,
+}
+
+#[derive(Deserialize)]
+struct SourceInfo {
+    lsn: Option<u64>,
+}
+
+impl ValueChange {
+    /// change event of a record value, `None` for the tombstone following a delete
+    ///
+    /// the json converter may wrap the event as `{"schema": .., "payload": {..}}`, both forms are accepted
+    pub fn parse(payload: Option<&[u8]>) -> Result<Option<ValueChange>, SyncError> {
+        let Some(payload) = payload else {
+            return Ok(None);
+        };
+        let value = serde_json::from_slice::<Value>(payload).map_err(|err| SyncError::Malformed(err.to_string()))?;
+        let value = match value {
+            | Value::Null => return Ok(None),
+            | Value::Object(mut object) if object.contains_key("schema") => match object.remove("payload") {
+                | Some(Value::Null) | None => return Ok(None),
+                | Some(payload) => payload,
+            },
+            | value => value,
+        };
+        let envelope = serde_json::from_value::<Envelope>(value).map_err(|err| SyncError::Malformed(err.to_string()))?;
+        let key = [&envelope.after, &envelope.before].into_iter().flatten().find_map(|row| row.get("value_id")?.as_str().map(str::to_string));
+        Ok(Some(ValueChange {
+            op: envelope.op,
+            key,
+            before: envelope.before.and_then(full_row),
+            after: match envelope.after {
+                | Some(after) => Some(serde_json::from_value(after).map_err(|err| SyncError::Malformed(err.to_string()))?),
+                | None => None,
+            },
+            lsn: envelope.source.and_then(|x| x.lsn),
+        }))
+    }
+
+    /// tuples to write and to delete, in this order, for OpenFGA to reflect the change
+    pub fn tuple_changes(&self) -> Result<(Vec<Tuple>, Vec<Tuple>), SyncError> {
+        let key = || self.key.clone().unwrap_or_default();
+        match self.op {
+            | Operation::Create | Operation::Read => match &self.after {
+                | Some(after) => Ok((vec![after.tuple()], Vec::new())),
+                | None => Err(SyncError::Malformed(format!("{:?} of value {} without after", self.op, key()))),
+            },
+            | Operation::Update => match (&self.before, &self.after) {
+                | (Some(before), Some(after)) if before.tuple() == after.tuple() => Ok((Vec::new(), Vec::new())),
+                | (Some(before), Some(after)) => Ok((vec![after.tuple()], vec![before.tuple()])),
+                | (None, _) => Err(SyncError::MissingBefore(self.op, key())),
+                | (_, None) => Err(SyncError::Malformed(format!("{:?} of value {} without after", self.op, key()))),
+            },
+            | Operation::Delete => match &self.before {
+                | Some(before) => Ok((Vec::new(), vec![before.tuple()])),
+                | None => Err(SyncError::MissingBefore(self.op, key())),
+            },
+            | Operation::Truncate => Err(SyncError::Truncated),
+        }
+    }
+}
+
+/// old row of an update or delete, `None` when debezium sent only its primary key
+///
+/// not null columns missing from the old row are sent as empty strings
+fn full_row(row: Value) -> Option<ValueRow> {
+    serde_json::from_value::<ValueRow>(row).ok().filter(|x| !x.user_name.is_empty() && !x.relation.is_empty() && !x.object_name.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn row(value_id: &str, user_name: &str, object_name: &str) -> Value {
+        json!({"value_id": value_id, "source_id": "s", "user_name": user_name, "relation": "viewer", "object_name": object_name})
+    }
+
+    fn parse(value: Value) -> Result<Option<ValueChange>, SyncError> {
+        ValueChange::parse(Some(value.to_string().as_bytes()))
+    }
+
+    #[test]
+    fn envelope() {
+        let event = json!({"before": null, "after": row("v1", "user:a", "doc:1"), "source": {"lsn": 42}, "op": "c"});
+        let plain = parse(event.clone()).unwrap().unwrap();
+        let wrapped = parse(json!({"schema": {"type": "struct"}, "payload": event})).unwrap().unwrap();
+        assert_eq!(plain, wrapped);
+        assert_eq!(plain.key.as_deref(), Some("v1"));
+        assert_eq!(plain.lsn, Some(42));
+        assert_eq!(plain.tuple_changes().unwrap(), (vec![Tuple::new("user:a".into(), "viewer".into(), "doc:1".into())], Vec::new()));
+    }
+
+    #[test]
+    fn tombstone() {
+        assert_eq!(ValueChange::parse(None).unwrap(), None);
+        assert_eq!(parse(json!({"schema": {"type": "struct"}, "payload": null})).unwrap(), None);
+    }
+
+    #[test]
+    fn update() {
+        let moved = parse(json!({"before": row("v1", "user:a", "doc:1"), "after": row("v1", "user:a", "doc:2"), "op": "u"})).unwrap().unwrap();
+        assert_eq!(
+            moved.tuple_changes().unwrap(),
+            (vec![Tuple::new("user:a".into(), "viewer".into(), "doc:2".into())], vec![Tuple::new("user:a".into(), "viewer".into(), "doc:1".into())])
+        );
+
+        let mut source_changed = row("v1", "user:a", "doc:1");
+        source_changed["source_id"] = json!("t");
+        let unchanged = parse(json!({"before": row("v1", "user:a", "doc:1"), "after": source_changed, "op": "u"})).unwrap().unwrap();
+        assert_eq!(unchanged.tuple_changes().unwrap(), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn missing_before() {
+        let deleted = parse(json!({"before": {"value_id": "v1", "source_id": "", "user_name": "", "relation": "", "object_name": ""}, "after": null, "op": "d"}))
+            .unwrap()
+            .unwrap();
+        assert_eq!(deleted.key.as_deref(), Some("v1"));
+        assert_eq!(deleted.before, None);
+        assert!(matches!(deleted.tuple_changes(), Err(SyncError::MissingBefore(Operation::Delete, key)) if key == "v1"));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(matches!(ValueChange::parse(Some(b"{")), Err(SyncError::Malformed(_))));
+        assert!(matches!(parse(json!({"after": {"value_id": "v1"}, "op": "c"})), Err(SyncError::Malformed(_))));
+        assert!(matches!(parse(json!({"op": "t"})).unwrap().unwrap().tuple_changes(), Err(SyncError::Truncated)));
+    }
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::error::KafkaResult;
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{Message, Offset, TopicPartitionList};
+
+use crate::{DeadLetterSink, EventSource, Record, SyncError};
+
+/// consumes the debezium postgres connector topic of `value`, `{topic_prefix}.public.value`
+///
+/// instances share `group_id` and split partitions, offsets are committed once records are applied
+pub struct KafkaSource {
+    consumer: StreamConsumer,
+    topic: String,
+}
+
+impl KafkaSource {
+    pub fn new(brokers: &str, group_id: &str, topic: &str) -> KafkaResult<Self> {
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("group.id", group_id)
+            .set("auto.offset.reset", "earliest")
+            .set("enable.auto.commit", "false")
+            .create()?;
+        consumer.subscribe(&[topic])?;
+        Ok(KafkaSource { consumer, topic: topic.to_string() })
+    }
+}
+
+#[async_trait]
+impl EventSource for KafkaSource {
+    async fn next(&mut self) -> Option<Result<Record, SyncError>> {
+        Some(match self.consumer.recv().await {
+            | Ok(message) => Ok(Record {
+                partition: message.partition(),
+                offset: message.offset(),
+                key: message.key().map(<[u8]>::to_vec),
+                payload: message.payload().map(<[u8]>::to_vec),
+            }),
+            | Err(err) => Err(err.into()),
+        })
+    }
+
+    async fn commit(&mut self, partition: i32, offset: i64) -> Result<(), SyncError> {
+        let mut offsets = TopicPartitionList::new();
+        // committed offset is the next record to consume
+        offsets.add_partition_offset(&self.topic, partition, Offset::Offset(offset + 1))?;
+        self.consumer.commit(&offsets, CommitMode::Async)?;
+        Ok(())
+    }
+}
+
+/// publishes records which can not be applied to `topic` as they were, with headers `error`, `partition` and `offset`
+pub struct KafkaDeadLetter {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl KafkaDeadLetter {
+    pub fn new(brokers: &str, topic: &str) -> KafkaResult<Self> {
+        let producer = ClientConfig::new().set("bootstrap.servers", brokers).create()?;
+        Ok(KafkaDeadLetter { producer, topic: topic.to_string() })
+    }
+}
+
+#[async_trait]
+impl DeadLetterSink for KafkaDeadLetter {
+    async fn send(&self, record: &Record, error: &SyncError) -> Result<(), SyncError> {
+        let error = error.to_string();
+        let partition = record.partition.to_string();
+        let offset = record.offset.to_string();
+        let headers = OwnedHeaders::new()
+            .insert(Header { key: "error", value: Some(&error) })
+            .insert(Header { key: "partition", value: Some(&partition) })
+            .insert(Header { key: "offset", value: Some(&offset) });
+        let mut dead_letter = FutureRecord::<[u8], [u8]>::to(&self.topic).headers(headers);
+        if let Some(key) = &record.key {
+            dead_letter = dead_letter.key(key);
+        }
+        if let Some(payload) = &record.payload {
+            dead_letter = dead_letter.payload(payload);
+        }
+        self.producer.send(dead_letter, Duration::from_secs(5)).await.map_err(|(err, _)| SyncError::Kafka(err))?;
+        Ok(())
+    }
+}
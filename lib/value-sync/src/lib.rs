@@ -0,0 +1,19 @@
+//! sync of `value` rows to OpenFGA tuples from debezium change events
+//!
+//! `value` needs `replica identity full`, updates and deletes carry the old tuple only then
+
+pub use error::*;
+pub use event::*;
+#[cfg(feature = "kafka")]
+pub use kafka::*;
+pub use pipeline::*;
+pub use source::*;
+pub use store::*;
+
+mod error;
+mod event;
+#[cfg(feature = "kafka")]
+mod kafka;
+mod pipeline;
+mod source;
+mod store;
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use moka::sync::Cache;
+use openfga_client::Tuple;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use crate::{DeadLetterSink, EventSource, Record, SyncError, TupleStore, ValueChange};
+
+/// change event of a record, `None` for tombstones
+type ParsedChange = Result<Option<ValueChange>, SyncError>;
+
+#[derive(Debug, Clone)]
+pub struct SyncConfig {
+    /// records of a batch, keys of a batch are applied concurrently
+    pub max_batch: usize,
+    /// wait for more records after the first record of a batch
+    pub linger: Duration,
+    /// backoff of a transient failure, doubled up to `max_backoff` while it keeps failing
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// keys whose last applied lsn is kept to skip redelivered changes
+    pub remembered_keys: u64,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        SyncConfig {
+            max_batch: 256,
+            linger: Duration::from_millis(100),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            remembered_keys: 100_000,
+        }
+    }
+}
+
+/// applies debezium change events of `value` to OpenFGA tuples
+///
+/// - changes of a key or a tuple are applied in the order of the source, unrelated changes of a batch concurrently
+/// - transient failures are retried until they succeed, the batch is held meanwhile so nothing overtakes
+/// - changes which can never apply (malformed, invalid tuple, missing old row) are sent to the dead letter sink
+/// - offsets are committed once every record of the batch is applied or dead-lettered, redelivery is harmless
+///   as `TupleStore` is idempotent and replays older than the last applied change of a key are skipped
+pub struct ValueSync {
+    store: Arc<dyn TupleStore>,
+    dead_letter: Arc<dyn DeadLetterSink>,
+    config: SyncConfig,
+    /// `value_id -> lsn` of the last applied change
+    applied: Cache<String, u64>,
+}
+
+impl ValueSync {
+    pub fn new<T: TupleStore + 'static, D: DeadLetterSink + 'static>(store: T, dead_letter: D) -> Self {
+        let config = SyncConfig::default();
+        ValueSync {
+            store: Arc::new(store),
+            dead_letter: Arc::new(dead_letter),
+            applied: Cache::new(config.remembered_keys),
+            config,
+        }
+    }
+
+    pub fn with_config(self, config: SyncConfig) -> Self {
+        ValueSync {
+            applied: Cache::new(config.remembered_keys),
+            config,
+            ..self
+        }
+    }
+
+    /// applies records of `source` until it is exhausted, a kafka source is never exhausted
+    pub async fn run<S: EventSource>(&self, source: &mut S) {
+        loop {
+            let (batch, exhausted) = self.next_batch(source).await;
+            let mut committing = HashMap::new();
+            for record in &batch {
+                let offset = committing.entry(record.partition).or_insert(record.offset);
+                *offset = record.offset.max(*offset);
+            }
+            self.apply_batch(batch).await;
+            for (partition, offset) in committing {
+                // uncommitted records are delivered again, after a rebalance for example
+                if let Err(err) = source.commit(partition, offset).await {
+                    warn!(%err, partition, offset, "commit of value changes failed");
+                }
+            }
+            if exhausted {
+                return;
+            }
+        }
+    }
+
+    /// records until the batch is full or lingered, and whether the source is exhausted
+    async fn next_batch<S: EventSource>(&self, source: &mut S) -> (Vec<Record>, bool) {
+        let mut batch = Vec::new();
+        let mut deadline = None;
+        while batch.len() < self.config.max_batch.max(1) {
+            let next = match deadline {
+                | Some(deadline) => match tokio::time::timeout_at(deadline, source.next()).await {
+                    | Ok(next) => next,
+                    | Err(_) => break,
+                },
+                | None => source.next().await,
+            };
+            match next {
+                | Some(Ok(record)) => {
+                    deadline.get_or_insert_with(|| Instant::now() + self.config.linger);
+                    batch.push(record);
+                }
+                | Some(Err(err)) => {
+                    warn!(%err, "receiving value changes failed");
+                    tokio::time::sleep(self.config.initial_backoff).await;
+                }
+                | None => return (batch, true),
+            }
+        }
+        (batch, false)
+    }
+
+    /// groups records sharing a key or a tuple, groups are applied concurrently and records of a group in order
+    ///
+    /// a tuple may move between rows, `v1` updated from `A` to `B` and `v2` created as `A` must not run concurrently,
+    /// the delete of `A` would overtake the write of `A` otherwise
+    async fn apply_batch(&self, batch: Vec<Record>) {
+        let mut keys: HashMap<GroupKey, usize> = HashMap::new();
+        let mut groups: Vec<Vec<(usize, Record, ParsedChange)>> = Vec::new();
+        for (position, record) in batch.into_iter().enumerate() {
+            let change = ValueChange::parse(record.payload.as_deref());
+            let record_keys = group_keys(&record, &change);
+            let mut found = record_keys.iter().filter_map(|x| keys.get(x).copied()).collect::<Vec<_>>();
+            found.sort_unstable();
+            found.dedup();
+            let index = match found.first() {
+                | Some(index) => *index,
+                | None => {
+                    groups.push(Vec::new());
+                    groups.len() - 1
+                }
+            };
+            // the record joins groups which were independent so far
+            for other in found.iter().skip(1) {
+                let merged = std::mem::take(&mut groups[*other]);
+                groups[index].extend(merged);
+                keys.values_mut().filter(|x| **x == *other).for_each(|x| *x = index);
+            }
+            for key in record_keys {
+                keys.insert(key, index);
+            }
+            groups[index].push((position, record, change));
+        }
+        futures::future::join_all(groups.into_iter().filter(|x| !x.is_empty()).map(|mut group| async move {
+            group.sort_by_key(|(position, ..)| *position);
+            for (_, record, change) in group {
+                self.apply_record(&record, change).await;
+            }
+        }))
+        .await;
+    }
+
+    /// applies a record or dead-letters it, returns once either succeeded
+    async fn apply_record(&self, record: &Record, change: ParsedChange) {
+        let change = match change {
+            | Ok(Some(change)) => change,
+            | Ok(None) => return,
+            | Err(err) => return self.send_dead_letter(record, err).await,
+        };
+        if let (Some(key), Some(lsn)) = (&change.key, change.lsn) {
+            if self.applied.get(key).is_some_and(|applied| lsn <= applied) {
+                debug!(key, lsn, "skipping replayed value change");
+                return;
+            }
+        }
+        let (writes, deletes) = match change.tuple_changes() {
+            | Ok(tuples) => tuples,
+            | Err(err) => return self.send_dead_letter(record, err).await,
+        };
+        let mut attempt = 0;
+        loop {
+            match self.store.apply(writes.clone(), deletes.clone()).await.map_err(SyncError::from) {
+                | Ok(()) => break,
+                | Err(err) if err.is_transient() => {
+                    let backoff = self.backoff(attempt);
+                    warn!(%err, key = ?change.key, "applying value change failed, retry {} in {:?}", attempt + 1, backoff);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                | Err(err) => return self.send_dead_letter(record, err).await,
+            }
+        }
+        if let (Some(key), Some(lsn)) = (change.key, change.lsn) {
+            self.applied.insert(key, lsn);
+        }
+    }
+
+    async fn send_dead_letter(&self, record: &Record, error: SyncError) {
+        warn!(%error, partition = record.partition, offset = record.offset, "dead-lettering value change");
+        let mut attempt = 0;
+        while let Err(err) = self.dead_letter.send(record, &error).await {
+            let backoff = self.backoff(attempt);
+            warn!(%err, "dead-lettering value change failed, retry {} in {:?}", attempt + 1, backoff);
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.config.initial_backoff.saturating_mul(2u32.saturating_pow(attempt)).min(self.config.max_backoff)
+    }
+}
+
+/// what orders records of a batch, their `value_id` and the tuples they write or delete
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum GroupKey {
+    Value(String),
+    Tuple(Tuple),
+}
+
+fn group_keys(record: &Record, change: &ParsedChange) -> Vec<GroupKey> {
+    // tombstones and malformed records are keyed by the record key, debezium keys by `value_id` as well
+    let key = match change {
+        | Ok(Some(ValueChange { key: Some(key), .. })) => key.clone(),
+        | _ => record.key.as_deref().map(|x| String::from_utf8_lossy(x).into_owned()).unwrap_or_else(|| format!("{}:{}", record.partition, record.offset)),
+    };
+    let mut keys = vec![GroupKey::Value(key)];
+    if let Ok(Some(change)) = change {
+        keys.extend([&change.before, &change.after].into_iter().flatten().map(|row| GroupKey::Tuple(row.tuple())));
+    }
+    keys
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use openfga_client::{ContextualTuple, InMemoryEngine, Tuple};
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{ChannelDeadLetter, ChannelSource, FileSource};
+
+    const MODEL: &str = r#"model
+  schema 1.1
+
+type user
+
+type doc
+  relations
+    define viewer: [user]
+"#;
+
+    fn row(value_id: &str, user_name: &str, relation: &str, object_name: &str) -> Value {
+        json!({"value_id": value_id, "source_id": "s", "user_name": user_name, "relation": relation, "object_name": object_name})
+    }
+
+    fn event(op: &str, before: Option<Value>, after: Option<Value>, lsn: u64) -> Value {
+        json!({"before": before, "after": after, "source": {"lsn": lsn}, "op": op})
+    }
+
+    fn record(offset: i64, payload: &Value) -> Record {
+        Record {
+            partition: 0,
+            offset,
+            key: None,
+            payload: Some(payload.to_string().into_bytes()),
+        }
+    }
+
+    fn tuples(engine: &InMemoryEngine) -> Vec<String> {
+        let mut tuples = engine.tuples().iter().map(|x| x.tuple()).map(|x| format!("{} {} {}", x.user, x.relation, x.object)).collect::<Vec<_>>();
+        tuples.sort();
+        tuples
+    }
+
+    fn config() -> SyncConfig {
+        SyncConfig {
+            initial_backoff: Duration::from_millis(1),
+            ..SyncConfig::default()
+        }
+    }
+
+    async fn sync<T: TupleStore + 'static>(store: T, events: Vec<Value>) -> (Option<i64>, Vec<String>) {
+        let (sender, receiver) = mpsc::channel(events.len().max(1));
+        for (offset, event) in events.iter().enumerate() {
+            sender.send(record(offset as i64, event)).await.unwrap();
+        }
+        drop(sender);
+        let (dead_sender, mut dead_receiver) = mpsc::unbounded_channel();
+        let mut source = ChannelSource::new(receiver);
+        ValueSync::new(store, ChannelDeadLetter::new(dead_sender)).with_config(config()).run(&mut source).await;
+        let mut dead = Vec::new();
+        while let Ok((record, message)) = dead_receiver.try_recv() {
+            dead.push(format!("{} {}", record.offset, message));
+        }
+        (source.committed(0), dead)
+    }
+
+    #[tokio::test]
+    async fn apply_in_order() {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        let events = vec![
+            event("r", None, Some(row("v1", "user:a", "viewer", "doc:1")), 10),
+            event("c", None, Some(row("v2", "user:b", "viewer", "doc:1")), 11),
+            event("u", Some(row("v2", "user:b", "viewer", "doc:1")), Some(row("v2", "user:b", "viewer", "doc:2")), 12),
+            event("d", Some(row("v1", "user:a", "viewer", "doc:1")), None, 13),
+            json!(null),
+            event("c", None, Some(row("v1", "user:a", "viewer", "doc:3")), 14),
+        ];
+        let (committed, dead) = sync(engine.clone(), events).await;
+        assert_eq!(committed, Some(5));
+        assert_eq!(dead, Vec::<String>::new());
+        assert_eq!(tuples(&engine), vec!["user:a viewer doc:3", "user:b viewer doc:2"]);
+    }
+
+    #[tokio::test]
+    async fn dead_letter() {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        let events = vec![
+            json!({"op": "c", "after": {"value_id": "v1"}}),
+            event("c", None, Some(row("v2", "user:a", "editor", "doc:1")), 11),
+            event("d", Some(json!({"value_id": "v3", "source_id": "", "user_name": "", "relation": "", "object_name": ""})), None, 12),
+            event("c", None, Some(row("v4", "user:a", "viewer", "doc:1")), 13),
+        ];
+        let (committed, dead) = sync(engine.clone(), events).await;
+        assert_eq!(committed, Some(3));
+        assert_eq!(dead.iter().map(|x| x.split_once(' ').unwrap().0).collect::<Vec<_>>(), vec!["0", "1", "2"]);
+        assert!(dead[2].ends_with("Delete of value v3 has no old row, value needs `replica identity full`"));
+        assert_eq!(tuples(&engine), vec!["user:a viewer doc:1"]);
+    }
+
+    #[tokio::test]
+    async fn skip_replay() {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        let created = event("c", None, Some(row("v1", "user:a", "viewer", "doc:1")), 10);
+        let deleted = event("d", Some(row("v1", "user:a", "viewer", "doc:1")), None, 11);
+        let (_, dead) = sync(engine.clone(), vec![created.clone(), deleted.clone(), created, deleted]).await;
+        assert_eq!(dead, Vec::<String>::new());
+        assert_eq!(tuples(&engine), Vec::<String>::new());
+    }
+
+    /// fails the first `failures` applies with an open circuit
+    struct Flaky {
+        engine: InMemoryEngine,
+        failures: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TupleStore for Flaky {
+        async fn apply(&self, writes: Vec<Tuple>, deletes: Vec<Tuple>) -> Result<(), openfga_client::Error> {
+            if self.failures.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |x| x.checked_sub(1)).is_ok() {
+                return Err(openfga_client::Error::CircuitOpen(Duration::ZERO));
+            }
+            self.engine.apply(writes, deletes).await
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_transient() {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        let events = vec![
+            event("c", None, Some(row("v1", "user:a", "viewer", "doc:1")), 10),
+            event("u", Some(row("v1", "user:a", "viewer", "doc:1")), Some(row("v1", "user:b", "viewer", "doc:1")), 11),
+        ];
+        let (committed, dead) = sync(
+            Flaky {
+                engine: engine.clone(),
+                failures: AtomicUsize::new(3),
+            },
+            events,
+        )
+        .await;
+        assert_eq!(committed, Some(1));
+        assert_eq!(dead, Vec::<String>::new());
+        assert_eq!(tuples(&engine), vec!["user:b viewer doc:1"]);
+    }
+
+    /// delays applies deleting tuples, a concurrent write of the same tuple would finish first
+    struct SlowDelete(InMemoryEngine);
+
+    #[async_trait]
+    impl TupleStore for SlowDelete {
+        async fn apply(&self, writes: Vec<Tuple>, deletes: Vec<Tuple>) -> Result<(), openfga_client::Error> {
+            if !deletes.is_empty() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            self.0.apply(writes, deletes).await
+        }
+    }
+
+    #[tokio::test]
+    async fn order_by_tuple() {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        let events = vec![
+            event("c", None, Some(row("v1", "user:a", "viewer", "doc:1")), 10),
+            // `v2` takes the tuple `v1` moved away from, the write must wait for the slow delete
+            event("u", Some(row("v1", "user:a", "viewer", "doc:1")), Some(row("v1", "user:b", "viewer", "doc:1")), 11),
+            event("c", None, Some(row("v2", "user:a", "viewer", "doc:1")), 12),
+            event("c", None, Some(row("v3", "user:c", "viewer", "doc:1")), 13),
+        ];
+        let (_, dead) = sync(SlowDelete(engine.clone()), events).await;
+        assert_eq!(dead, Vec::<String>::new());
+        assert_eq!(tuples(&engine), vec!["user:a viewer doc:1", "user:b viewer doc:1", "user:c viewer doc:1"]);
+    }
+
+    #[tokio::test]
+    async fn file_source() {
+        let engine = InMemoryEngine::from_dsl(MODEL).unwrap();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let lines = [event("c", None, Some(row("v1", "user:a", "viewer", "doc:1")), 10).to_string(), String::new(), "null".to_string()];
+        std::fs::write(file.path(), lines.join("\n")).unwrap();
+        let (dead_sender, _dead_receiver) = mpsc::unbounded_channel();
+        let mut source = FileSource::open(file.path()).await.unwrap();
+        ValueSync::new(engine.clone(), ChannelDeadLetter::new(dead_sender)).run(&mut source).await;
+        assert_eq!(source.committed(), Some(2));
+        assert_eq!(engine.tuples(), vec![ContextualTuple::new(Tuple::new("user:a".into(), "viewer".into(), "doc:1".into()), None)]);
+    }
+}
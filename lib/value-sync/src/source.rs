@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+use crate::SyncError;
+
+/// record of a debezium topic, `payload` is `None` for tombstones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<Vec<u8>>,
+    pub payload: Option<Vec<u8>>,
+}
+
+/// records of `value` changes, `KafkaSource` or a stand-in for tests
+///
+/// records of a key are expected in one partition in order, as debezium keys records by primary key
+#[async_trait]
+pub trait EventSource: Send {
+    /// next record, `None` once the source is exhausted
+    ///
+    /// must be cancel safe, waiting for a record is cancelled once a batch waited long enough
+    async fn next(&mut self) -> Option<Result<Record, SyncError>>;
+
+    /// every record of `partition` up to `offset` is applied or dead-lettered, they are not delivered again
+    async fn commit(&mut self, partition: i32, offset: i64) -> Result<(), SyncError>;
+}
+
+/// records sent through a channel, exhausted when every sender is dropped
+pub struct ChannelSource {
+    receiver: mpsc::Receiver<Record>,
+    committed: HashMap<i32, i64>,
+}
+
+impl ChannelSource {
+    pub fn new(receiver: mpsc::Receiver<Record>) -> Self {
+        ChannelSource { receiver, committed: HashMap::new() }
+    }
+
+    /// last committed offset of `partition`
+    pub fn committed(&self, partition: i32) -> Option<i64> {
+        self.committed.get(&partition).copied()
+    }
+}
+
+#[async_trait]
+impl EventSource for ChannelSource {
+    async fn next(&mut self) -> Option<Result<Record, SyncError>> {
+        self.receiver.recv().await.map(Ok)
+    }
+
+    async fn commit(&mut self, partition: i32, offset: i64) -> Result<(), SyncError> {
+        self.committed.insert(partition, offset);
+        Ok(())
+    }
+}
+
+/// records of a file of json lines, every line is a record value of partition `0` and `null` is a tombstone
+///
+/// replays change events captured from the topic, offset of a record is its line index
+pub struct FileSource {
+    records: std::vec::IntoIter<Record>,
+    committed: Option<i64>,
+}
+
+impl FileSource {
+    pub async fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let records = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| Record {
+                partition: 0,
+                offset: index as i64,
+                key: None,
+                payload: (line.trim() != "null").then(|| line.as_bytes().to_vec()),
+            })
+            .collect::<Vec<_>>();
+        Ok(FileSource { records: records.into_iter(), committed: None })
+    }
+
+    /// line index of the last committed record
+    pub fn committed(&self) -> Option<i64> {
+        self.committed
+    }
+}
+
+#[async_trait]
+impl EventSource for FileSource {
+    async fn next(&mut self) -> Option<Result<Record, SyncError>> {
+        self.records.next().map(Ok)
+    }
+
+    async fn commit(&mut self, _partition: i32, offset: i64) -> Result<(), SyncError> {
+        self.committed = Some(offset);
+        Ok(())
+    }
+}
+
+/// records which can not be applied, with the reason
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    async fn send(&self, record: &Record, error: &SyncError) -> Result<(), SyncError>;
+}
+
+/// dead letters sent through a channel as `(record, error message)`
+pub struct ChannelDeadLetter {
+    sender: mpsc::UnboundedSender<(Record, String)>,
+}
+
+impl ChannelDeadLetter {
+    pub fn new(sender: mpsc::UnboundedSender<(Record, String)>) -> Self {
+        ChannelDeadLetter { sender }
+    }
+}
+
+#[async_trait]
+impl DeadLetterSink for ChannelDeadLetter {
+    async fn send(&self, record: &Record, error: &SyncError) -> Result<(), SyncError> {
+        self.sender.send((record.clone(), error.to_string())).map_err(|_| SyncError::DeadLetter("receiver dropped".to_string()))
+    }
+}
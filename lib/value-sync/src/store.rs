@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use openfga_client::{ContextualTuple, Error, InMemoryEngine, OpenFGA, Tuple};
+
+/// tuples of OpenFGA the changes are applied to, `InMemoryEngine` for tests
+///
+/// applying is idempotent, writing an existing tuple and deleting a missing one succeed so redelivered events are harmless
+#[async_trait]
+pub trait TupleStore: Send + Sync {
+    async fn apply(&self, writes: Vec<Tuple>, deletes: Vec<Tuple>) -> Result<(), Error>;
+}
+
+#[async_trait]
+impl TupleStore for OpenFGA {
+    async fn apply(&self, writes: Vec<Tuple>, deletes: Vec<Tuple>) -> Result<(), Error> {
+        let contextual = writes.iter().cloned().map(|x| ContextualTuple::new(x, None)).collect();
+        match self.write(contextual, deletes.clone()).await {
+            | Err(Error::WriteFailed(status, _, message)) if status.as_u16() == 400 && is_applied(&message) => {
+                // the transaction is rejected as a whole, the rest of it may still be pending
+                for delete in deletes {
+                    ignore_applied(self.write(Vec::new(), vec![delete]).await)?;
+                }
+                for write in writes {
+                    ignore_applied(self.write(vec![ContextualTuple::new(write, None)], Vec::new()).await)?;
+                }
+                Ok(())
+            }
+            | result => result,
+        }
+    }
+}
+
+#[async_trait]
+impl TupleStore for InMemoryEngine {
+    async fn apply(&self, writes: Vec<Tuple>, deletes: Vec<Tuple>) -> Result<(), Error> {
+        // writes are validated, nothing is deleted when they are invalid
+        self.write(writes.into_iter().map(|x| ContextualTuple::new(x, None)).collect())?;
+        self.delete(deletes);
+        Ok(())
+    }
+}
+
+/// OpenFGA rejects writing an existing tuple and deleting a missing one
+fn is_applied(message: &str) -> bool {
+    message.contains("already exists") || message.contains("does not exist")
+}
+
+fn ignore_applied(result: Result<(), Error>) -> Result<(), Error> {
+    match result {
+        | Err(Error::WriteFailed(status, _, message)) if status.as_u16() == 400 && is_applied(&message) => Ok(()),
+        | result => result,
+    }
+}